@@ -0,0 +1,98 @@
+//! Measures heap allocations and time per solver step on a tree with a fanout of 3. Run with
+//! and without the `smallvec` feature to compare.
+
+use async_trait::async_trait;
+use criterion::{criterion_group, criterion_main, Criterion};
+use futures::executor::block_on;
+use gpp_solver::{FragmentId, Problem, Solver};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    convert::Infallible,
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+const FRAGMENTS: usize = 3280;
+const FANOUT: usize = 3;
+const CONCURRENCY: NonZeroUsize = NonZeroUsize::new(4).unwrap();
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+struct TreeProblem;
+
+#[async_trait]
+impl Problem for TreeProblem {
+    type Error = Infallible;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependecies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        dependecies.extend(
+            (id.0 * FANOUT + 1..=id.0 * FANOUT + FANOUT)
+                .filter(|x| *x < FRAGMENTS)
+                .map(FragmentId),
+        );
+
+        Ok(())
+    }
+
+    async fn evaluate(&self, _: FragmentId) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+fn solve_tree() -> usize {
+    let solver = Solver::new(TreeProblem);
+    block_on(async {
+        solver.enqueue_fragment(FragmentId(0)).await;
+        let mut steps = 0;
+        while solver.step().await.unwrap() {
+            steps += 1;
+        }
+
+        steps
+    })
+}
+
+fn allocations(c: &mut Criterion) {
+    let before = ALLOCATIONS.load(Ordering::Relaxed);
+    let steps = solve_tree();
+    let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+    println!(
+        "{} allocations over {} steps ({:.2} per step)",
+        allocations,
+        steps,
+        allocations as f64 / steps as f64,
+    );
+
+    c.bench_function("tree with fanout 3", |b| {
+        b.iter(|| {
+            block_on(
+                Solver::new(TreeProblem)
+                    .solve_fragment(FragmentId(0), CONCURRENCY),
+            )
+        })
+    });
+}
+
+criterion_group!(benches, allocations);
+criterion_main!(benches);
@@ -0,0 +1,37 @@
+//! Compares membership queries on `DenseBitSet` and `BTreeSet` with 10,000 dense fragment IDs.
+//! Requires the `dense-ids` feature.
+
+use criterion::{black_box, criterion_group, criterion_main, Criterion};
+use gpp_solver::{reexported::DenseBitSet, FragmentId};
+use std::collections::BTreeSet;
+
+const IDS: usize = 10_000;
+
+fn membership(c: &mut Criterion) {
+    let dense = (0..IDS).step_by(2).map(FragmentId).fold(
+        DenseBitSet::new(),
+        |mut set, id| {
+            set.insert(id);
+            set
+        },
+    );
+    let btree = (0..IDS).step_by(2).map(FragmentId).collect::<BTreeSet<_>>();
+
+    c.bench_function("dense_bit_set_contains", |b| {
+        b.iter(|| {
+            (0..IDS)
+                .filter(|&x| dense.contains(&black_box(FragmentId(x))))
+                .count()
+        })
+    });
+    c.bench_function("btree_set_contains", |b| {
+        b.iter(|| {
+            (0..IDS)
+                .filter(|&x| btree.contains(&black_box(FragmentId(x))))
+                .count()
+        })
+    });
+}
+
+criterion_group!(benches, membership);
+criterion_main!(benches);
@@ -0,0 +1,111 @@
+//! Measures heap allocations and time to expand and solve a tree where every fragment has 10
+//! dependencies, with and without `Problem::dependencies_hint`.
+
+use async_trait::async_trait;
+use criterion::{criterion_group, criterion_main, Criterion};
+use futures::executor::block_on;
+use gpp_solver::{FragmentId, Problem, Solver};
+use std::{
+    alloc::{GlobalAlloc, Layout, System},
+    convert::Infallible,
+    num::NonZeroUsize,
+    sync::atomic::{AtomicUsize, Ordering},
+};
+
+const FRAGMENTS: usize = 11111;
+const FANOUT: usize = 10;
+const CONCURRENCY: NonZeroUsize = NonZeroUsize::new(4).unwrap();
+
+struct CountingAllocator;
+
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+
+unsafe impl GlobalAlloc for CountingAllocator {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        System.alloc(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        System.dealloc(ptr, layout)
+    }
+}
+
+#[global_allocator]
+static GLOBAL: CountingAllocator = CountingAllocator;
+
+struct TreeProblem {
+    hint: bool,
+}
+
+impl TreeProblem {
+    fn children(id: FragmentId) -> impl Iterator<Item = FragmentId> {
+        (id.0 * FANOUT + 1..=id.0 * FANOUT + FANOUT)
+            .filter(|x| *x < FRAGMENTS)
+            .map(FragmentId)
+    }
+}
+
+#[async_trait]
+impl Problem for TreeProblem {
+    type Error = Infallible;
+    type Output = ();
+
+    fn dependencies_hint(&self, id: FragmentId) -> Option<usize> {
+        if self.hint {
+            Some(Self::children(id).count())
+        } else {
+            None
+        }
+    }
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependecies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        // Push one at a time so the vector grows as it would with a real, unsized source
+        for child in Self::children(id) {
+            dependecies.push(child);
+        }
+
+        Ok(())
+    }
+
+    async fn evaluate(&self, _: FragmentId) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+fn solve_tree(hint: bool) {
+    let solver = Solver::new(TreeProblem { hint });
+    block_on(async {
+        solver.enqueue_fragment(FragmentId(0)).await;
+        solver.expand_all_dependencies(CONCURRENCY).await;
+        assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    })
+}
+
+fn dependencies_hint(c: &mut Criterion) {
+    for hint in [false, true] {
+        let before = ALLOCATIONS.load(Ordering::Relaxed);
+        solve_tree(hint);
+        let allocations = ALLOCATIONS.load(Ordering::Relaxed) - before;
+        println!(
+            "{} allocations {} hint ({:.2} per fragment)",
+            allocations,
+            if hint { "with" } else { "without" },
+            allocations as f64 / FRAGMENTS as f64,
+        );
+    }
+
+    c.bench_function("tree with fanout 10 without hint", |b| {
+        b.iter(|| solve_tree(false))
+    });
+    c.bench_function("tree with fanout 10 with hint", |b| {
+        b.iter(|| solve_tree(true))
+    });
+}
+
+criterion_group!(benches, dependencies_hint);
+criterion_main!(benches);
@@ -0,0 +1,79 @@
+//! Compares evaluating 100 independent fragments with a single `Solver::step_batch` call against
+//! 100 sequential `Solver::step` calls, for a problem where each evaluation awaits a short timer,
+//! like a fast cache or network lookup would.
+
+use async_trait::async_trait;
+use criterion::{criterion_group, criterion_main, Criterion};
+use gpp_solver::{FragmentId, Problem, Solver};
+use std::{convert::Infallible, time::Duration};
+use tokio::runtime::{Builder, Runtime};
+
+const FRAGMENTS: usize = 100;
+const DELAY: Duration = Duration::from_micros(50);
+
+// Fragments without dependencies that take `DELAY` to evaluate
+struct FastProblem;
+
+#[async_trait]
+impl Problem for FastProblem {
+    type Error = Infallible;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        _: FragmentId,
+        _: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn evaluate(&self, _: FragmentId) -> Result<(), Self::Error> {
+        tokio::time::sleep(DELAY).await;
+
+        Ok(())
+    }
+}
+
+fn solver(runtime: &Runtime) -> Solver<FastProblem> {
+    let solver = Solver::new(FastProblem);
+    runtime.block_on(async {
+        for id in 0..FRAGMENTS {
+            solver.enqueue_fragment(FragmentId(id)).await;
+        }
+    });
+
+    solver
+}
+
+fn step_batch(c: &mut Criterion) {
+    let runtime = Builder::new_current_thread().enable_time().build().unwrap();
+
+    c.bench_function("100 sequential steps", |b| {
+        b.iter_with_setup(
+            || solver(&runtime),
+            |solver| {
+                runtime.block_on(async {
+                    for _ in 0..FRAGMENTS {
+                        assert!(solver.step().await.unwrap());
+                    }
+                })
+            },
+        )
+    });
+    c.bench_function("step_batch(100)", |b| {
+        b.iter_with_setup(
+            || solver(&runtime),
+            |solver| {
+                runtime.block_on(async {
+                    assert_eq!(
+                        solver.step_batch(FRAGMENTS).await.unwrap(),
+                        FRAGMENTS
+                    );
+                })
+            },
+        )
+    });
+}
+
+criterion_group!(benches, step_batch);
+criterion_main!(benches);
@@ -0,0 +1,130 @@
+use crate::{
+    reexported::{mem, NonZeroUsize, Vec},
+    Problem, Solver,
+};
+use std::time::{Duration, Instant};
+
+/// Timings of the measured rounds of a [`Solver::benchmark_problem`] call.
+#[derive(Clone, Copy, Debug, PartialEq)]
+pub struct BenchmarkReport {
+    /// Duration of the fastest round.
+    pub min_duration: Duration,
+
+    /// Duration of the slowest round.
+    pub max_duration: Duration,
+
+    /// Mean duration of all rounds.
+    pub mean_duration: Duration,
+
+    /// Duration that 99% of the rounds did not exceed.
+    pub p99_duration: Duration,
+
+    /// Number of [`Problem::evaluate`] calls per second, over all rounds.
+    pub throughput_fragments_per_sec: f64,
+}
+
+impl<P> Solver<P>
+where
+    P: Problem,
+{
+    /// Run the solver `warmup_rounds + measure_rounds` times from its current state and report
+    /// how long the last `measure_rounds` took. Each round starts from the state the solver was in
+    /// when this method was called, and the solver is left in that state afterwards, so
+    /// benchmarks can be repeated, for example to pick the best concurrency for a [`Problem`]:
+    ///
+    /// ```
+    /// # use gpp_solver::{problems::HashMapProblem, FragmentId, Solver};
+    /// # use std::num::NonZeroUsize;
+    /// # futures::executor::block_on(async {
+    /// let solver = Solver::new(HashMapProblem::from_edges(
+    ///     (1..100).map(|id| (FragmentId(0), FragmentId(id))),
+    /// ));
+    /// solver.enqueue_fragment(FragmentId(0)).await;
+    ///
+    /// let mut best = None;
+    /// for concurrency in [1, 2, 4, 8] {
+    ///     let concurrency = NonZeroUsize::new(concurrency).unwrap();
+    ///     let report = solver.benchmark_problem(concurrency, 2, 10).await.unwrap();
+    ///     if best.map_or(true, |(_, mean)| report.mean_duration < mean) {
+    ///         best = Some((concurrency, report.mean_duration));
+    ///     }
+    /// }
+    /// let (concurrency, _) = best.unwrap();
+    ///
+    /// let punted = solver.run(concurrency).await.unwrap().punted;
+    /// assert!(punted.is_empty());
+    /// # });
+    /// ```
+    ///
+    /// Returns an error, after restoring the solver state, if any round returns an error.
+    ///
+    /// Only available with the `benchmarking` feature.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `measure_rounds` is `0`.
+    pub async fn benchmark_problem(
+        &self,
+        concurrency: NonZeroUsize,
+        warmup_rounds: usize,
+        measure_rounds: usize,
+    ) -> Result<BenchmarkReport, P::Error> {
+        assert!(measure_rounds > 0, "at least one round must be measured");
+
+        let run_lock = self.run_lock.lock().await;
+
+        let (snapshot, outputs) = {
+            let mut state = self.state.lock().await;
+
+            // Forks don't keep evaluation outputs, so those are put back separately
+            (state.fork(), mem::take(&mut state.outputs))
+        };
+        let mut durations = Vec::with_capacity(measure_rounds);
+        let mut evaluations = 0;
+        let res = async {
+            for round in 0..warmup_rounds + measure_rounds {
+                *self.state.lock().await = snapshot.fork();
+
+                let started_evaluations = self.evaluations_attempted();
+                let run = self.begin_locked_run(&run_lock).await;
+                let started = Instant::now();
+                let res = self.run_steps(run.id(), concurrency).await;
+                let elapsed = started.elapsed();
+                let _ = run.finish().await;
+                res?;
+
+                if round >= warmup_rounds {
+                    durations.push(elapsed);
+                    evaluations +=
+                        self.evaluations_attempted() - started_evaluations;
+                }
+            }
+
+            Ok(())
+        }
+        .await;
+        {
+            let mut state = self.state.lock().await;
+            *state = snapshot;
+            state.outputs = outputs;
+        }
+        res?;
+
+        durations.sort();
+        let total = durations.iter().sum::<Duration>();
+        // Nearest-rank percentile
+        let p99 = (durations.len() * 99).div_ceil(100) - 1;
+
+        Ok(BenchmarkReport {
+            min_duration: durations[0],
+            max_duration: durations[durations.len() - 1],
+            mean_duration: total / durations.len() as u32,
+            p99_duration: durations[p99],
+            throughput_fragments_per_sec: if total.is_zero() {
+                0.0
+            } else {
+                evaluations as f64 / total.as_secs_f64()
+            },
+        })
+    }
+}
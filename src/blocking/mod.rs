@@ -0,0 +1,218 @@
+use crate::{
+    reexported::{Box, NonZeroUsize, Vec},
+    FragmentId, Problem, SolveReport, Solver, Status,
+};
+use async_trait::async_trait;
+use futures::executor::block_on;
+use std::{
+    sync::{Condvar, Mutex},
+    thread,
+};
+
+/// Synchronous version of [`Problem`] for use with [`BlockingSolver`].
+pub trait SyncProblem {
+    /// Error type for [`SyncProblem::direct_dependencies`] and [`SyncProblem::evaluate`].
+    type Error;
+
+    /// Synchronous version of [`Problem::direct_dependencies`].
+    fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error>;
+
+    /// Synchronous version of [`Problem::evaluate`].
+    fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error>;
+}
+
+/// Adapter that implements [`Problem`] for a [`SyncProblem`]. See [`BlockingSolver::solver`].
+pub struct SyncAdapter<P>(P);
+
+#[async_trait]
+impl<P> Problem for SyncAdapter<P>
+where
+    P: SyncProblem + Sync,
+    P::Error: Send,
+{
+    type Error = P::Error;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        self.0.direct_dependencies(id, dependencies)
+    }
+
+    async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        self.0.evaluate(id)
+    }
+}
+
+/// Blocking adapter around [`Solver`] for a [`SyncProblem`]. No async runtime is needed: each
+/// method blocks the current thread until the wrapped [`Solver`] is done. The solver itself is
+/// not reimplemented, so its state is still guarded by the `futures` locks.
+///
+/// Runs evaluate fragments in up to `concurrency` threads at a time, which are spawned when the
+/// run starts and joined before it returns.
+pub struct BlockingSolver<P> {
+    inner: Solver<SyncAdapter<P>>,
+}
+
+// State shared between the threads of a run
+struct Workers<E> {
+    // Number of threads running a step
+    active: usize,
+    // First error returned by a step, which stops the run
+    error: Option<E>,
+}
+
+impl<P> BlockingSolver<P>
+where
+    P: SyncProblem + Sync,
+    P::Error: Send,
+{
+    /// Create a new [`BlockingSolver`] instance for a [`SyncProblem`].
+    pub fn new(problem_instance: P) -> Self {
+        Self {
+            inner: Solver::new(SyncAdapter(problem_instance)),
+        }
+    }
+
+    /// Get the wrapped [`Solver`].
+    pub fn solver(&self) -> &Solver<SyncAdapter<P>> {
+        &self.inner
+    }
+
+    /// Consume `self` and return the wrapped [`SyncProblem`] instance.
+    pub fn into_problem_instance(self) -> P {
+        self.inner.into_problem_instance().0
+    }
+
+    /// Synchronous version of [`Solver::enqueue_fragment`].
+    pub fn enqueue_fragment(&self, id: FragmentId) -> &Self {
+        block_on(self.inner.enqueue_fragment(id));
+
+        self
+    }
+
+    /// Synchronous version of [`Solver::assume_evaluated`].
+    pub fn assume_evaluated(&self, id: FragmentId) -> &Self {
+        block_on(self.inner.assume_evaluated(id));
+
+        self
+    }
+
+    /// Synchronous version of [`Solver::step`].
+    #[must_use = "must check whether evaluation succeeded"]
+    pub fn step(&self) -> Result<bool, P::Error> {
+        block_on(self.inner.step())
+    }
+
+    /// Synchronous version of [`Solver::run`].
+    #[must_use = "must check whether evaluation succeeded"]
+    pub fn run(
+        &self,
+        concurrency: NonZeroUsize,
+    ) -> Result<SolveReport, P::Error> {
+        self.run_threads(concurrency)
+    }
+
+    /// Synchronous version of [`Solver::solve_fragment`].
+    pub fn solve_fragment(
+        &self,
+        id: FragmentId,
+        concurrency: NonZeroUsize,
+    ) -> Result<SolveReport, P::Error> {
+        block_on(self.inner.enqueue_fragment(id));
+
+        self.run_threads(concurrency)
+    }
+
+    /// Synchronous version of [`Solver::status`].
+    pub fn status(&self) -> Status {
+        block_on(self.inner.status())
+    }
+
+    /// Synchronous version of [`Solver::is_evaluated`].
+    pub fn is_evaluated(&self, id: FragmentId) -> bool {
+        block_on(self.inner.is_evaluated(id))
+    }
+
+    /// Synchronous version of [`Solver::punted_iter`].
+    pub fn punted_iter(&self) -> Vec<FragmentId> {
+        block_on(self.inner.punted_iter())
+    }
+
+    /// Synchronous version of [`Solver::topological_order`].
+    pub fn topological_order(&self) -> Vec<FragmentId> {
+        block_on(self.inner.topological_order())
+    }
+
+    fn run_threads(
+        &self,
+        concurrency: NonZeroUsize,
+    ) -> Result<SolveReport, P::Error> {
+        let run = block_on(self.inner.begin_run());
+        let workers = Mutex::new(Workers {
+            active: 0,
+            error: None,
+        });
+        let idle = Condvar::new();
+        thread::scope(|scope| {
+            for _ in 0..concurrency.get() {
+                scope.spawn(|| self.work(run.id(), &workers, &idle));
+            }
+        });
+        let report = block_on(run.finish());
+
+        match workers.into_inner().unwrap().error {
+            Some(err) => Err(err),
+            None => Ok(report),
+        }
+    }
+
+    // Run steps until the run fails or there is nothing left to do
+    fn work(
+        &self,
+        run: usize,
+        workers: &Mutex<Workers<P::Error>>,
+        idle: &Condvar,
+    ) {
+        loop {
+            let mut guard = workers.lock().unwrap();
+            loop {
+                if guard.error.is_some() {
+                    return;
+                }
+                if block_on(self.inner.can_make_progress()) {
+                    break;
+                }
+                // Steps still running may queue more fragments when they finish
+                if guard.active == 0 {
+                    if self.inner.config.auto_enqueue_missing
+                        && !block_on(self.inner.missing_fragments()).is_empty()
+                    {
+                        continue;
+                    }
+                    idle.notify_all();
+
+                    return;
+                }
+                guard = idle.wait(guard).unwrap();
+            }
+            guard.active += 1;
+            drop(guard);
+
+            let res = block_on(self.inner.run_step(run));
+
+            let mut guard = workers.lock().unwrap();
+            guard.active -= 1;
+            if let Err((_, err)) = res {
+                guard.error.get_or_insert(err);
+            }
+            idle.notify_all();
+        }
+    }
+}
@@ -0,0 +1,133 @@
+use crate::{
+    reexported::{NonZeroUsize, Vec},
+    FragmentId, Problem, Progress, SolveReport, Solver, State,
+};
+use core::ops::ControlFlow;
+use futures::stream::FuturesUnordered;
+
+/// Progress of a [`Solver`] that can be saved and later restored with
+/// [`Solver::restore_from_snapshot`], such as to resume a long build after a crash. See
+/// [`Solver::run_with_checkpointing`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[must_use]
+pub struct SolverCheckpoint {
+    /// All solved fragments, in the order they were solved.
+    pub solved: Vec<FragmentId>,
+
+    /// Fragments that were queued, punted, or being evaluated, sorted by ID.
+    pub pending: Vec<FragmentId>,
+}
+
+impl SolverCheckpoint {
+    fn new(state: &State) -> Self {
+        let mut pending = state
+            .to_solve
+            .iter()
+            .chain(state.punted.keys())
+            .chain(&state.in_flight)
+            .copied()
+            .collect::<Vec<_>>();
+        pending.sort_unstable();
+
+        Self {
+            solved: state.solved_order.clone(),
+            pending,
+        }
+    }
+}
+
+impl<P> Solver<P>
+where
+    P: Problem,
+{
+    /// Like [`Solver::run`], but calls `save` with a [`SolverCheckpoint`] after every
+    /// `checkpoint_every` successful evaluations. `save` is called without holding any solver
+    /// locks.
+    ///
+    /// Returns a [`SolveReport`] once all fragments that can be evaluated were evaluated, or the
+    /// first evaluation error.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `checkpoint_every` is zero.
+    pub async fn run_with_checkpointing<F>(
+        &self,
+        concurrency: NonZeroUsize,
+        checkpoint_every: usize,
+        save: F,
+    ) -> Result<SolveReport, P::Error>
+    where
+        F: Fn(&SolverCheckpoint) + Send + Sync,
+    {
+        assert!(checkpoint_every > 0, "checkpoint_every must not be zero");
+
+        let run = self.begin_run().await;
+        let res = self
+            .run_checkpointed_steps(
+                run.id(),
+                concurrency,
+                checkpoint_every,
+                save,
+            )
+            .await;
+        let report = run.finish().await;
+        res?;
+
+        Ok(report)
+    }
+
+    async fn run_checkpointed_steps<F>(
+        &self,
+        run: usize,
+        concurrency: NonZeroUsize,
+        checkpoint_every: usize,
+        save: F,
+    ) -> Result<(), P::Error>
+    where
+        F: Fn(&SolverCheckpoint),
+    {
+        let mut evaluated = 0;
+        let mut steps = FuturesUnordered::new();
+        loop {
+            let flow = self
+                .drive_steps(
+                    &mut steps,
+                    concurrency,
+                    || self.run_step(run),
+                    |progress| {
+                        if let Progress::Evaluated(_) = progress {
+                            evaluated += 1;
+                            if evaluated % checkpoint_every == 0 {
+                                return ControlFlow::Break(Ok(()));
+                            }
+                        }
+
+                        ControlFlow::Continue(())
+                    },
+                    |_, err| ControlFlow::Break(Err(err)),
+                )
+                .await;
+            match flow {
+                ControlFlow::Break(Ok(())) => {
+                    let checkpoint =
+                        SolverCheckpoint::new(&*self.state.lock().await);
+                    save(&checkpoint);
+                }
+                ControlFlow::Break(Err(err)) => return Err(err),
+                ControlFlow::Continue(()) => return Ok(()),
+            }
+        }
+    }
+
+    /// Restore the progress saved in a [`SolverCheckpoint`], usually into a new solver. Solved
+    /// fragments are [assumed to be evaluated](Solver::assume_evaluated) and pending fragments
+    /// are enqueued again, so the next [run](Solver::run) picks up where the checkpoint left off.
+    pub async fn restore_from_snapshot(&self, checkpoint: &SolverCheckpoint) {
+        self.mark_all_solved(checkpoint.solved.iter().copied())
+            .await;
+        for &id in &checkpoint.pending {
+            self.enqueue_fragment(id).await;
+        }
+    }
+}
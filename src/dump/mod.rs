@@ -0,0 +1,124 @@
+use crate::{
+    reexported::{String, Vec},
+    FragmentId, Solver, State,
+};
+
+feature_cfg! {
+    for "std";
+
+    use std::fmt::Write;
+
+    impl<P> Solver<P> {
+        /// Get a human-readable, multi-line representation of the internal solver state. Meant for
+        /// bug reports and debugging.
+        ///
+        /// All fragment lists are sorted so the output is stable across runs.
+        pub async fn dump_state(&self) -> String {
+            let snapshot = StateSnapshot::new(&*self.state.lock().await);
+            let mut out = String::new();
+
+            writeln!(out, "to_solve: {}", fmt_ids(&snapshot.to_solve)).unwrap();
+            writeln!(out, "punted:").unwrap();
+            for punted in &snapshot.punted {
+                writeln!(out, "  {} (pending on {})", punted.id.0, punted.pending)
+                    .unwrap();
+            }
+            writeln!(out, "solved: {}", fmt_ids(&snapshot.solved)).unwrap();
+            writeln!(out, "pending_on:").unwrap();
+            for pending_on in &snapshot.pending_on {
+                writeln!(
+                    out,
+                    "  {} <- {}",
+                    pending_on.id.0,
+                    fmt_ids(&pending_on.dependents),
+                )
+                .unwrap();
+            }
+
+            out
+        }
+    }
+
+    fn fmt_ids(ids: &[FragmentId]) -> String {
+        let ids = ids
+            .iter()
+            .map(|id| id.0.to_string())
+            .collect::<Vec<_>>()
+            .join(", ");
+
+        format!("[{}]", ids)
+    }
+}
+
+feature_cfg! {
+    for "serde";
+
+    impl<P> Solver<P> {
+        /// Get a JSON representation of the internal solver state. Meant for bug reports and
+        /// tooling.
+        ///
+        /// All fragment lists are sorted so the output is stable across runs.
+        pub async fn dump_state_json(&self) -> String {
+            let snapshot = StateSnapshot::new(&*self.state.lock().await);
+
+            serde_json::to_string(&snapshot)
+                .expect("state snapshots are always serializable")
+        }
+    }
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct StateSnapshot {
+    to_solve: Vec<FragmentId>,
+    punted: Vec<PuntedSnapshot>,
+    solved: Vec<FragmentId>,
+    pending_on: Vec<PendingOnSnapshot>,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct PuntedSnapshot {
+    id: FragmentId,
+    pending: usize,
+}
+
+#[cfg_attr(feature = "serde", derive(serde::Serialize))]
+struct PendingOnSnapshot {
+    id: FragmentId,
+    dependents: Vec<FragmentId>,
+}
+
+impl StateSnapshot {
+    fn new(state: &State) -> Self {
+        let mut to_solve = state.to_solve.iter().copied().collect::<Vec<_>>();
+        to_solve.sort_unstable();
+
+        let mut punted = state
+            .punted
+            .iter()
+            .map(|(&id, &pending)| PuntedSnapshot { id, pending })
+            .collect::<Vec<_>>();
+        punted.sort_unstable_by_key(|x| x.id);
+
+        let mut solved = state.solved_iter().collect::<Vec<_>>();
+        solved.sort_unstable();
+
+        let mut pending_on = state
+            .pending_on
+            .iter()
+            .map(|(&id, dependents)| {
+                let mut dependents = dependents.to_vec();
+                dependents.sort_unstable();
+
+                PendingOnSnapshot { id, dependents }
+            })
+            .collect::<Vec<_>>();
+        pending_on.sort_unstable_by_key(|x| x.id);
+
+        Self {
+            to_solve,
+            punted,
+            solved,
+            pending_on,
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use crate::{reexported::Vec, FragmentId, SolveReport, Solver, Status};
+use std::time::Duration;
+use tokio::sync::broadcast;
+
+pub use self::pipe::PipeHandle;
+
+mod pipe;
+
+/// Default capacity of the event channel. See [`Solver::with_event_capacity`].
+pub const DEFAULT_EVENT_CAPACITY: usize = 1024;
+
+/// Event emitted by a [`Solver`] while solving. See [`Solver::subscribe_events`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum SolverEvent {
+    /// A fragment was queued to be considered for evaluation.
+    FragmentQueued {
+        /// The queued fragment.
+        id: FragmentId,
+    },
+
+    /// The direct dependencies of a fragment were fetched.
+    DependenciesFetched {
+        /// The fragment whose dependencies were fetched.
+        id: FragmentId,
+
+        /// Number of direct dependencies, including
+        /// [ordering constraints](Solver::add_ordering_constraint).
+        dep_count: usize,
+    },
+
+    /// A fragment is about to be evaluated.
+    FragmentEvaluating {
+        /// The fragment being evaluated.
+        id: FragmentId,
+    },
+
+    /// A fragment was successfully evaluated.
+    FragmentSolved {
+        /// The evaluated fragment.
+        id: FragmentId,
+
+        /// How long [`Problem::evaluate`](crate::Problem::evaluate) took.
+        duration: Duration,
+    },
+
+    /// A fragment was punted until its dependencies are evaluated.
+    FragmentPunted {
+        /// The punted fragment.
+        id: FragmentId,
+
+        /// Number of dependencies that were not evaluated yet.
+        pending_deps: usize,
+    },
+
+    /// A run finished with fragments that are part of at least one cycle.
+    CycleFinalized {
+        /// All punted fragments. See [`Solver::punted_iter`].
+        members: Vec<FragmentId>,
+    },
+
+    /// A run started.
+    SolverStarted,
+
+    /// A run finished.
+    SolverFinished {
+        /// [`Status`] of the solver after the run.
+        status: Status,
+
+        /// Full report of the run.
+        report: SolveReport,
+    },
+}
+
+impl<P> Solver<P> {
+    /// Set the capacity of the event channel. Receivers that fall more than `capacity` events
+    /// behind will miss the oldest events. The default is [`DEFAULT_EVENT_CAPACITY`].
+    ///
+    /// Existing receivers will not get any more events.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `capacity` is zero.
+    pub fn with_event_capacity(mut self, capacity: usize) -> Self {
+        self.events = broadcast::channel(capacity).0;
+
+        self
+    }
+
+    /// Subscribe to all [`SolverEvent`]s emitted from now on.
+    pub fn subscribe_events(&self) -> broadcast::Receiver<SolverEvent> {
+        self.events.subscribe()
+    }
+
+    pub(crate) fn emit(&self, event: SolverEvent) {
+        // Sending only fails if there are no receivers
+        let _ = self.events.send(event);
+    }
+}
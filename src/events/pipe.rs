@@ -0,0 +1,81 @@
+use crate::{
+    reexported::{task, Box, Future, Pin, Vec},
+    FragmentId, Problem, Solver, SolverEvent,
+};
+use tokio::sync::broadcast::error::RecvError;
+
+/// Future that forwards fragments solved by one [`Solver`] to another. See
+/// [`Solver::pipe_into`].
+#[must_use = "fragments are only forwarded while the handle is polled"]
+pub struct PipeHandle<'a> {
+    forward: Pin<Box<dyn Future<Output = ()> + Send + 'a>>,
+}
+
+impl Future for PipeHandle<'_> {
+    type Output = ();
+
+    fn poll(
+        mut self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Self::Output> {
+        self.forward.as_mut().poll(cx)
+    }
+}
+
+impl<P> Solver<P>
+where
+    P: Problem + Sync,
+{
+    /// Enqueue `mapper(id)` into `other` for every fragment `id` evaluated by `self` from now on,
+    /// such as to feed the results of one phase of a build into the next one. Fragments
+    /// [assumed to be evaluated](Solver::assume_evaluated) are not forwarded.
+    ///
+    /// Fragments are only forwarded while the returned [`PipeHandle`] is polled, so await it
+    /// alongside the run of `self` or spawn it. It resolves once the next run of `self` finishes,
+    /// after forwarding everything evaluated during that run. Dropping it stops forwarding.
+    pub async fn pipe_into<'a, P2, F>(
+        &'a self,
+        other: &'a Solver<P2>,
+        mapper: F,
+    ) -> PipeHandle<'a>
+    where
+        P2: Problem + Sync,
+        F: Fn(FragmentId) -> FragmentId + Send + 'a,
+    {
+        let mut receiver = self.subscribe_events();
+        let mut forwarded = self.state.lock().await.solved_order.len();
+
+        PipeHandle {
+            forward: Box::pin(async move {
+                loop {
+                    // Events are only used to know when to look for newly solved fragments, so
+                    // lagging behind doesn't lose anything
+                    let finished = match receiver.recv().await {
+                        Ok(SolverEvent::SolverFinished { .. })
+                        | Err(RecvError::Closed) => true,
+                        Ok(_) | Err(RecvError::Lagged(_)) => false,
+                    };
+
+                    let solved = {
+                        let state = self.state.lock().await;
+                        let solved = state.solved_order[forwarded..]
+                            .iter()
+                            .copied()
+                            .filter(|id| !state.assumed.contains(id))
+                            .collect::<Vec<_>>();
+                        forwarded = state.solved_order.len();
+
+                        solved
+                    };
+                    for id in solved {
+                        other.enqueue_fragment(mapper(id)).await;
+                    }
+
+                    if finished {
+                        break;
+                    }
+                }
+            }),
+        }
+    }
+}
@@ -0,0 +1,109 @@
+use crate::{
+    reexported::{format, Map, String, Vec},
+    DetailedSummary, FragmentId, FragmentLifecycle, Solver, Status,
+};
+
+/// Immutable copy of the state of a [`Solver`] at one point in time, returned by
+/// [`Solver::freeze`].
+///
+/// All queries are synchronous and never touch the solver, so a snapshot can be shared between
+/// threads and inspected while the solver keeps running, such as for progress reporting. Queries
+/// always reflect the solver as it was when the snapshot was taken.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FrozenSolverState {
+    // Every fragment not in `FragmentLifecycle::Unknown`, sorted by ID
+    fragments: Vec<(FragmentId, FragmentLifecycle)>,
+    status: Status,
+    summary: DetailedSummary,
+}
+
+impl FrozenSolverState {
+    /// Get the [lifecycle state](FragmentLifecycle) of a fragment. See
+    /// [`Solver::fragment_state`].
+    pub fn fragment_state(&self, id: FragmentId) -> FragmentLifecycle {
+        self.fragments
+            .binary_search_by_key(&id, |&(id, _)| id)
+            .map_or(FragmentLifecycle::Unknown, |index| self.fragments[index].1)
+    }
+
+    /// Check whether a fragment has been evaluated, or assumed to be evaluated. See
+    /// [`Solver::is_evaluated`].
+    pub fn is_evaluated(&self, id: FragmentId) -> bool {
+        matches!(
+            self.fragment_state(id),
+            FragmentLifecycle::Evaluated | FragmentLifecycle::Skipped,
+        )
+    }
+
+    /// Check whether a fragment is punted.
+    pub fn is_punted(&self, id: FragmentId) -> bool {
+        matches!(self.fragment_state(id), FragmentLifecycle::Punted { .. },)
+    }
+
+    /// Get the number of fragments the solver knew about. See [`Solver::fragment_ids`].
+    pub fn fragment_count(&self) -> usize {
+        self.fragments.len()
+    }
+
+    /// Get all fragments the solver knew about, sorted by ID. See [`Solver::fragment_ids`].
+    pub fn fragment_ids(&self) -> Vec<FragmentId> {
+        self.fragments.iter().map(|&(id, _)| id).collect()
+    }
+
+    /// Like [`FrozenSolverState::fragment_ids`], but grouped by lifecycle state. See
+    /// [`Solver::fragment_ids_by_state`].
+    pub fn fragment_ids_by_state(
+        &self,
+    ) -> Map<FragmentLifecycle, Vec<FragmentId>> {
+        let mut groups = Map::<_, Vec<_>>::new();
+        for &(id, lifecycle) in &self.fragments {
+            groups.entry(lifecycle).or_default().push(id);
+        }
+
+        groups
+    }
+
+    /// Get all punted fragments, sorted by ID. See [`Solver::punted_iter`].
+    pub fn punted_iter(&self) -> Vec<FragmentId> {
+        self.fragments
+            .iter()
+            .filter(|(_, lifecycle)| {
+                matches!(lifecycle, FragmentLifecycle::Punted { .. })
+            })
+            .map(|&(id, _)| id)
+            .collect()
+    }
+
+    /// Get the [`Status`] of the solver. See [`Solver::status`].
+    pub fn status(&self) -> Status {
+        self.status
+    }
+
+    /// Get the number of fragments in each state. See [`Solver::detailed_summary`].
+    pub fn detailed_summary(&self) -> DetailedSummary {
+        self.summary
+    }
+
+    /// Get a one-line, human-readable summary of the solver progress. See [`Solver::summary`].
+    pub fn summary(&self) -> String {
+        format!("{}", self.summary)
+    }
+}
+
+impl<P> Solver<P> {
+    /// Take a [`FrozenSolverState`] snapshot of the current state. The state lock is only held
+    /// while copying, so this can be called while the solver is running without blocking it.
+    pub async fn freeze(&self) -> FrozenSolverState {
+        let state = self.state.lock().await;
+
+        FrozenSolverState {
+            fragments: state
+                .known_fragments()
+                .into_iter()
+                .map(|id| (id, state.lifecycle(id)))
+                .collect(),
+            status: state.status(),
+            summary: state.detailed_summary(),
+        }
+    }
+}
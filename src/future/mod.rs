@@ -0,0 +1,147 @@
+use crate::{
+    reexported::{
+        atomic::Ordering,
+        task::{Context, Poll},
+        Box, Future, MutexGuard, Pin,
+    },
+    FragmentId, Problem, SolveReport, Solver,
+};
+use futures::future::BoxFuture;
+
+/// Future returned by [`Solver::solving`].
+///
+/// Resolves once all root fragments and their transitive dependencies are either evaluated or
+/// proven to be part of at least one cycle.
+pub struct SolverFuture<P>
+where
+    P: Problem,
+{
+    inner: BoxFuture<'static, Result<SolveReport, P::Error>>,
+}
+
+impl<P> Future for SolverFuture<P>
+where
+    P: Problem,
+{
+    type Output = Result<SolveReport, P::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.get_mut().inner.as_mut().poll(cx)
+    }
+}
+
+/// Future returned by [`Solver::run`]. Resolves to a [`SolveReport`] for the run.
+///
+/// Dropping the handle before it resolves cancels the run. Like any dropped future, evaluations
+/// still in progress are dropped with it instead of completing, and no new ones start. Their
+/// fragments are queued again by the next step, so the [`Solver`] can be run again afterwards.
+/// A handle that is still waiting for another run to finish can be dropped without affecting
+/// that run.
+#[must_use = "futures do nothing unless awaited"]
+pub struct SolverHandle<'a, P>
+where
+    P: Problem,
+{
+    inner: BoxFuture<'a, Result<SolveReport, P::Error>>,
+}
+
+impl<'a, P> SolverHandle<'a, P>
+where
+    P: Problem,
+{
+    pub(crate) fn new(
+        inner: BoxFuture<'a, Result<SolveReport, P::Error>>,
+    ) -> Self {
+        Self { inner }
+    }
+}
+
+impl<P> Future for SolverHandle<'_, P>
+where
+    P: Problem,
+{
+    type Output = Result<SolveReport, P::Error>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.get_mut().inner.as_mut().poll(cx)
+    }
+}
+
+// A run in progress, from `Solver::begin_run` until `RunGuard::finish`. Holds the run lock so runs
+// are serialized, unless the caller holds it itself. Dropping the guard before `finish` cancels
+// the run: the next step or run queues the fragments the run's steps left in flight again, as
+// those steps were dropped with it
+pub(crate) struct RunGuard<'a, P> {
+    solver: &'a Solver<P>,
+    id: usize,
+    finished: bool,
+    _lock: Option<MutexGuard<'a, ()>>,
+}
+
+impl<'a, P> RunGuard<'a, P> {
+    pub(crate) fn new(
+        solver: &'a Solver<P>,
+        id: usize,
+        lock: Option<MutexGuard<'a, ()>>,
+    ) -> Self {
+        Self {
+            solver,
+            id,
+            finished: false,
+            _lock: lock,
+        }
+    }
+
+    // ID steps of this run are tagged with, see `Solver::run_step`
+    pub(crate) fn id(&self) -> usize {
+        self.id
+    }
+}
+
+impl<P> RunGuard<'_, P>
+where
+    P: Problem,
+{
+    // Notify the problem instance and event subscribers that the run finished. Fragments left in
+    // flight by steps of the run that were dropped, such as when another step failed, are queued
+    // again first
+    pub(crate) async fn finish(mut self) -> SolveReport {
+        self.solver
+            .requeue_in_flight(self.id, &mut *self.solver.state.lock().await);
+        let report = self.solver.finish_run().await;
+        self.finished = true;
+
+        report
+    }
+}
+
+impl<P> Drop for RunGuard<'_, P> {
+    fn drop(&mut self) {
+        if !self.finished {
+            self.solver.cancelled_run.store(self.id, Ordering::Release);
+            self.solver.running.store(false, Ordering::Release);
+        }
+    }
+}
+
+impl<P> Solver<P>
+where
+    P: Problem + Send + Sync + 'static,
+    P::Error: Send,
+{
+    /// Consume `self` and return a [`SolverFuture`] that enqueues all of `roots` and then runs
+    /// the solver to completion. Concurrency is taken from the solver's
+    /// [`SolverConfig`](crate::SolverConfig).
+    ///
+    /// This is equivalent to [`Solver::solve_fragments`], but can be awaited as a single
+    /// expression.
+    pub fn solving(self, roots: &[FragmentId]) -> SolverFuture<P> {
+        let roots = roots.to_vec();
+
+        SolverFuture {
+            inner: Box::pin(async move {
+                self.solve_fragments(roots, self.config.concurrency).await
+            }),
+        }
+    }
+}
@@ -0,0 +1,256 @@
+use crate::{reexported::Error, FragmentId, Status};
+use core::{fmt, future::Future};
+use heapless::{Deque, FnvIndexMap, FnvIndexSet, Vec};
+
+/// Version of [`Problem`](crate::Problem) for use with [`HeaplessSolver`]. Fragments can have at
+/// most `N` direct dependencies.
+///
+/// Methods return futures directly instead of going through [`mod@async_trait`], so they can be
+/// implemented with `async fn` without allocating.
+pub trait HeaplessProblem<const N: usize> {
+    /// Error type for [`HeaplessProblem::evaluate`].
+    type Error;
+
+    /// Get all direct dependencies of a fragment. See
+    /// [`Problem::direct_dependencies`](crate::Problem::direct_dependencies).
+    fn direct_dependencies(
+        &self,
+        id: FragmentId,
+    ) -> impl Future<Output = Vec<FragmentId, N>>;
+
+    /// Evaluate a fragment. See [`Problem::evaluate`](crate::Problem::evaluate).
+    fn evaluate(
+        &self,
+        id: FragmentId,
+    ) -> impl Future<Output = Result<(), Self::Error>>;
+}
+
+/// Error returned by [`HeaplessSolver`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum SolverError<E> {
+    /// More than `MAX_FRAGMENTS` fragments would be queued, punted, solved, or waiting on the
+    /// same fragment.
+    CapacityExceeded,
+
+    /// [`HeaplessProblem::evaluate`] returned an error.
+    Evaluation(E),
+}
+
+impl<E> fmt::Display for SolverError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::CapacityExceeded => f.write_str("solver capacity exceeded"),
+            Self::Evaluation(err) => write!(f, "evaluation failed: {}", err),
+        }
+    }
+}
+
+impl<E> Error for SolverError<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::CapacityExceeded => None,
+            Self::Evaluation(err) => Some(err),
+        }
+    }
+}
+
+/// Solver for memory-constrained targets that keeps all of its state in fixed-capacity
+/// collections. At most `MAX_FRAGMENTS` fragments can be queued, punted, or solved at the same
+/// time, which must be a power of two greater than one.
+///
+/// The algorithm is the same as [`Solver`](crate::Solver)'s, but there are no locks and
+/// fragments are always evaluated one at a time. Memory usage is fixed and quadratic on
+/// `MAX_FRAGMENTS`, as every fragment can have up to `MAX_FRAGMENTS` punted dependents.
+pub struct HeaplessSolver<P, const MAX_FRAGMENTS: usize> {
+    problem_instance: P,
+    to_solve: Deque<FragmentId, MAX_FRAGMENTS>,
+    // Same fragments as `to_solve`, to check whether a fragment is queued without a linear scan
+    queued: FnvIndexSet<FragmentId, MAX_FRAGMENTS>,
+    pending_on:
+        FnvIndexMap<FragmentId, Vec<FragmentId, MAX_FRAGMENTS>, MAX_FRAGMENTS>,
+    punted: FnvIndexMap<FragmentId, usize, MAX_FRAGMENTS>,
+    solved: FnvIndexSet<FragmentId, MAX_FRAGMENTS>,
+}
+
+impl<P, const MAX_FRAGMENTS: usize> HeaplessSolver<P, MAX_FRAGMENTS>
+where
+    P: HeaplessProblem<MAX_FRAGMENTS>,
+{
+    /// Create a new [`HeaplessSolver`] instance for a [`HeaplessProblem`].
+    pub fn new(problem_instance: P) -> Self {
+        Self {
+            problem_instance,
+            to_solve: Deque::new(),
+            queued: FnvIndexSet::new(),
+            pending_on: FnvIndexMap::new(),
+            punted: FnvIndexMap::new(),
+            solved: FnvIndexSet::new(),
+        }
+    }
+
+    /// Get the [`HeaplessProblem`] instance.
+    pub fn problem_instance(&self) -> &P {
+        &self.problem_instance
+    }
+
+    /// Consume `self` and return the [`HeaplessProblem`] instance.
+    pub fn into_problem_instance(self) -> P {
+        self.problem_instance
+    }
+
+    /// Enqueue a fragment to be solved. See
+    /// [`Solver::enqueue_fragment`](crate::Solver::enqueue_fragment).
+    ///
+    /// Returns `true` if the fragment was enqueued or `false` if it was skipped because it was
+    /// already solved or punted.
+    pub fn enqueue_fragment(
+        &mut self,
+        id: FragmentId,
+    ) -> Result<bool, SolverError<P::Error>> {
+        if self.solved.contains(&id) || self.punted.contains_key(&id) {
+            Ok(false)
+        } else {
+            self.push_to_solve(id)?;
+
+            Ok(true)
+        }
+    }
+
+    /// Run a single solver step for a single fragment. See [`Solver::step`](crate::Solver::step).
+    ///
+    /// Returns `false` if there are no more fragments that can be evaluated.
+    ///
+    /// Returns an error if [`HeaplessProblem::evaluate`] returned an error, in which case the
+    /// fragment is not queued again, or if the solver ran out of capacity.
+    pub async fn step(&mut self) -> Result<bool, SolverError<P::Error>> {
+        let id = match self.to_solve.pop_front() {
+            Some(id) => id,
+            None => return Ok(false),
+        };
+        self.queued.remove(&id);
+
+        let mut dependencies =
+            self.problem_instance.direct_dependencies(id).await;
+        dependencies.retain(|x| !self.solved.contains(x));
+        if dependencies.is_empty() {
+            self.problem_instance
+                .evaluate(id)
+                .await
+                .map_err(SolverError::Evaluation)?;
+            self.mark_solved(id)?;
+        } else {
+            self.mark_punted(id, &dependencies)?;
+        }
+
+        Ok(true)
+    }
+
+    /// Run the solver until all enqueued fragments and their transitive dependencies are either
+    /// solved or proven to be part of at least one cycle.
+    ///
+    /// Returns the [`Status`] of the solver afterwards, or the first error.
+    pub async fn run(&mut self) -> Result<Status, SolverError<P::Error>> {
+        while self.step().await? {}
+
+        Ok(self.status())
+    }
+
+    /// Get the current [`Status`] of the solver.
+    pub fn status(&self) -> Status {
+        if !self.to_solve.is_empty() {
+            Status::Pending
+        } else if !self.punted.is_empty() {
+            Status::DoneWithCycles
+        } else {
+            Status::Done
+        }
+    }
+
+    /// Check whether a fragment has been evaluated.
+    pub fn is_evaluated(&self, id: FragmentId) -> bool {
+        self.solved.contains(&id)
+    }
+
+    /// Get an iterator over all fragments that are currently punted. See
+    /// [`Solver::punted_iter`](crate::Solver::punted_iter).
+    pub fn punted_iter(&self) -> impl Iterator<Item = FragmentId> + '_ {
+        self.punted.keys().copied()
+    }
+
+    fn push_to_solve(
+        &mut self,
+        id: FragmentId,
+    ) -> Result<(), SolverError<P::Error>> {
+        if !self.queued.contains(&id) {
+            // Both have the same capacity, so this fails before the fragment is queued
+            self.queued
+                .insert(id)
+                .map_err(|_| SolverError::CapacityExceeded)?;
+            self.to_solve
+                .push_back(id)
+                .map_err(|_| SolverError::CapacityExceeded)?;
+        }
+
+        Ok(())
+    }
+
+    fn mark_solved(
+        &mut self,
+        id: FragmentId,
+    ) -> Result<(), SolverError<P::Error>> {
+        self.solved
+            .insert(id)
+            .map_err(|_| SolverError::CapacityExceeded)?;
+
+        for dependent in self.pending_on.remove(&id).into_iter().flatten() {
+            if let Some(pending) = self.punted.get_mut(&dependent) {
+                *pending -= 1;
+                if *pending == 0 {
+                    self.punted.remove(&dependent);
+                    self.push_to_solve(dependent)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    fn mark_punted(
+        &mut self,
+        id: FragmentId,
+        dependencies: &[FragmentId],
+    ) -> Result<(), SolverError<P::Error>> {
+        // Self-dependencies are not counted nor registered, same as `Solver::mark_punted`
+        let dependencies = dependencies.iter().copied().filter(|&x| x != id);
+        self.punted
+            .insert(id, dependencies.clone().count())
+            .map_err(|_| SolverError::CapacityExceeded)?;
+
+        for dependency in dependencies {
+            if !self.punted.contains_key(&dependency) {
+                self.push_to_solve(dependency)?;
+            }
+            let dependents = match self.pending_on.get_mut(&dependency) {
+                Some(dependents) => dependents,
+                None => {
+                    self.pending_on
+                        .insert(dependency, Vec::new())
+                        .map_err(|_| SolverError::CapacityExceeded)?;
+
+                    &mut self.pending_on[&dependency]
+                }
+            };
+            dependents
+                .push(id)
+                .map_err(|_| SolverError::CapacityExceeded)?;
+        }
+
+        Ok(())
+    }
+}
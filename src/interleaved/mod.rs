@@ -0,0 +1,85 @@
+use crate::{
+    problems::BoxError,
+    reexported::{task, Box, Future, NonZeroUsize, Pin, Vec},
+    Problem, SolveReport, Solver,
+};
+use futures::future;
+
+/// Object-safe view of a [`Solver`] for any [`Problem`], so solvers for different problem types
+/// can be passed to [`run_interleaved`] together.
+pub trait AnyDynSolver: Sync {
+    /// Like [`Solver::run`], but runs up to `concurrency` steps at a time with
+    /// [`Solver::step_batch`] and yields to the executor after each batch. Evaluation errors are
+    /// boxed.
+    fn run_yielding(
+        &self,
+        concurrency: NonZeroUsize,
+    ) -> Pin<Box<dyn Future<Output = Result<SolveReport, BoxError>> + Send + '_>>;
+}
+
+impl<P> AnyDynSolver for Solver<P>
+where
+    P: Problem + Send + Sync,
+    P::Error: Into<BoxError> + Send,
+{
+    fn run_yielding(
+        &self,
+        concurrency: NonZeroUsize,
+    ) -> Pin<Box<dyn Future<Output = Result<SolveReport, BoxError>> + Send + '_>>
+    {
+        Box::pin(async move {
+            let run = self.begin_run().await;
+            let res = loop {
+                match self.step_batch(concurrency.get()).await {
+                    Ok(0) => break Ok(()),
+                    Ok(_) => YieldNow(false).await,
+                    Err(err) => break Err(err.into()),
+                }
+            };
+            let report = run.finish().await;
+            res?;
+
+            Ok(report)
+        })
+    }
+}
+
+/// Run multiple solvers, possibly for different problem types, round-robin in the same task.
+/// Each solver runs up to `concurrency` steps at a time, then yields so every other solver can
+/// run a batch of steps before it continues, so no solver starves the others even if its
+/// evaluations never wait.
+///
+/// Returns one result per solver, in the same order as `solvers`. A solver that fails stops
+/// without affecting the others.
+pub async fn run_interleaved(
+    solvers: &[&dyn AnyDynSolver],
+    concurrency: NonZeroUsize,
+) -> Vec<Result<SolveReport, BoxError>> {
+    future::join_all(
+        solvers
+            .iter()
+            .map(|solver| solver.run_yielding(concurrency)),
+    )
+    .await
+}
+
+// Future that returns `Pending` once, after asking to be polled again
+struct YieldNow(bool);
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(
+        mut self: Pin<&mut Self>,
+        cx: &mut task::Context<'_>,
+    ) -> task::Poll<Self::Output> {
+        if self.0 {
+            task::Poll::Ready(())
+        } else {
+            self.0 = true;
+            cx.waker().wake_by_ref();
+
+            task::Poll::Pending
+        }
+    }
+}
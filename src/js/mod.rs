@@ -26,12 +26,15 @@ unsafe impl Sync for Problem {}
 // necessary unsafe convertion (see `unsafe_add_send`)
 impl crate::Problem for Problem {
     type Error = JsValue;
+    type Output = ();
 
     fn direct_dependencies<'life0, 'life1, 'async_trait>(
         &'life0 self,
         id: FragmentId,
         dependecies: &'life1 mut Vec<FragmentId>,
-    ) -> Pin<Box<dyn Future<Output = ()> + Send + 'async_trait>>
+    ) -> Pin<
+        Box<dyn Future<Output = Result<(), Self::Error>> + Send + 'async_trait>,
+    >
     where
         'life0: 'async_trait,
         'life1: 'async_trait,
@@ -42,11 +45,12 @@ impl crate::Problem for Problem {
                 dependecies.extend(
                     serde_wasm_bindgen::from_value::<Vec<usize>>(
                         Problem::direct_dependencies(self, id.into()).await,
-                    )
-                    .unwrap()
+                    )?
                     .into_iter()
                     .map(|x| FragmentId(x)),
                 );
+
+                Ok(())
             })
         }
     }
@@ -98,7 +102,7 @@ impl Solver {
 
         wasm_bindgen_futures::future_to_promise(async move {
             Ok(JsValue::from(format!(
-                "{:?}",
+                "{}",
                 BaseSolver::status(&this).await
             )))
         })
@@ -144,13 +148,14 @@ impl Solver {
 
         wasm_bindgen_futures::future_to_promise(async move {
             Ok(serde_wasm_bindgen::to_value(
-                &BaseSolver::run(
+                &BaseSolver::run_detailed(
                     &this,
                     NonZeroUsize::new(concurrency).ok_or(JsValue::from_str(
                         "The `concurrency` argument for `run` must not be zero",
                     ))?,
                 )
                 .await?
+                .punted
                 .into_iter()
                 .map(|x| x.into())
                 .collect::<Vec<usize>>(),
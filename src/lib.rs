@@ -15,24 +15,30 @@
 //! the direct dependencies of specified fragments as the solver explores the dependency graph.
 //!
 //! [`Solver::run`] and [`Solver::step`] will incrementally explore the depedency graph and call
-//! [`Problem::evaluate`] on fragments that have all of its dependencies met.
+//! [`Problem::evaluate`] on fragments that have all of its dependencies met. For simple use cases,
+//! [`Solver::solving`] returns a [`SolverFuture`] that does all of that in a single `await`.
 //!
 //! In the end, all requested fragments will either have been evaluated or will be proven to be
 //! part of a dependency cycle. The user may choose to report cycles as errors, or break them with
-//! [`Solver::assume_evaluated`] or [`Solver::clone_with_evaluation_assumptions`]. See also
-//! [`Solver::status`].
+//! [`Solver::assume_evaluated`] or [`Solver::clone_for_assumption_testing`]. See also
+//! [`Solver::status`] and [`Solver::explain_cycle`].
 //!
 //! [`Solver::punted_iter`] will return an iterator yielding all fragments that have been *punted*
 //! so far. A punted fragment is one that has been considered for evaluation but its dependencies
 //! haven't been met yet. If the solver is done, punted fragments must be part of at least one
-//! cycle.
+//! cycle. [`Solver::fragment_state`] can be used to inspect a single fragment.
 //!
 //! # Concurrency
 //!
 //! [`Solver`] is fully asynchronous but the core algorithm is not parallel at the moment. Running
 //! multiple [`Solver::step`] concurrently or calling [`Solver::run`] with `concurrency > 1` is
 //! safe but will not make the solver itself run faster. What this does allow is for multiple
-//! [`Problem::direct_dependencies`] and [`Problem::evaluate`] calls to run concurrently.
+//! [`Problem::direct_dependencies`] and [`Problem::evaluate`] calls to run concurrently. For
+//! genuine parallelism, use `Solver::run_parallel_core` from the `tokio-runtime` feature.
+//!
+//! Concurrent calls to [`Solver::run`] are serialized: a call will wait until all previous calls
+//! have returned before running any steps. Otherwise a call could return early while fragments
+//! are still being evaluated by another call.
 //!
 //! # Build Features
 //!
@@ -42,12 +48,106 @@
 //! ## `std`
 //!
 //! Use std. [`Solver::run`] will be unavailable if `std` is disabled. **TODO**: actually make
-//! this assertion true.
+//! this assertion true. `Solver::dump_state`, `Solver::run_to_completion_sync`,
+//! `Solver::run_default`, and `with_default_concurrency` also require `std`.
+//!
+//! ## `benchmarking`
+//!
+//! Add `Solver::benchmark_problem`, which times repeated runs of the solver from the same state
+//! to help tune parameters such as concurrency without setting up a benchmark harness. Implies
+//! `std`.
+//!
+//! ## `chaos`
+//!
+//! Add `ChaosMonkeyProblem` to [`problems`], which randomly fails or delays evaluations for chaos
+//! testing. Implies `std`.
+//!
+//! ## `dense-ids`
+//!
+//! Store the set of solved fragments in a bitfield, making membership tests O(1) even without
+//! `std`. Memory usage grows with the largest solved [`FragmentId`], so only enable this if IDs
+//! are dense.
+//!
+//! This is incompatible with IDs declared with a nonzero namespace by [`declare_fragment_id`]:
+//! the namespace is stored in the high bits of the [`FragmentId`], so solving one of them tries
+//! to allocate a bitfield covering most of the address space and aborts.
+//!
+//! ## `deterministic`
+//!
+//! Use `BTreeMap` and `BTreeSet` internally even with `std`, so fragments are always considered
+//! in the same order. Always enabled when testing this crate.
+//!
+//! ## `events`
+//!
+//! Add `Solver::subscribe_events`, which streams `SolverEvent`s through a `tokio` broadcast
+//! channel for real-time observability, and `Solver::pipe_into`, which feeds evaluated fragments
+//! into another solver. Implies `std`.
+//!
+//! ## `heapless`
+//!
+//! Add `HeaplessSolver`, which keeps all of its state in fixed-capacity collections from the
+//! `heapless` crate for memory-constrained targets, and `HeaplessProblem`, the matching version
+//! of [`Problem`] that returns dependencies in a fixed-capacity vector.
 //!
 //! ## `js-bindings`
 //!
 //! Build the JavaScript API if building for WASM.
 //!
+//! ## `lru`
+//!
+//! Add `CachingProblem` to [`problems`], which caches dependencies of a wrapped problem across
+//! solver instances using an LRU cache. Implies `std`.
+//!
+//! ## `petgraph`
+//!
+//! Add the `petgraph_ext` module, which implements [`Problem`] for `petgraph` graphs.
+//!
+//! ## `quickcheck`
+//!
+//! Implement `quickcheck::Arbitrary` for [`FragmentId`] and add `MockProblem` to [`problems`],
+//! which can generate random DAGs for property testing. Implies `std`.
+//!
+//! ## `serde`
+//!
+//! Implement `serde` traits for [`FragmentId`] and some of the
+//! [ready-made problems](crate::problems), such as [`HashMapProblem`](problems::HashMapProblem).
+//! Also adds `Solver::dump_state_json`.
+//!
+//! ## `smallvec`
+//!
+//! Store the short per-fragment lists the solver keeps internally inline using the `smallvec`
+//! crate, avoiding a heap allocation for most fragments.
+//!
+//! ## `nightly`
+//!
+//! Enable features that require a nightly compiler. At the moment this implements the unstable
+//! `Step` trait for [`FragmentId`], allowing native ranges of fragment IDs. See
+//! [`FragmentId::range`] for a stable alternative.
+//!
+//! ## `sync-only`
+//!
+//! Add `SyncProblem`, a synchronous version of [`Problem`], and `BlockingSolver`, a blocking
+//! adapter that solves a `SyncProblem` without any async runtime. It is not a separate
+//! synchronous implementation: it wraps a [`Solver`] and blocks the current thread on each call,
+//! evaluating fragments in threads spawned with `std::thread`. The wrapped [`Solver`] is still
+//! locked with the runtime-independent `futures` locks rather than `std::sync::Mutex`, so this
+//! feature implies `std` and `futures-lock`, and can't be used with `tokio-lock` or
+//! `async-std-lock`.
+//!
+//! ## `testing-helpers`
+//!
+//! Add helpers for use in tests, such as `Solver::assert_no_cycles`,
+//! `Solver::assert_all_solved`, and `Solver::verify_solved_order`. Also add `TracingProblem`,
+//! which records an `EvaluationTrace` of a run, and `Solver::replay_from_trace`, which replays it
+//! against another problem.
+//!
+//! ## `tokio-runtime`
+//!
+//! Add `Solver::run_parallel_core`, which spawns steps as `tokio` tasks so fragments can be
+//! evaluated in parallel, `Solver::run_with_concurrency_schedule`, which changes concurrency
+//! over time, `Solver::step_with_timeout`, which cancels slow evaluations, and
+//! `Solver::schedule_evaluation_after`, which delays enqueuing a fragment. Implies `std`.
+//!
 //! ## `futures-lock`
 //!
 //! Use the locks implemented by the `futures` crate.
@@ -78,9 +178,10 @@
 //! - There is no need to explicitly detect nor handle cycles, unlike both pure push and pure
 //!   pull. Fragments that are part of cycles will naturally be punted and never considered again.
 //!   Unless the cycle is explicitly broken with [`Solver::assume_evaluated`] or
-//!   [`Solver::clone_with_evaluation_assumptions`]. This enables a much simpler implementation.
+//!   [`Solver::clone_for_assumption_testing`]. This enables a much simpler implementation.
 
 #![cfg_attr(not(feature = "std"), no_std)]
+#![cfg_attr(feature = "nightly", feature(step_trait))]
 
 // Only used when testing
 #[cfg(test)]
@@ -114,12 +215,74 @@ macro_rules! feature_cfg {
     };
 }
 
-use crate::reexported::{iter, Box, Map, Mutex, NonZeroUsize, Set, Vec};
+use crate::future::RunGuard;
+use crate::reexported::{
+    atomic::{AtomicBool, AtomicUsize, Ordering},
+    format, iter, mem, Arc, BTreeMap, BinaryHeap, Box, DenseSet,
+    DoubleEndedIterator, Error, ExactSizeIterator, Future, InlineVec,
+    IntoIterator, Iterator, Map, Mutex, MutexGuard, NonZeroUsize, Pin, Set,
+    String, Vec, VecDeque,
+};
 use async_trait::async_trait;
+use core::{any::Any, cmp::Reverse, fmt, ops::ControlFlow, str::FromStr};
 use derive_more::{From, Into};
-use futures::stream::{FuturesUnordered, StreamExt};
+use futures::{
+    channel::oneshot,
+    future::{select, BoxFuture, Either},
+    stream::{FuturesUnordered, StreamExt},
+};
 
+#[cfg(feature = "benchmarking")]
+pub use crate::benchmarking::BenchmarkReport;
+#[cfg(feature = "sync-only")]
+pub use crate::blocking::{BlockingSolver, SyncAdapter, SyncProblem};
+pub use crate::checkpoint::SolverCheckpoint;
+#[cfg(feature = "events")]
+pub use crate::events::{PipeHandle, SolverEvent, DEFAULT_EVENT_CAPACITY};
+pub use crate::frozen::FrozenSolverState;
+#[cfg(feature = "heapless")]
+pub use crate::heapless_solver::{
+    HeaplessProblem, HeaplessSolver, SolverError,
+};
+#[cfg(feature = "tokio-runtime")]
+pub use crate::parallel::{ScheduleError, StepOutcome};
+#[cfg(feature = "std")]
+pub use crate::stats::SolverStatistics;
+#[cfg(any(test, feature = "testing-helpers"))]
+pub use crate::testing::{EvaluationTrace, TopologicalViolation};
+pub use crate::{
+    future::{SolverFuture, SolverHandle},
+    interleaved::{run_interleaved, AnyDynSolver},
+    stream::EvaluationEvent,
+    typed::{FragmentIdNamespaceError, TypedSolveReport, TypedSolver},
+};
+
+#[cfg(feature = "benchmarking")]
+mod benchmarking;
+#[cfg(feature = "sync-only")]
+mod blocking;
+mod checkpoint;
+#[cfg(any(feature = "std", feature = "serde"))]
+mod dump;
+#[cfg(feature = "events")]
+mod events;
+mod frozen;
+mod future;
+#[cfg(feature = "heapless")]
+mod heapless_solver;
+mod interleaved;
+#[cfg(feature = "tokio-runtime")]
+mod parallel;
+#[cfg(feature = "petgraph")]
+pub mod petgraph_ext;
+pub mod problems;
 pub mod reexported;
+#[cfg(feature = "std")]
+mod stats;
+mod stream;
+#[cfg(any(test, feature = "testing-helpers"))]
+mod testing;
+mod typed;
 
 #[cfg(all(feature = "js-bindings", target_family = "wasm"))]
 mod js;
@@ -132,16 +295,100 @@ mod test;
 /// Use [`mod@async_trait`] to implement this trait.
 #[async_trait]
 pub trait Problem {
-    /// Error type for [`Problem::evaluate`].
+    /// Error type for [`Problem::direct_dependencies`] and [`Problem::evaluate`].
     type Error;
 
+    /// Value produced by [`Problem::evaluate`]. The solver keeps the output of each evaluated
+    /// fragment until it is taken with [`Solver::take_output`]. Use `()` if evaluating a fragment
+    /// produces nothing worth keeping.
+    type Output: Send + 'static;
+
     /// Fill `dependencies` with the direct dependencies of `id`. The output vector is guaranteed
     /// to be empty when this method is called.
+    ///
+    /// Returning an error, such as when the dependencies of `id` can't be read, fails `id` the
+    /// same way as an error from [`Problem::evaluate`] would, without evaluating it.
     async fn direct_dependencies(
         &self,
         id: FragmentId,
         dependecies: &mut Vec<FragmentId>,
-    );
+    ) -> Result<(), Self::Error>;
+
+    /// Get the number of direct dependencies of `id`, if known ahead of time. The solver uses it to
+    /// allocate enough room before calling [`Problem::direct_dependencies`]. The default
+    /// implementation returns `None`.
+    ///
+    /// This is a plain function as boxing a future for it would cost the allocation it saves.
+    fn dependencies_hint(&self, _id: FragmentId) -> Option<usize> {
+        None
+    }
+
+    /// Reorder the direct dependencies of `id` returned by [`Problem::direct_dependencies`], most
+    /// preferred first. Useful to apply domain-specific knowledge such as expected evaluation
+    /// times. The default implementation leaves the order unchanged. Implement it as an
+    /// `async fn` with [`mod@async_trait`].
+    ///
+    /// The order is used whenever the solver walks the dependencies of `id`, such as when queuing
+    /// them and in [`Solver::what_blocks`].
+    // The default implementations of `preferred_dependencies_order`, `dependencies_for_batch`,
+    // `on_start`, and `on_finish` are written out by hand because `async_trait` would otherwise
+    // require `Self: Sync` to call them. The lifetimes must stay explicit and bounded by `'fut` so
+    // they match the signatures `async_trait` generates for `async fn` implementations
+    fn preferred_dependencies_order<'a, 'b, 'fut>(
+        &'a self,
+        _id: FragmentId,
+        _dependencies: &'b mut [FragmentId],
+    ) -> BoxFuture<'fut, ()>
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: 'fut,
+    {
+        Box::pin(async {})
+    }
+
+    /// Fill `dependencies[i]` with the direct dependencies of `ids[i]`, like calling
+    /// [`Problem::direct_dependencies`] for each fragment. Useful when many lookups can be batched
+    /// together, such as in a single database query. `dependencies` has the same length as `ids`
+    /// and all of its vectors are guaranteed to be empty when this method is called.
+    ///
+    /// The default implementation calls [`Problem::direct_dependencies`] for each fragment, one at
+    /// a time. Implement it as an `async fn` with [`mod@async_trait`].
+    ///
+    /// Used when fetching dependencies ahead of evaluation, such as in
+    /// [`Solver::expand_all_dependencies`], with up to [`SolverConfig::dependency_batch_size`]
+    /// fragments per call. If this returns an error, the whole batch is discarded and the
+    /// dependencies of its fragments are fetched again with [`Problem::direct_dependencies`] when
+    /// they are taken for evaluation, which then reports the error.
+    fn dependencies_for_batch<'a, 'b, 'c, 'fut>(
+        &'a self,
+        ids: &'b [FragmentId],
+        dependencies: &'c mut [Vec<FragmentId>],
+    ) -> BoxFuture<'fut, Result<(), Self::Error>>
+    where
+        'a: 'fut,
+        'b: 'fut,
+        'c: 'fut,
+        Self: 'fut,
+    {
+        // Create all fetches before awaiting any of them so the returned future doesn't hold on to
+        // `&self`
+        let fetches = ids
+            .iter()
+            .zip(dependencies)
+            .map(|(&id, dependencies)| {
+                self.direct_dependencies(id, dependencies)
+            })
+            .collect::<Vec<_>>();
+
+        Box::pin(async move {
+            for fetch in fetches {
+                fetch.await?;
+            }
+
+            Ok(())
+        })
+    }
 
     /// Called by the solver to signal that a fragment has had all of its dependencies evaluated.
     /// Thus, the fragment should be evaluated too.
@@ -149,7 +396,43 @@ pub trait Problem {
     /// See [`Solver::run`] and [`Solver::step`] on how evaluation failures are handled.
     ///
     /// This method is never called more than once with the same fragment.
-    async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error>;
+    async fn evaluate(
+        &self,
+        id: FragmentId,
+    ) -> Result<Self::Output, Self::Error>;
+
+    /// Called once by [`Solver::run`] before any other method of this trait. Useful to set up
+    /// resources needed during evaluation. Implement it as an `async fn` with [`mod@async_trait`].
+    fn on_start<'a, 'fut>(&'a self) -> BoxFuture<'fut, ()>
+    where
+        'a: 'fut,
+        Self: 'fut,
+    {
+        Box::pin(async {})
+    }
+
+    /// Called once by [`Solver::run`] after the last step, even if an evaluation failed. Useful to
+    /// tear down resources set up in [`Problem::on_start`]. Implement it as an `async fn` with
+    /// [`mod@async_trait`].
+    ///
+    /// Not called if the future returned by [`Solver::run`] is dropped before completion.
+    fn on_finish<'a, 'b, 'fut>(
+        &'a self,
+        _report: &'b SolveReport,
+    ) -> BoxFuture<'fut, ()>
+    where
+        'a: 'fut,
+        'b: 'fut,
+        Self: 'fut,
+    {
+        Box::pin(async {})
+    }
+
+    /// Get a human-readable description of a fragment. Used for diagnostics such as
+    /// [`Solver::explain_cycle`].
+    fn describe_fragment(&self, id: FragmentId) -> String {
+        format!("fragment {}", id.0)
+    }
 }
 
 /// ID of a fragment.
@@ -157,52 +440,488 @@ pub trait Problem {
 #[derive(
     Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash, From, Into,
 )]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
 pub struct FragmentId(pub usize);
 
+impl FragmentId {
+    /// The smallest fragment ID.
+    pub const ZERO: FragmentId = FragmentId(0);
+
+    /// The largest fragment ID. Useful as a sentinel.
+    pub const MAX: FragmentId = FragmentId(usize::MAX);
+
+    /// Number of high bits reserved for the namespace of IDs declared with
+    /// [`declare_fragment_id`].
+    pub const NAMESPACE_BITS: u32 = 8;
+
+    const NAMESPACE_SHIFT: u32 = usize::BITS - Self::NAMESPACE_BITS;
+
+    /// Build a fragment ID from a namespace and an ID local to that namespace.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `namespace` doesn't fit in [`FragmentId::NAMESPACE_BITS`] bits or if `local`
+    /// overlaps the namespace bits.
+    pub const fn in_namespace(namespace: usize, local: usize) -> FragmentId {
+        assert!(
+            namespace >> Self::NAMESPACE_BITS == 0,
+            "fragment ID namespace out of range"
+        );
+        assert!(
+            local >> Self::NAMESPACE_SHIFT == 0,
+            "local fragment ID overlaps the namespace bits"
+        );
+
+        FragmentId((namespace << Self::NAMESPACE_SHIFT) | local)
+    }
+
+    /// Get the namespace of this ID. See [`FragmentId::in_namespace`].
+    pub const fn namespace(self) -> usize {
+        self.0 >> Self::NAMESPACE_SHIFT
+    }
+
+    /// Get this ID without its namespace bits. See [`FragmentId::in_namespace`].
+    pub const fn local_id(self) -> usize {
+        self.0 & (usize::MAX >> Self::NAMESPACE_BITS)
+    }
+
+    /// Get an iterator over all fragment IDs from `start` (inclusive) to `end` (exclusive).
+    pub fn range(start: FragmentId, end: FragmentId) -> FragmentIdRange {
+        FragmentIdRange {
+            next: start.0,
+            end: end.0.max(start.0),
+        }
+    }
+}
+
+feature_cfg! {
+    for "nightly";
+
+    impl iter::Step for FragmentId {
+        fn steps_between(start: &Self, end: &Self) -> (usize, Option<usize>) {
+            usize::steps_between(&start.0, &end.0)
+        }
+
+        fn forward_checked(start: Self, count: usize) -> Option<Self> {
+            usize::forward_checked(start.0, count).map(FragmentId)
+        }
+
+        fn backward_checked(start: Self, count: usize) -> Option<Self> {
+            usize::backward_checked(start.0, count).map(FragmentId)
+        }
+    }
+}
+
+/// Iterator over a range of fragment IDs. See [`FragmentId::range`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FragmentIdRange {
+    next: usize,
+    end: usize,
+}
+
+impl Iterator for FragmentIdRange {
+    type Item = FragmentId;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.next < self.end {
+            self.next += 1;
+
+            Some(FragmentId(self.next - 1))
+        } else {
+            None
+        }
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let len = self.end - self.next;
+
+        (len, Some(len))
+    }
+}
+
+impl DoubleEndedIterator for FragmentIdRange {
+    fn next_back(&mut self) -> Option<Self::Item> {
+        if self.next < self.end {
+            self.end -= 1;
+
+            Some(FragmentId(self.end))
+        } else {
+            None
+        }
+    }
+}
+
+impl ExactSizeIterator for FragmentIdRange {}
+
 /// Hybrid push-pull solver.
 pub struct Solver<P> {
     state: Mutex<State>,
-    // This is a scratch vector we store here to reduce allocations
+    // This is a scratch vector we store here to reduce allocations. Steps take it out while they
+    // use it
     dependencies: Mutex<Vec<FragmentId>>,
+    // Held for the whole duration of `run` so concurrent calls are serialized
+    run_lock: Mutex<()>,
+    // Mirrors whether a run is in progress so it can be checked without awaiting
+    running: AtomicBool,
+    // Number of runs started so far, used to give each run an ID
+    run_count: AtomicUsize,
+    // ID of the last run that was dropped before it finished, or `0`. The next step or run queues
+    // the fragments the dropped steps left in flight again. See `RunGuard`
+    cancelled_run: AtomicUsize,
+    // Counters are kept outside of `state` so they can be read without locking
+    steps_taken: AtomicUsize,
+    evaluations_attempted: AtomicUsize,
+    max_queue_depth: AtomicUsize,
+    punts: AtomicUsize,
+    dependency_fetches: AtomicUsize,
+    config: SolverConfig,
+    pre_hook: Option<Arc<FragmentHook>>,
+    post_hook: Option<Arc<PostHook>>,
+    solved_hook: Option<Arc<FragmentHook>>,
+    punted_hook: Option<Arc<FragmentHook>>,
+    #[cfg(feature = "events")]
+    events: tokio::sync::broadcast::Sender<SolverEvent>,
     problem_instance: P,
 }
 
+type FragmentHook = dyn Fn(FragmentId) + Send + Sync;
+type PostHook = dyn Fn(FragmentId, bool) + Send + Sync;
+type PriorityFunction = dyn Fn(FragmentId) -> u64 + Send + Sync;
+
+/// Configuration for a [`Solver`] instance.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct SolverConfig {
+    /// Number of concurrent steps used when the solver drives itself, such as when awaiting a
+    /// [`SolverFuture`].
+    pub concurrency: NonZeroUsize,
+
+    /// Maximum number of fragments passed to each [`Problem::dependencies_for_batch`] call.
+    /// Defaults to 1.
+    pub dependency_batch_size: NonZeroUsize,
+
+    /// Enqueue the fragments found by [`Solver::missing_fragments`], including when a run would
+    /// otherwise end with them missing. Defaults to `false`.
+    pub auto_enqueue_missing: bool,
+}
+
+impl Default for SolverConfig {
+    fn default() -> Self {
+        Self {
+            concurrency: NonZeroUsize::new(1).unwrap(),
+            dependency_batch_size: NonZeroUsize::new(1).unwrap(),
+            auto_enqueue_missing: false,
+        }
+    }
+}
+
+/// Get a concurrency suitable for most problems: the amount of parallelism available to the
+/// program according to [`std::thread::available_parallelism`], or `1` if that is unknown.
+///
+/// Only available with the `std` feature.
+#[cfg(feature = "std")]
+pub fn with_default_concurrency() -> NonZeroUsize {
+    std::thread::available_parallelism().unwrap_or(NonZeroUsize::MIN)
+}
+
+/// Outcome of running a [`Solver`] to completion.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[must_use]
+pub struct SolveReport {
+    /// [`Status`] of the solver after the run.
+    pub status: Status,
+
+    /// Fragments that were punted when the run ended. See [`Solver::punted_iter`].
+    pub punted: Vec<FragmentId>,
+
+    /// Number of fragments for which [`Problem::evaluate`] returned an error.
+    pub failed: usize,
+
+    /// Number of fragments that can never be evaluated because one of their transitive
+    /// dependencies failed. See [`Solver::run_with_error_handler`].
+    pub blocked: usize,
+}
+
+/// Outcome of a [`Solver::run_detailed`] call.
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[must_use]
+pub struct DetailedSolveReport {
+    /// [`Status`] of the solver after the run.
+    pub status: Status,
+
+    /// Fragments evaluated during the run, in the order their evaluations completed. This is a
+    /// topological order: fragments always come after their dependencies. Fragments
+    /// [assumed to be evaluated](Solver::assume_evaluated) are not included.
+    pub evaluated_in_order: Vec<FragmentId>,
+
+    /// Fragments that were punted when the run ended. See [`Solver::punted_iter`].
+    pub punted: Vec<FragmentId>,
+
+    /// Number of fragments for which [`Problem::evaluate`] returned an error.
+    pub failed: usize,
+
+    /// Number of fragments that can never be evaluated because one of their transitive
+    /// dependencies failed.
+    pub blocked: usize,
+}
+
+impl From<DetailedSolveReport> for SolveReport {
+    fn from(report: DetailedSolveReport) -> Self {
+        Self {
+            status: report.status,
+            punted: report.punted,
+            failed: report.failed,
+            blocked: report.blocked,
+        }
+    }
+}
+
+// Outcome of a single solver step
+enum Progress {
+    Idle,
+    Blocked,
+    Punted(FragmentId),
+    Evaluated(FragmentId),
+    // The evaluation deadline passed before `evaluate` returned. The fragment was queued again
+    TimedOut,
+}
+
+// Future that cancels an evaluation when it completes. See `Solver::step_until`
+type Deadline = Pin<Box<dyn Future<Output = ()> + Send>>;
+
 // POD struct
 struct State {
     // TODO: these should be an intrusive copy-on-write to make cloning and testing alternatives
     // cheap
     to_solve: Set<FragmentId>,
-    pending_on: Map<FragmentId, Vec<FragmentId>>,
+    pending_on: Map<FragmentId, InlineVec<FragmentId>>,
     punted: Map<FragmentId, usize>,
-    solved: Set<FragmentId>,
+    solved: DenseSet<FragmentId>,
+    // Fragments between being taken from `to_solve` and being either punted or evaluated
+    in_flight: Set<FragmentId>,
+    // ID of the run that took each in-flight fragment, if it was taken by a run's step. Entries
+    // may be stale, so always check `in_flight` too
+    in_flight_runs: Map<FragmentId, usize>,
+    // Fragments for which `Problem::evaluate` returned an error
+    failed: Set<FragmentId>,
+    // Fragments that depend on a failed fragment, directly or transitively
+    blocked: Set<FragmentId>,
+    // Fragments marked as solved through `Solver::assume_evaluated`
+    assumed: Set<FragmentId>,
+    // All direct dependencies returned by `Problem::direct_dependencies` so far. Unlike
+    // `pending_on`, edges are never removed
+    dependency_graph: Map<FragmentId, Vec<FragmentId>>,
+    // Extra dependencies added through `Solver::add_ordering_constraint`
+    ordering_constraints: Map<FragmentId, Vec<FragmentId>>,
+    // Dependencies fetched ahead of time that the next step taking the fragment will use instead
+    // of fetching them again
+    prefetched: Map<FragmentId, Vec<FragmentId>>,
+    // Dependencies as returned by `Problem::direct_dependencies`, without ordering constraints.
+    // Only cleared by `Solver::clear_dep_cache`
+    dep_cache: Map<FragmentId, Vec<FragmentId>>,
+    // Solved fragments in the order they were solved
+    solved_order: Vec<FragmentId>,
+    // Number of the step that evaluated each fragment, counting from `1`. See
+    // `Solver::steps_to_solve`
+    solved_at_step: Map<FragmentId, usize>,
+    // Number of times each fragment was taken to be evaluated, including failed and interrupted
+    // evaluations. See `Solver::fragment_evaluation_attempts`
+    attempt_counts: Map<FragmentId, usize>,
+    // Fragments whose evaluation was interrupted by a timeout or a cancelled run and that were
+    // queued again, or that failed and were taken again
+    interrupted: Set<FragmentId>,
+    // Outputs of `Problem::evaluate` that were not taken yet. Type-erased so `State` doesn't
+    // depend on the problem type. Outputs are not cloned by `State::fork`
+    outputs: Map<FragmentId, Box<dyn Any + Send>>,
+    // Tasks waiting in `Solver::wait_for_progress`
+    progress_waiters: Vec<oneshot::Sender<()>>,
+    // Tasks waiting in `Solver::wait_until_done`
+    done_waiters: Vec<oneshot::Sender<()>>,
+    // Scheduling priorities added through `Solver::priority_boost`. Fragments without an entry
+    // have priority `0`
+    priorities: Map<FragmentId, u64>,
+    // Fragments the current `Solver::run_subset` call is restricted to. Other queued fragments
+    // are left alone
+    subset: Option<Set<FragmentId>>,
+    // Priority function of the current `Solver::run_with_priority_function` call, and the priority
+    // it gave to each queued fragment when it was enqueued
+    priority_function: Option<Box<PriorityFunction>>,
+    queued_priorities: Map<FragmentId, u64>,
+    // Queued fragments with a priority, that is, boosted fragments or all queued fragments while
+    // there is a priority function. Entries are never removed when a fragment is taken or its
+    // priority changes, so they are checked and discarded lazily by `State::next_to_solve`
+    priority_queue: BinaryHeap<(u64, Reverse<FragmentId>)>,
+    // Statistics for the current `Solver::run_with_stats` call
+    #[cfg(feature = "std")]
+    run_stats: stats::RunStats,
+    // Number of successful evaluations of each fragment, to catch fragments that are evaluated
+    // more than once
+    #[cfg(debug_assertions)]
+    evaluation_count: Map<FragmentId, usize>,
+    // Fragments added through `Solver::schedule_evaluation_after`, keyed by when they are due
+    #[cfg(feature = "tokio-runtime")]
+    delayed_queue: BTreeMap<tokio::time::Instant, Vec<FragmentId>>,
 }
 
-impl<P> Solver<P> {
-    /// Create a new [`Solver`] instance for a [`Problem`].
-    pub fn new(problem_instance: P) -> Self {
-        Self {
-            state: Mutex::new(State {
-                to_solve: Set::new(),
-                pending_on: Map::new(),
-                punted: Map::new(),
-                solved: Set::new(),
-            }),
-            dependencies: Mutex::new(Vec::new()),
-            problem_instance,
+impl State {
+    // Copy of all fragments and dependencies, without waiters or anything scoped to the current
+    // run. Fragments that are being evaluated are queued again in the copy, as it can't see
+    // their evaluation finish
+    fn fork(&self) -> Self {
+        let mut to_solve = self.to_solve.clone();
+        to_solve.extend(self.in_flight.iter().copied());
+
+        let mut state = Self {
+            to_solve,
+            pending_on: self.pending_on.clone(),
+            punted: self.punted.clone(),
+            solved: self.solved.clone(),
+            in_flight: Set::new(),
+            in_flight_runs: Map::new(),
+            failed: self.failed.clone(),
+            blocked: self.blocked.clone(),
+            assumed: self.assumed.clone(),
+            dependency_graph: self.dependency_graph.clone(),
+            ordering_constraints: self.ordering_constraints.clone(),
+            prefetched: self.prefetched.clone(),
+            dep_cache: self.dep_cache.clone(),
+            solved_order: self.solved_order.clone(),
+            solved_at_step: self.solved_at_step.clone(),
+            attempt_counts: self.attempt_counts.clone(),
+            interrupted: self.interrupted.clone(),
+            outputs: Map::new(),
+            progress_waiters: Vec::new(),
+            done_waiters: Vec::new(),
+            priorities: self.priorities.clone(),
+            subset: None,
+            priority_function: None,
+            queued_priorities: Map::new(),
+            priority_queue: BinaryHeap::new(),
+            #[cfg(feature = "std")]
+            run_stats: stats::RunStats::default(),
+            #[cfg(debug_assertions)]
+            evaluation_count: self.evaluation_count.clone(),
+            #[cfg(feature = "tokio-runtime")]
+            delayed_queue: self.delayed_queue.clone(),
+        };
+        state.rebuild_priority_queue();
+
+        state
+    }
+
+    fn add_ordering_constraints(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) {
+        if let Some(constraints) = self.ordering_constraints.get(&id) {
+            for &before in constraints {
+                if !dependencies.contains(&before) {
+                    dependencies.push(before);
+                }
+            }
         }
     }
 
-    /// Consume `self` and return the wrapped [`Problem`] instance.
-    pub fn into_problem_instance(self) -> P {
-        self.problem_instance
+    // Mark `id` as in flight, taken by a step of run `run` or outside of any run. A failed
+    // fragment is not failed anymore once it is retried
+    fn take_in_flight(&mut self, id: FragmentId, run: Option<usize>) {
+        if self.failed.remove(&id) {
+            self.interrupted.insert(id);
+            // Dependents wait on the retried fragment again. None of them is ready, as `id` is
+            // not solved
+            self.unblock_dependents(id);
+        }
+        self.in_flight.insert(id);
+        match run {
+            Some(run) => self.in_flight_runs.insert(id, run),
+            None => self.in_flight_runs.remove(&id),
+        };
     }
 
-    /// Get the current [`Status`] of the solver.
-    pub async fn status(&self) -> Status {
-        let state = self.state.lock().await;
+    // Punt fragments that were blocked on `id`, directly or transitively, again once `id` is
+    // retried or solved. Fragments that are still blocked by another failed fragment are left
+    // alone. Returns the fragments that don't wait on anything anymore, which must be queued
+    fn unblock_dependents(&mut self, id: FragmentId) -> Vec<FragmentId> {
+        let mut ready = Vec::new();
+        let mut queue = Vec::from([id]);
+        while let Some(id) = queue.pop() {
+            let dependents = self
+                .blocked
+                .iter()
+                .copied()
+                .filter(|dependent| {
+                    self.dependency_graph
+                        .get(dependent)
+                        .is_some_and(|dependencies| dependencies.contains(&id))
+                })
+                .collect::<Vec<_>>();
+            for dependent in dependents {
+                let dependencies = self.dependency_graph[&dependent].clone();
+                if dependencies.iter().any(|x| {
+                    self.failed.contains(x) || self.blocked.contains(x)
+                }) {
+                    continue;
+                }
+
+                self.blocked.remove(&dependent);
+                queue.push(dependent);
+                let pending = dependencies
+                    .into_iter()
+                    .filter(|&x| x != dependent && !self.solved.contains(&x))
+                    .collect::<Vec<_>>();
+                if pending.is_empty() {
+                    ready.push(dependent);
+                    continue;
+                }
+                self.punted.insert(dependent, pending.len());
+                // Blocking a fragment leaves it waiting on its other dependencies
+                for dependency in pending {
+                    let dependents =
+                        self.pending_on.entry(dependency).or_default();
+                    if !dependents.contains(&dependent) {
+                        dependents.push(dependent);
+                    }
+                }
+            }
+        }
+
+        ready
+    }
+
+    // Undo `State::take_in_flight` once the step that took `id` is done with it
+    fn release_in_flight(&mut self, id: FragmentId) {
+        self.in_flight.remove(&id);
+        self.in_flight_runs.remove(&id);
+    }
+
+    fn is_done(&self) -> bool {
+        self.to_solve.is_empty() && self.in_flight.is_empty()
+    }
+
+    // Count an evaluation attempt of `id`. A fragment is only attempted again after a previous
+    // attempt failed or was interrupted. Fragments that were already evaluated are reported by the
+    // evaluation count check instead
+    fn record_attempt(&mut self, id: FragmentId) {
+        let count = self.attempt_counts.entry(id).or_default();
+        *count += 1;
+        #[cfg(debug_assertions)]
+        assert!(
+            *count == 1
+                || self.interrupted.contains(&id)
+                || self.evaluation_count.contains_key(&id),
+            "fragment {:?} was attempted {} times without being retried",
+            id,
+            count,
+        );
+    }
 
-        if state.to_solve.is_empty() {
-            if state.punted.is_empty() {
+    fn status(&self) -> Status {
+        if self.to_solve.is_empty() {
+            if self.punted.is_empty() {
                 Status::Done
             } else {
                 Status::DoneWithCycles
@@ -212,170 +931,2173 @@ impl<P> Solver<P> {
         }
     }
 
-    /// Enqueue a fragment to be solved.
-    ///
-    /// Only fragments enqueued through this method and their transitive dependencies will be
-    /// considered for evaluation.
-    pub async fn enqueue_fragment(&self, id: FragmentId) -> &Self {
-        self.state.lock().await.to_solve.insert(id);
-
-        self
+    fn detailed_summary(&self) -> DetailedSummary {
+        DetailedSummary {
+            solved: self.solved.len(),
+            pending: self.to_solve.len(),
+            punted: self.punted.len(),
+            in_flight: self.in_flight.len(),
+            failed: self.failed.len(),
+            blocked: self.blocked.len(),
+        }
     }
 
-    /// Get an interator to all fragments that are currently punted. Interpretation of punted
-    /// fragments depends on the current [status](Solver::status):
-    ///
-    /// - [`Status::Pending`]: fragments are pending on dependencies.
-    /// - [`Status::DoneWithCycles`]: fragments are part of one or more cycles.
-    /// - [`Status::Done`]: the returned iterator will be empty.
-    pub async fn punted_iter(&self) -> Vec<FragmentId> {
-        self.state.lock().await.punted.keys().copied().collect()
+    fn lifecycle(&self, id: FragmentId) -> FragmentLifecycle {
+        if self.in_flight.contains(&id) {
+            FragmentLifecycle::InFlight
+        } else if self.failed.contains(&id) {
+            FragmentLifecycle::Failed
+        } else if self.blocked.contains(&id) {
+            FragmentLifecycle::Blocked
+        } else if self.assumed.contains(&id) {
+            FragmentLifecycle::Skipped
+        } else if self.solved.contains(&id) {
+            FragmentLifecycle::Evaluated
+        } else if let Some(&pending_count) = self.punted.get(&id) {
+            FragmentLifecycle::Punted { pending_count }
+        } else if self.to_solve.contains(&id) {
+            FragmentLifecycle::Queued
+        } else {
+            FragmentLifecycle::Unknown
+        }
     }
-}
 
-impl<P> Solver<P>
-where
-    P: Problem,
-{
-    /// Assume the given fragment is already evaluated.
-    pub async fn assume_evaluated(&self, id: FragmentId) -> &Self {
-        self.mark_solved(id, &mut *self.state.lock().await);
+    // Next queued fragment that may be taken by a step: the one with the highest priority, or
+    // with the lowest ID if there is a tie. Fragments without a priority come last
+    fn next_to_solve(&mut self) -> Option<FragmentId> {
+        let allowed = |state: &Self, id: &FragmentId| {
+            state
+                .subset
+                .as_ref()
+                .is_none_or(|subset| subset.contains(id))
+        };
 
-        self
+        // Entries of fragments outside the subset are still valid, so they are pushed back
+        let mut skipped = Vec::new();
+        let mut next = None;
+        while let Some((priority, Reverse(id))) =
+            self.priority_queue.peek().copied()
+        {
+            if self.priority(id) != Some(priority) {
+                self.priority_queue.pop();
+            } else if allowed(self, &id) {
+                next = Some(id);
+                break;
+            } else {
+                skipped.extend(self.priority_queue.pop());
+            }
+        }
+        self.priority_queue.extend(skipped);
+
+        next.or_else(|| {
+            self.to_solve.iter().copied().find(|id| allowed(self, id))
+        })
     }
 
-    /* TODO: rethink about cloning in general
-    /// Create a clone of `self` that assumes some fragments are already evaluated.
-    ///
-    /// This method is useful for trying out assumptions that may need to be discarted.
-    pub async fn clone_with_evaluation_assumptions<A>(
-        &self,
-        assume_evaluated: A,
-    ) -> Self
-    where
-        A: IntoIterator<Item = FragmentId>,
-        P: Clone,
-    {
-        let clone = self.clone();
-        for id in assume_evaluated {
-            clone.assume_evaluated(id).await;
+    // Current priority of a queued fragment, or `None` if it is not queued or has no priority
+    fn priority(&self, id: FragmentId) -> Option<u64> {
+        let boost = self.priorities.get(&id).copied();
+        if !self.to_solve.contains(&id)
+            || (self.priority_function.is_none() && boost.is_none())
+        {
+            return None;
         }
 
-        clone
+        let base = self.queued_priorities.get(&id).copied().unwrap_or(0);
+
+        Some(base.saturating_add(boost.unwrap_or(0)))
     }
-    */
 
-    /// Run the solver until all enqueued fragments and their transitive dependencies are either
-    /// solved or proven to be part of at least one cycle. See the module docs for the limitations
-    /// when `concurrency > 1`.
-    ///
-    /// Returns an interator with all fragments that are part of at least one cycle, if any. See
-    /// [`Solver::punted_iter`].
-    ///
-    /// Returns an error if any evaluation returns an error.
-    ///
-    /// # Known Issues
-    ///
-    /// - If [`Solver::enqueue_fragment`] is called while [`Solver::run`] is executing, those new
-    ///   fragments may not be solved.
-    /// - If [`Solver::run`] returns with an error, the [`Solver`] may be left in an inconsistent
-    ///   state.
-    pub async fn run(
-        &self,
-        concurrency: NonZeroUsize,
-    ) -> Result<Vec<FragmentId>, P::Error> {
-        let mut steps = iter::repeat_with(|| self.step())
-            .take(concurrency.into())
-            .collect::<FuturesUnordered<_>>();
-        loop {
-            // Run a `parallelism` number of `step`s until one of them errors out or we evaluate
-            // all fragments
-            match steps.next().await.unwrap() {
-                Ok(false) => break,
-                Ok(true) => steps.push(self.step()),
-                Err(err) => return Err(err),
-            }
-        }
-        while let Some(res) = steps.next().await {
-            // Make sure all pending `step`s are evaluated to completion
-            if let Err(err) = res {
-                return Err(err);
-            }
+    // Add an entry for the current priority of `id` to `priority_queue`, if it has one
+    fn push_priority(&mut self, id: FragmentId) {
+        if let Some(priority) = self.priority(id) {
+            self.priority_queue.push((priority, Reverse(id)));
         }
+    }
 
-        Ok(self.punted_iter().await)
+    fn rebuild_priority_queue(&mut self) {
+        let queue = self
+            .to_solve
+            .iter()
+            .filter_map(|&id| Some((self.priority(id)?, Reverse(id))))
+            .collect();
+        self.priority_queue = queue;
     }
 
-    /// Run a single solver step for a single fragment.
-    ///
-    /// Returns `false` if there are no more fragments that can be evaluated.
-    ///
-    /// Returns an error if [`Problem::evaluate`] was called and evaluation returned an error.
-    ///
-    /// # Known Issues
+    fn solved_iter(&self) -> impl Iterator<Item = FragmentId> + '_ {
+        #[cfg(feature = "dense-ids")]
+        return self.solved.iter();
+
+        #[cfg(not(feature = "dense-ids"))]
+        return self.solved.iter().copied();
+    }
+
+    // Every fragment with a lifecycle state other than `FragmentLifecycle::Unknown`, sorted by ID
+    fn known_fragments(&self) -> Vec<FragmentId> {
+        let mut ids = self
+            .to_solve
+            .iter()
+            .chain(self.punted.keys())
+            .chain(&self.in_flight)
+            .chain(&self.failed)
+            .chain(&self.blocked)
+            .copied()
+            .chain(self.solved_iter())
+            .collect::<Vec<_>>();
+        ids.sort_unstable();
+        ids.dedup();
+
+        ids
+    }
+}
+
+impl<P> Solver<P> {
+    /// Create a new [`Solver`] instance for a [`Problem`].
+    pub fn new(problem_instance: P) -> Self {
+        Self::with_config(problem_instance, SolverConfig::default())
+    }
+
+    /// Create a new [`Solver`] instance for a [`Problem`] with a custom [`SolverConfig`].
+    pub fn with_config(problem_instance: P, config: SolverConfig) -> Self {
+        Self {
+            state: Mutex::new(State {
+                to_solve: Set::new(),
+                pending_on: Map::new(),
+                punted: Map::new(),
+                solved: DenseSet::new(),
+                in_flight: Set::new(),
+                in_flight_runs: Map::new(),
+                failed: Set::new(),
+                blocked: Set::new(),
+                assumed: Set::new(),
+                dependency_graph: Map::new(),
+                ordering_constraints: Map::new(),
+                prefetched: Map::new(),
+                dep_cache: Map::new(),
+                solved_order: Vec::new(),
+                solved_at_step: Map::new(),
+                attempt_counts: Map::new(),
+                interrupted: Set::new(),
+                outputs: Map::new(),
+                progress_waiters: Vec::new(),
+                done_waiters: Vec::new(),
+                priorities: Map::new(),
+                subset: None,
+                priority_function: None,
+                queued_priorities: Map::new(),
+                priority_queue: BinaryHeap::new(),
+                #[cfg(feature = "std")]
+                run_stats: stats::RunStats::default(),
+                #[cfg(debug_assertions)]
+                evaluation_count: Map::new(),
+                #[cfg(feature = "tokio-runtime")]
+                delayed_queue: BTreeMap::new(),
+            }),
+            dependencies: Mutex::new(Vec::new()),
+            run_lock: Mutex::new(()),
+            running: AtomicBool::new(false),
+            run_count: AtomicUsize::new(0),
+            cancelled_run: AtomicUsize::new(0),
+            steps_taken: AtomicUsize::new(0),
+            evaluations_attempted: AtomicUsize::new(0),
+            max_queue_depth: AtomicUsize::new(0),
+            punts: AtomicUsize::new(0),
+            dependency_fetches: AtomicUsize::new(0),
+            config,
+            pre_hook: None,
+            post_hook: None,
+            solved_hook: None,
+            punted_hook: None,
+            #[cfg(feature = "events")]
+            events: tokio::sync::broadcast::channel(DEFAULT_EVENT_CAPACITY).0,
+            problem_instance,
+        }
+    }
+
+    /// Add a hook that is called right before each [`Problem::evaluate`] call.
+    ///
+    /// Hooks are called synchronously and in the order they were added.
+    pub fn with_pre_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(FragmentId) + Send + Sync + 'static,
+    {
+        self.pre_hook = Some(chain_fragment_hooks(self.pre_hook.take(), hook));
+
+        self
+    }
+
+    /// Add a hook that is called right after each [`Problem::evaluate`] call. The second
+    /// argument is `true` if evaluation succeeded.
+    ///
+    /// Hooks are called synchronously and in the order they were added.
+    pub fn with_post_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(FragmentId, bool) + Send + Sync + 'static,
+    {
+        self.post_hook = Some(chain_post_hooks(self.post_hook.take(), hook));
+
+        self
+    }
+
+    /// Add a hook that is called after a fragment is evaluated successfully and marked as solved.
+    /// Unlike hooks added with [`Solver::with_post_hook`], the solver already considers the
+    /// fragment [evaluated](Solver::is_evaluated) when the hook is called. Fragments that are
+    /// assumed to be evaluated, such as through [`Solver::assume_evaluated`], don't trigger it.
+    ///
+    /// Hooks are called synchronously, in the order they were added, and without holding any
+    /// solver locks.
+    pub fn with_solved_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(FragmentId) + Send + Sync + 'static,
+    {
+        self.solved_hook =
+            Some(chain_fragment_hooks(self.solved_hook.take(), hook));
+
+        self
+    }
+
+    /// Add a hook that is called after a fragment is punted because some of its dependencies
+    /// were not evaluated yet.
+    ///
+    /// Hooks are called synchronously, in the order they were added, and without holding any
+    /// solver locks.
+    pub fn with_punted_hook<F>(mut self, hook: F) -> Self
+    where
+        F: Fn(FragmentId) + Send + Sync + 'static,
+    {
+        self.punted_hook =
+            Some(chain_fragment_hooks(self.punted_hook.take(), hook));
+
+        self
+    }
+
+    /// Get the [`SolverConfig`] used by this solver.
+    pub fn config(&self) -> &SolverConfig {
+        &self.config
+    }
+
+    /// Create a new, independent [`Solver`] for a clone of the [`Problem`] instance, with the same
+    /// [configuration](SolverConfig) and hooks but without any queued, punted, or solved
+    /// fragments. Counters start from zero.
+    pub fn clone_reset(&self) -> Self
+    where
+        P: Clone,
+    {
+        let mut solver =
+            Self::with_config(self.problem_instance.clone(), self.config);
+        solver.pre_hook = self.pre_hook.clone();
+        solver.post_hook = self.post_hook.clone();
+        solver.solved_hook = self.solved_hook.clone();
+        solver.punted_hook = self.punted_hook.clone();
+
+        solver
+    }
+
+    /// Consume `self` and return the wrapped [`Problem`] instance.
+    pub fn into_problem_instance(self) -> P {
+        self.problem_instance
+    }
+
+    /// Require `before` to be evaluated before `after`, even if `after` does not depend on
+    /// `before`. The constraint is treated exactly like a dependency of `after`, so `before` will
+    /// also be evaluated if `after` is. Constraint edges are included in the dependency graph used
+    /// by [`Solver::shortest_dependency_path`] and [`Solver::explain_cycle`].
+    ///
+    /// Constraints only apply to fragments that are considered for evaluation after the constraint
+    /// is added.
+    pub async fn add_ordering_constraint(
+        &self,
+        before: FragmentId,
+        after: FragmentId,
+    ) -> &Self {
+        let mut state = self.state.lock().await;
+        let constraints = state.ordering_constraints.entry(after).or_default();
+        if !constraints.contains(&before) {
+            constraints.push(before);
+        }
+        drop(state);
+
+        self
+    }
+
+    /// Check whether there are fragments waiting to be considered for evaluation. If this returns
+    /// `false`, [`Solver::step`] would return `Ok(false)` right away.
+    ///
+    /// During [`Solver::run_subset`], only fragments in the subset are considered.
+    pub async fn can_make_progress(&self) -> bool {
+        self.state.lock().await.next_to_solve().is_some()
+    }
+
+    /// Wait until [`Solver::can_make_progress`] would return `true`. Fragments become available
+    /// when they are enqueued or when all dependencies of a punted fragment are evaluated.
+    pub async fn wait_for_progress(&self) {
+        let waiter = {
+            let mut state = self.state.lock().await;
+            if state.next_to_solve().is_some() {
+                return;
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            state.progress_waiters.push(sender);
+
+            receiver
+        };
+
+        // Senders are only dropped after sending
+        let _ = waiter.await;
+    }
+
+    /// Wait until there are no fragments queued nor being evaluated, such as when a
+    /// [run](Solver::run) in another task finishes. Returns right away if nothing was enqueued.
+    ///
+    /// The [status](Solver::status) of the solver will be either [`Status::Done`] or
+    /// [`Status::DoneWithCycles`] afterwards, unless more fragments are enqueued in the meantime.
+    pub async fn wait_until_done(&self) {
+        let waiter = {
+            let mut state = self.state.lock().await;
+            if state.is_done() {
+                return;
+            }
+
+            let (sender, receiver) = oneshot::channel();
+            state.done_waiters.push(sender);
+
+            receiver
+        };
+
+        // Senders are only dropped after sending
+        let _ = waiter.await;
+    }
+
+    /// Get a one-line, human-readable summary of the solver progress, such as
+    /// `Solved 42/100 (8 pending, 3 punted, 2 in-flight)`. Failed and blocked fragments are
+    /// appended as `, 1 failed, 4 blocked` if there are any. Suitable for progress bars and
+    /// terminal output. See [`DetailedSummary`] for the format.
+    pub async fn summary(&self) -> String {
+        format!("{}", self.detailed_summary().await)
+    }
+
+    /// Get the number of fragments in each state for programmatic use. See
+    /// [`Solver::summary`].
+    pub async fn detailed_summary(&self) -> DetailedSummary {
+        self.state.lock().await.detailed_summary()
+    }
+
+    /// Get the current [`Status`] of the solver.
+    pub async fn status(&self) -> Status {
+        self.state.lock().await.status()
+    }
+
+    /// Enqueue a fragment to be solved.
+    ///
+    /// Only fragments enqueued through this method and their transitive dependencies will be
+    /// considered for evaluation. Fragments that were already evaluated, such as in a previous
+    /// run, are skipped so they are never evaluated twice.
+    ///
+    /// Fragments that are currently being evaluated are also skipped. Use
+    /// [`Solver::enqueue_if_not_solved`] to know whether the fragment was skipped.
+    pub async fn enqueue_fragment(&self, id: FragmentId) -> &Self {
+        self.enqueue_if_not_solved(id).await;
+
+        self
+    }
+
+    /// Enqueue a fragment only if the solver does not know about it yet. Useful to avoid
+    /// processing the same fragment twice.
+    ///
+    /// Returns `true` if the fragment was newly enqueued, or `false` if it was skipped because it
+    /// is already queued, punted, being evaluated, or solved. Unlike [`Solver::enqueue_fragment`],
+    /// punted fragments are not queued again.
+    pub async fn try_enqueue_fragment(&self, id: FragmentId) -> bool {
+        let mut state = self.state.lock().await;
+        if state.to_solve.contains(&id)
+            || state.punted.contains_key(&id)
+            || state.solved.contains(&id)
+            || state.in_flight.contains(&id)
+        {
+            false
+        } else {
+            self.push_to_solve(id, &mut state);
+
+            true
+        }
+    }
+
+    /// Enqueue a fragment to be solved unless it was already evaluated, such as in a previous
+    /// run, or is currently being evaluated. Same as [`Solver::enqueue_fragment`], but returns
+    /// whether the fragment was skipped instead of `&Self`.
+    ///
+    /// Returns `true` if the fragment was enqueued or `false` if it was skipped.
+    pub async fn enqueue_if_not_solved(&self, id: FragmentId) -> bool {
+        let mut state = self.state.lock().await;
+        if state.solved.contains(&id) || state.in_flight.contains(&id) {
+            false
+        } else {
+            self.push_to_solve(id, &mut state);
+
+            true
+        }
+    }
+
+    /// Remove all queued fragments that are not transitively reachable from `roots`. Call this
+    /// before [`Solver::run`] after speculatively enqueuing fragments to avoid evaluating
+    /// fragments that nothing needs.
+    ///
+    /// Reachability follows the dependencies fetched so far and
+    /// [ordering constraints](Solver::add_ordering_constraint). Roots themselves are always
+    /// reachable.
+    pub async fn prune_unreachable(&self, roots: &[FragmentId]) {
+        let mut state = self.state.lock().await;
+
+        let mut reachable = Set::new();
+        let mut queue = roots.iter().copied().collect::<VecDeque<_>>();
+        while let Some(id) = queue.pop_front() {
+            if !reachable.insert(id) {
+                continue;
+            }
+
+            let dependencies = state.dependency_graph.get(&id).into_iter();
+            let constraints = state.ordering_constraints.get(&id).into_iter();
+            queue.extend(dependencies.chain(constraints).flatten().copied());
+        }
+
+        state.to_solve.retain(|id| reachable.contains(id));
+    }
+
+    /// Increase the scheduling priority of a queued fragment by `boost`. Steps take queued
+    /// fragments with higher priorities first. All fragments start with priority `0` and keep
+    /// their priority until they are evaluated, even if they are punted in the meantime.
+    ///
+    /// Useful to get results for specific fragments sooner, such as the ones a user is currently
+    /// looking at.
+    ///
+    /// Returns `true` if the priority was increased, or `false` if `id` is not queued.
+    pub async fn priority_boost(&self, id: FragmentId, boost: u64) -> bool {
+        let mut state = self.state.lock().await;
+        if !state.to_solve.contains(&id) {
+            return false;
+        }
+
+        let priority = state.priorities.entry(id).or_default();
+        *priority = priority.saturating_add(boost);
+        state.push_priority(id);
+
+        true
+    }
+
+    /// Stop considering a queued or punted fragment for evaluation, such as when whatever needed
+    /// it was cancelled. The fragment can be enqueued again later.
+    ///
+    /// Punted fragments waiting on `id`, directly or transitively, can never be evaluated without
+    /// it, so they are removed as well. They are not enqueued again automatically, even with
+    /// [`SolverConfig::auto_enqueue_missing`].
+    ///
+    /// Returns `true` if the fragment was removed, or `false` if it was neither queued nor punted,
+    /// including when it is being evaluated or was already solved.
+    pub async fn remove_fragment(&self, id: FragmentId) -> bool {
+        let mut state = self.state.lock().await;
+        if !state.to_solve.contains(&id) && !state.punted.contains_key(&id) {
+            return false;
+        }
+
+        let mut removed = Set::new();
+        let mut queue = Vec::from([id]);
+        while let Some(id) = queue.pop() {
+            if !removed.insert(id) {
+                continue;
+            }
+            state.to_solve.remove(&id);
+            state.punted.remove(&id);
+            state.priorities.remove(&id);
+            state.queued_priorities.remove(&id);
+            for dependent in state.pending_on.remove(&id).into_iter().flatten()
+            {
+                if state.punted.contains_key(&dependent) {
+                    queue.push(dependent);
+                }
+            }
+        }
+        // Otherwise removed fragments would be counted twice if they are punted again after being
+        // enqueued again
+        for dependents in state.pending_on.values_mut() {
+            dependents.retain(|dependent| !removed.contains(dependent));
+        }
+        state
+            .pending_on
+            .retain(|_, dependents| !dependents.is_empty());
+        Self::notify_if_done(&mut state);
+
+        true
+    }
+
+    /// Check whether a fragment has been evaluated, or assumed to be evaluated.
+    pub async fn is_evaluated(&self, id: FragmentId) -> bool {
+        self.state.lock().await.solved.contains(&id)
+    }
+
+    /// Get the number of times [`Problem::evaluate`] succeeded for a fragment. This is never
+    /// more than `1`, as the solver panics if a fragment is evaluated twice.
+    ///
+    /// Evaluations are only counted in debug builds. Always returns `0` in release builds.
+    pub async fn evaluate_count_for(&self, id: FragmentId) -> usize {
+        #[cfg(debug_assertions)]
+        {
+            self.state
+                .lock()
+                .await
+                .evaluation_count
+                .get(&id)
+                .copied()
+                .unwrap_or(0)
+        }
+        #[cfg(not(debug_assertions))]
+        {
+            let _ = id;
+
+            0
+        }
+    }
+
+    /// Get the number of times a fragment was taken to be evaluated, including evaluations that
+    /// failed or were interrupted by a timeout or a cancelled run. Punting a fragment doesn't
+    /// count as an attempt, so this is at most `1` unless the fragment was retried.
+    pub async fn fragment_evaluation_attempts(&self, id: FragmentId) -> usize {
+        self.state
+            .lock()
+            .await
+            .attempt_counts
+            .get(&id)
+            .copied()
+            .unwrap_or(0)
+    }
+
+    /// Get all solved fragments in the order they were evaluated or
+    /// [assumed to be evaluated](Solver::assume_evaluated). Fragments are only evaluated after all
+    /// of their dependencies, so this is a topological order of the solved part of the dependency
+    /// graph.
+    pub async fn topological_order(&self) -> Vec<FragmentId> {
+        self.state.lock().await.solved_order.clone()
+    }
+
+    /// Get the generation of a solved fragment in the dependency graph. Fragments without
+    /// dependencies are generation `0` and every other fragment is one generation above its
+    /// highest dependency, so fragments of the same generation can be evaluated in parallel.
+    ///
+    /// Returns `None` for fragments that were not solved, including fragments in cycles.
+    /// Fragments [assumed to be evaluated](Solver::assume_evaluated) are generation `0`.
+    pub async fn fragment_generation(&self, id: FragmentId) -> Option<usize> {
+        generations(&*self.state.lock().await).get(&id).copied()
+    }
+
+    /// Get the highest [generation](Solver::fragment_generation) of all solved fragments, or `0`
+    /// if no fragment was solved.
+    pub async fn max_generation(&self) -> usize {
+        generations(&*self.state.lock().await)
+            .into_values()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Group all fragments in the dependency graph into levels that can be evaluated one after the
+    /// other, with all fragments of a level evaluated in parallel. `levels[0]` holds fragments
+    /// without dependencies and every other fragment is in the level after its highest
+    /// dependency. Each level is sorted.
+    ///
+    /// Only dependencies that were already fetched are considered, so this can be used to plan a
+    /// build after [`Solver::expand_all_dependencies`]. Fragments in cycles, and fragments that
+    /// depend on them, are not included. Unlike [`Solver::fragment_generation`], fragments don't
+    /// need to be solved.
+    pub async fn topological_levels(&self) -> Vec<Vec<FragmentId>> {
+        topological_levels(&*self.state.lock().await)
+    }
+
+    /// Get the index of the [level](Solver::topological_levels) of `id`, or `None` if `id` is not
+    /// in any level.
+    pub async fn level_of(&self, id: FragmentId) -> Option<usize> {
+        topological_levels(&*self.state.lock().await)
+            .iter()
+            .position(|level| level.contains(&id))
+    }
+
+    /// Get all fragments the solver knows about, sorted by ID: queued, punted, in-flight, solved,
+    /// failed, and blocked fragments. These are the fragments whose
+    /// [lifecycle state](Solver::fragment_state) is not [`FragmentLifecycle::Unknown`].
+    pub async fn fragment_ids(&self) -> Vec<FragmentId> {
+        self.state.lock().await.known_fragments()
+    }
+
+    /// Like [`Solver::fragment_ids`], but grouped by [lifecycle state](Solver::fragment_state).
+    /// Punted fragments are grouped by their pending count, as it is part of their state.
+    pub async fn fragment_ids_by_state(
+        &self,
+    ) -> Map<FragmentLifecycle, Vec<FragmentId>> {
+        let state = self.state.lock().await;
+
+        let mut groups = Map::<_, Vec<_>>::new();
+        for id in state.known_fragments() {
+            groups.entry(state.lifecycle(id)).or_default().push(id);
+        }
+
+        groups
+    }
+
+    /// Get the [lifecycle state](FragmentLifecycle) of a fragment.
+    pub async fn fragment_state(&self, id: FragmentId) -> FragmentLifecycle {
+        self.state.lock().await.lifecycle(id)
+    }
+
+    /// Find the shortest chain of direct dependencies leading from `from` to `to`. The returned
+    /// path starts with `from` and ends with `to`. Returns `None` if there is no such path.
+    ///
+    /// Only dependencies that were fetched while solving are considered, so this is most useful
+    /// after the solver is done.
+    pub async fn shortest_dependency_path(
+        &self,
+        from: FragmentId,
+        to: FragmentId,
+    ) -> Option<Vec<FragmentId>> {
+        if from == to {
+            return Some(Vec::from([from]));
+        }
+
+        find_dependency_path(&*self.state.lock().await, from, to, |_| true)
+    }
+
+    /// Get all punted fragments that are waiting for `id` to be evaluated. This is the inverse of
+    /// [`Solver::what_blocks`].
+    pub async fn fragments_pending_on(
+        &self,
+        id: FragmentId,
+    ) -> Vec<FragmentId> {
+        self.state
+            .lock()
+            .await
+            .pending_on
+            .get(&id)
+            .map(|dependents| dependents.to_vec())
+            .unwrap_or_default()
+    }
+
+    /// Get all fragments that punted fragments are waiting on but that will never be evaluated
+    /// because they are not queued, punted, nor being evaluated, such as after
+    /// [`Solver::prune_unreachable`]. Solved fragments and fragments that failed or are blocked
+    /// are not considered missing. Fragments removed with [`Solver::remove_fragment`] never are,
+    /// as the fragments waiting on them are removed too.
+    ///
+    /// If [`SolverConfig::auto_enqueue_missing`] is set, the missing fragments are also enqueued.
+    pub async fn missing_fragments(&self) -> Vec<FragmentId> {
+        let mut state = self.state.lock().await;
+
+        let missing = state
+            .pending_on
+            .keys()
+            .copied()
+            .filter(|id| {
+                !state.to_solve.contains(id)
+                    && !state.solved.contains(id)
+                    && !state.punted.contains_key(id)
+                    && !state.in_flight.contains(id)
+                    && !state.failed.contains(id)
+                    && !state.blocked.contains(id)
+            })
+            .collect::<Vec<_>>();
+        if self.config.auto_enqueue_missing {
+            for &id in &missing {
+                self.push_to_solve(id, &mut state);
+            }
+        }
+
+        missing
+    }
+
+    /// Get all direct dependencies of `id` that were not evaluated yet. Only dependencies that
+    /// were already fetched are considered. This is the inverse of
+    /// [`Solver::fragments_pending_on`].
+    pub async fn what_blocks(&self, id: FragmentId) -> Vec<FragmentId> {
+        let state = self.state.lock().await;
+
+        state
+            .dependency_graph
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|dependency| !state.solved.contains(dependency))
+            .collect()
+    }
+
+    /// Get the number of direct dependencies of `id`. Only dependencies that were already fetched
+    /// are considered.
+    pub async fn fanout(&self, id: FragmentId) -> usize {
+        self.state
+            .lock()
+            .await
+            .dependency_graph
+            .get(&id)
+            .map_or(0, Vec::len)
+    }
+
+    /// Get the number of fragments that directly depend on `id`. Only dependencies that were
+    /// already fetched are considered.
+    pub async fn fanin(&self, id: FragmentId) -> usize {
+        self.state
+            .lock()
+            .await
+            .dependency_graph
+            .values()
+            .filter(|dependencies| dependencies.contains(&id))
+            .count()
+    }
+
+    /// Get the number of dependency edges fetched so far, including edges to fragments that were
+    /// already evaluated. Useful to estimate the complexity of a problem instance.
+    pub async fn total_edge_count(&self) -> usize {
+        self.state
+            .lock()
+            .await
+            .dependency_graph
+            .values()
+            .map(Vec::len)
+            .sum()
+    }
+
+    /// Get the average number of direct dependencies of the fragments whose dependencies were
+    /// fetched so far, or `0.0` if none were. See [`Solver::total_edge_count`].
+    pub async fn average_fanout(&self) -> f64 {
+        let state = self.state.lock().await;
+        if state.dependency_graph.is_empty() {
+            return 0.0;
+        }
+        let edges =
+            state.dependency_graph.values().map(Vec::len).sum::<usize>();
+
+        edges as f64 / state.dependency_graph.len() as f64
+    }
+
+    /// Get the direct dependencies of `id` as returned by [`Problem::direct_dependencies`], plus
+    /// any added with [`Solver::add_ordering_constraint`], or `None` if they were not fetched
+    /// yet. Unlike [`Solver::what_blocks`], evaluated dependencies are included.
+    pub async fn fragment_dependency_list(
+        &self,
+        id: FragmentId,
+    ) -> Option<Vec<FragmentId>> {
+        self.state.lock().await.dependency_graph.get(&id).cloned()
+    }
+
+    /// Get the dependencies of `id` as returned by [`Problem::direct_dependencies`] when a step
+    /// took it, or `None` if no step did yet. Unlike [`Solver::fragment_dependency_list`],
+    /// ordering constraints are not included.
+    ///
+    /// The cache keeps an entry for every fragment taken by a step and grows unboundedly. Use
+    /// [`Solver::clear_dep_cache`] to free it.
+    pub async fn dependencies_of(
+        &self,
+        id: FragmentId,
+    ) -> Option<Vec<FragmentId>> {
+        self.state.lock().await.dep_cache.get(&id).cloned()
+    }
+
+    /// Get the number of fragments in the cache used by [`Solver::dependencies_of`].
+    pub async fn dep_cache_size(&self) -> usize {
+        self.state.lock().await.dep_cache.len()
+    }
+
+    /// Clear the cache used by [`Solver::dependencies_of`]. Solving is not affected.
+    pub async fn clear_dep_cache(&self) {
+        self.state.lock().await.dep_cache.clear();
+    }
+
+    /// Get the longest chain of direct dependencies, starting with a dependent and ending with a
+    /// fragment without dependencies. Only dependencies that were already fetched are
+    /// considered. Edges that would close a cycle are ignored.
+    ///
+    /// The longest path bounds how much evaluation can be parallelized, as its fragments can only
+    /// be evaluated one after the other.
+    pub async fn longest_path(&self) -> Vec<FragmentId> {
+        longest_path(&*self.state.lock().await)
+    }
+
+    /// Get the smallest part of the dependency graph that contains all `roots` and their
+    /// transitive dependencies, such as to generate a build plan for just those fragments. Only
+    /// dependencies that were already fetched are considered.
+    pub async fn dependency_subgraph(
+        &self,
+        roots: &[FragmentId],
+    ) -> DependencySubgraph {
+        let state = self.state.lock().await;
+
+        let mut nodes = roots.iter().copied().collect::<Set<_>>();
+        let mut edges = Vec::new();
+        let mut queue = nodes.iter().copied().collect::<Vec<_>>();
+        while let Some(id) = queue.pop() {
+            for dependency in state
+                .dependency_graph
+                .get(&id)
+                .into_iter()
+                .flatten()
+                .copied()
+            {
+                edges.push((id, dependency));
+                if nodes.insert(dependency) {
+                    queue.push(dependency);
+                }
+            }
+        }
+
+        let mut nodes = nodes.into_iter().collect::<Vec<_>>();
+        nodes.sort_unstable();
+        edges.sort_unstable();
+        edges.dedup();
+
+        DependencySubgraph { nodes, edges }
+    }
+
+    /// Get the largest number of fragments that could be evaluated at the same time, which is an
+    /// upper bound on useful concurrency. This is the size of the largest set of fragments where
+    /// no fragment depends on another, directly or transitively. Only dependencies that were
+    /// already fetched are considered, so call [`Solver::expand_all_dependencies`] first to get
+    /// an estimate for the whole graph.
+    ///
+    /// The result is only meaningful if there are no cycles. Computed from a minimum path cover
+    /// using Dilworth's theorem, which is quadratic on the number of known fragments or worse.
+    pub async fn max_parallelism(&self) -> usize {
+        max_antichain(&*self.state.lock().await)
+    }
+
+    /// Get a histogram of the sizes of the connected components of the dependency graph, ignoring
+    /// edge directions. Maps each component size to the number of components of that size.
+    /// Fragments without known dependencies or dependents are components of size `1`.
+    ///
+    /// All fragments the solver knows about are considered, but only dependencies that were
+    /// already fetched.
+    pub async fn component_sizes(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        for size in component_sizes(&*self.state.lock().await) {
+            *histogram.entry(size).or_default() += 1;
+        }
+
+        histogram
+    }
+
+    /// Get the size of the largest [connected component](Solver::component_sizes) of the
+    /// dependency graph, or `0` if the solver knows no fragments.
+    pub async fn largest_component_size(&self) -> usize {
+        component_sizes(&*self.state.lock().await)
+            .into_iter()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Get an interator to all fragments that are currently punted. Interpretation of punted
+    /// fragments depends on the current [status](Solver::status):
+    ///
+    /// - [`Status::Pending`]: fragments are pending on dependencies.
+    /// - [`Status::DoneWithCycles`]: fragments are part of one or more cycles.
+    /// - [`Status::Done`]: the returned iterator will be empty.
+    pub async fn punted_iter(&self) -> Vec<FragmentId> {
+        self.state.lock().await.punted.keys().copied().collect()
+    }
+
+    /// Get all punted fragments that can only be waiting on dependency cycles. Unlike
+    /// [`Solver::punted_iter`], fragments that are still waiting on queued or in-flight
+    /// fragments, directly or through other punted fragments, are not included, so cycles can be
+    /// reported before the run ends.
+    ///
+    /// Once the solver is no longer [pending](Status::Pending), this is the same as
+    /// [`Solver::punted_iter`].
+    pub async fn fragments_only_blocked_by_cycles(&self) -> Vec<FragmentId> {
+        let state = self.state.lock().await;
+
+        // Start with all punted fragments and remove the ones waiting on anything else until
+        // nothing changes
+        let mut stuck = state.punted.keys().copied().collect::<Set<_>>();
+        loop {
+            let waiting = stuck
+                .iter()
+                .copied()
+                .filter(|id| {
+                    state.dependency_graph.get(id).into_iter().flatten().any(
+                        |dependency| {
+                            !state.solved.contains(dependency)
+                                && !stuck.contains(dependency)
+                        },
+                    )
+                })
+                .collect::<Vec<_>>();
+            if waiting.is_empty() {
+                break;
+            }
+            for id in waiting {
+                stuck.remove(&id);
+            }
+        }
+
+        stuck.into_iter().collect()
+    }
+
+    /// Get all punted fragments that are part of at least one dependency cycle, sorted by ID.
+    /// Unlike [`Solver::cycle_participants`], fragments that are only punted because they depend
+    /// on a cycle are not included, so these are the fragments whose dependencies must change to
+    /// break all cycles.
+    ///
+    /// Searches for a cycle through each punted fragment, so this is quadratic on the number of
+    /// punted fragments or worse.
+    pub async fn cycle_initiators(&self) -> Vec<FragmentId> {
+        let state = self.state.lock().await;
+
+        let mut initiators = state
+            .punted
+            .keys()
+            .copied()
+            .filter(|&id| {
+                find_dependency_path(&state, id, id, |x| {
+                    state.punted.contains_key(&x)
+                })
+                .is_some()
+            })
+            .collect::<Vec<_>>();
+        initiators.sort_unstable();
+
+        initiators
+    }
+
+    /// Suggest punted fragments to [assume evaluated](Solver::assume_evaluated) so that no cycles
+    /// are left, sorted by ID. Assuming all of them and running the solver again evaluates every
+    /// other punted fragment.
+    ///
+    /// Finding the smallest such set, a minimum feedback vertex set, is NP-hard in general, so
+    /// this greedily picks the fragment with the most dependents and dependencies in the
+    /// remaining cycles until none are left. The result is not always minimal, but is close for
+    /// the cycles typically found in practice. This is still quadratic on the number of punted
+    /// fragments or worse.
+    pub async fn cycle_break_suggestions(&self) -> Vec<FragmentId> {
+        feedback_vertex_set(&*self.state.lock().await)
+    }
+
+    /// Count the dependency cycles among punted fragments by size. Each strongly connected
+    /// component of punted fragments is one cycle, even if there are multiple ways around it, and
+    /// a fragment that depends on itself is a cycle of size `1`. Fragments that only depend on a
+    /// cycle are not counted.
+    ///
+    /// Returns an empty map if there are no cycles, such as when the solver is
+    /// [done](Status::Done).
+    pub async fn cycle_size_histogram(&self) -> BTreeMap<usize, usize> {
+        let mut histogram = BTreeMap::new();
+        for size in cycle_sizes(&*self.state.lock().await) {
+            *histogram.entry(size).or_default() += 1;
+        }
+
+        histogram
+    }
+
+    /// Get the number of fragments in the largest cycle, or `0` if there are no cycles. See
+    /// [`Solver::cycle_size_histogram`].
+    pub async fn largest_cycle_size(&self) -> usize {
+        cycle_sizes(&*self.state.lock().await)
+            .into_iter()
+            .max()
+            .unwrap_or(0)
+    }
+
+    /// Get the number of fragments in the smallest cycle, or `0` if there are no cycles. See
+    /// [`Solver::cycle_size_histogram`].
+    pub async fn smallest_cycle_size(&self) -> usize {
+        cycle_sizes(&*self.state.lock().await)
+            .into_iter()
+            .min()
+            .unwrap_or(0)
+    }
+
+    /// Get all punted fragments, sorted by ID. Once the solver is
+    /// [done with cycles](Status::DoneWithCycles), these are the fragments that are either part of
+    /// a cycle, the [initiators](Solver::cycle_initiators), or that depend on one.
+    pub async fn cycle_participants(&self) -> Vec<FragmentId> {
+        let mut participants = self.punted_iter().await;
+        participants.sort_unstable();
+
+        participants
+    }
+
+    /// Check whether a run, such as [`Solver::run`], is in progress.
+    ///
+    /// Unlike most other methods, this doesn't need to be awaited, so it can be used from signal
+    /// handlers and `Drop` implementations. Fragments evaluated through [`Solver::step`] outside
+    /// of a run are not considered.
+    pub fn is_running(&self) -> bool {
+        self.running.load(Ordering::Acquire)
+    }
+
+    /// Get the number of times [`Solver::step`] was called, including steps run by
+    /// [`Solver::run`] and steps that found nothing to do.
+    pub fn steps_taken(&self) -> usize {
+        self.steps_taken.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of times [`Problem::evaluate`] was called, successful or not.
+    pub fn evaluations_attempted(&self) -> usize {
+        self.evaluations_attempted.load(Ordering::Relaxed)
+    }
+
+    /// Get the largest number of fragments that were ever queued to be solved at the same time.
+    pub fn max_queue_depth(&self) -> usize {
+        self.max_queue_depth.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of times a fragment was punted because some of its dependencies were not
+    /// evaluated yet.
+    pub fn punts(&self) -> usize {
+        self.punts.load(Ordering::Relaxed)
+    }
+
+    /// Get the number of times the direct dependencies of a fragment were fetched, either through
+    /// [`Problem::direct_dependencies`] or as part of [`Problem::dependencies_for_batch`].
+    pub fn dependency_fetches(&self) -> usize {
+        self.dependency_fetches.load(Ordering::Relaxed)
+    }
+
+    async fn report(&self) -> SolveReport {
+        let (failed, blocked) = {
+            let state = self.state.lock().await;
+
+            (state.failed.len(), state.blocked.len())
+        };
+
+        SolveReport {
+            status: self.status().await,
+            punted: self.punted_iter().await,
+            failed,
+            blocked,
+        }
+    }
+
+    fn notify_if_done(state: &mut State) {
+        if state.is_done() {
+            for waiter in state.done_waiters.drain(..) {
+                // The waiter may have been dropped already
+                let _ = waiter.send(());
+            }
+        }
+    }
+
+    // Queue fragments left in `in_flight` by the dropped steps of run `run` again, as nothing
+    // else will take them out of it
+    fn requeue_in_flight(&self, run: usize, state: &mut State) {
+        let in_flight_runs = mem::take(&mut state.in_flight_runs);
+        for (id, owner) in in_flight_runs {
+            if owner != run {
+                state.in_flight_runs.insert(id, owner);
+            } else if state.in_flight.remove(&id) {
+                state.interrupted.insert(id);
+                self.push_to_solve(id, state);
+            }
+        }
+    }
+
+    // Requeue the fragments of the last cancelled run, if any
+    fn requeue_cancelled_run(&self, state: &mut State) {
+        let run = self.cancelled_run.swap(0, Ordering::AcqRel);
+        if run != 0 {
+            self.requeue_in_flight(run, state);
+        }
+    }
+
+    fn push_to_solve(&self, id: FragmentId, state: &mut State) {
+        if state.to_solve.insert(id) {
+            #[cfg(feature = "events")]
+            self.emit(SolverEvent::FragmentQueued { id });
+            if let Some(priority_function) = &state.priority_function {
+                let priority = priority_function(id);
+                state.queued_priorities.insert(id, priority);
+            }
+            state.push_priority(id);
+        }
+        self.max_queue_depth
+            .fetch_max(state.to_solve.len(), Ordering::Relaxed);
+        #[cfg(feature = "std")]
+        {
+            let depth = state.to_solve.len();
+            state.run_stats.record_queue_depth(depth);
+        }
+
+        for waiter in state.progress_waiters.drain(..) {
+            // The waiter may have been dropped already
+            let _ = waiter.send(());
+        }
+    }
+}
+
+impl<P> Solver<P>
+where
+    P: Problem,
+{
+    /// Get a clone of the output [`Problem::evaluate`] returned for `id`, or `None` if `id` was
+    /// not evaluated yet, was only assumed to be evaluated, or its output was already taken with
+    /// [`Solver::take_output`].
+    pub async fn get_output(&self, id: FragmentId) -> Option<P::Output>
+    where
+        P::Output: Clone,
+    {
+        self.state
+            .lock()
+            .await
+            .outputs
+            .get(&id)
+            .and_then(|output| output.downcast_ref::<P::Output>())
+            .cloned()
+    }
+
+    /// Take the output [`Problem::evaluate`] returned for `id` out of the solver. Returns `None`
+    /// in the same cases as [`Solver::get_output`]. The fragment stays evaluated.
+    pub async fn take_output(&self, id: FragmentId) -> Option<P::Output> {
+        let output = self.state.lock().await.outputs.remove(&id)?;
+
+        // Only outputs of `P` are ever stored
+        output.downcast().ok().map(|output| *output)
+    }
+
+    /// Assume the given fragment is already evaluated.
+    pub async fn assume_evaluated(&self, id: FragmentId) -> &Self {
+        let mut state = self.state.lock().await;
+        state.assumed.insert(id);
+        self.mark_solved(id, &mut state);
+        drop(state);
+
+        self
+    }
+
+    /// [Assume](Solver::assume_evaluated) all `external` fragments are evaluated, such as
+    /// fragments solved by another solver instance. Punted fragments that were only waiting on
+    /// imported fragments are queued again.
+    ///
+    /// Returns the number of punted fragments that were queued again.
+    pub async fn import_solved<I>(&self, external: I) -> usize
+    where
+        I: IntoIterator<Item = FragmentId>,
+    {
+        let mut state = self.state.lock().await;
+        let punted_before = state.punted.len();
+        let (_, imported_punted) = self.assume_all(external, &mut state);
+
+        punted_before - imported_punted - state.punted.len()
+    }
+
+    /// Bulk version of [`Solver::assume_evaluated`] that locks the solver state only once.
+    ///
+    /// Returns the number of fragments that were not already solved.
+    pub async fn mark_all_solved<I>(&self, ids: I) -> usize
+    where
+        I: IntoIterator<Item = FragmentId>,
+    {
+        self.assume_all(ids, &mut *self.state.lock().await).0
+    }
+
+    /// [Assume](Solver::assume_evaluated) all currently punted fragments are evaluated, breaking
+    /// all cycles at once. Fragments waiting on the cleared fragments can then be evaluated as
+    /// usual. Returns the cleared fragments.
+    ///
+    /// If the solver was [done with cycles](Status::DoneWithCycles), it will be
+    /// [done](Status::Done) afterwards.
+    pub async fn clear_punted(&self) -> Vec<FragmentId> {
+        let mut state = self.state.lock().await;
+        let cleared = state.punted.keys().copied().collect::<Vec<_>>();
+        self.assume_all(cleared.iter().copied(), &mut state);
+
+        cleared
+    }
+
+    /// Explain everything the solver knows about a fragment, such as its
+    /// [lifecycle state](FragmentLifecycle) and which dependencies it is waiting on. Useful to
+    /// debug unexpected solver behavior. Can be called at any time, including during a run.
+    ///
+    /// Only dependencies that were already fetched are considered.
+    pub async fn explain(&self, id: FragmentId) -> FragmentExplanation {
+        let state = self.state.lock().await;
+
+        let (satisfied_dependencies, unsatisfied_dependencies) = state
+            .dependency_graph
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .partition(|dependency| state.solved.contains(dependency));
+        let cycle_path = if state.punted.contains_key(&id) {
+            find_dependency_path(&state, id, id, |x| {
+                state.punted.contains_key(&x)
+            })
+        } else {
+            None
+        };
+
+        FragmentExplanation {
+            state: state.lifecycle(id),
+            unsatisfied_dependencies,
+            satisfied_dependencies,
+            blocked_dependents: state
+                .pending_on
+                .get(&id)
+                .map(|dependents| dependents.to_vec())
+                .unwrap_or_default(),
+            cycle_path,
+            description: self.problem_instance.describe_fragment(id),
+        }
+    }
+
+    /// Explain why a punted fragment is part of a cycle. The returned
+    /// [path](CycleExplanation::path) is the shortest cycle that starts and ends at `id`.
+    ///
+    /// Returns `None` if `id` is not punted or if it is only waiting on dependencies that are not
+    /// part of a cycle with it.
+    pub async fn explain_cycle(
+        &self,
+        id: FragmentId,
+    ) -> Option<CycleExplanation> {
+        let state = self.state.lock().await;
+        if !state.punted.contains_key(&id) {
+            return None;
+        }
+
+        let path = find_dependency_path(&state, id, id, |x| {
+            state.punted.contains_key(&x)
+        })?;
+        let description = path
+            .iter()
+            .map(|&x| self.problem_instance.describe_fragment(x))
+            .collect::<Vec<_>>()
+            .join(" -> ");
+
+        Some(CycleExplanation { path, description })
+    }
+
+    /// Create an independent copy of `self` for a clone of the [`Problem`] instance that also
+    /// [assumes](Solver::assume_evaluated) all `assumptions` are evaluated. Useful to try out
+    /// assumptions that may need to be discarded, such as different ways to break a cycle.
+    ///
+    /// The copy has the same queued, punted, and solved fragments, and the same
+    /// [configuration](SolverConfig) and hooks, but counters start from zero. Punted fragments
+    /// that were only waiting on `assumptions` are queued again right away. Fragments that are
+    /// being evaluated by `self` are queued in the copy, as it can't see those evaluations
+    /// finish. Outputs of evaluated fragments are not copied.
+    pub async fn clone_for_assumption_testing(
+        &self,
+        assumptions: &[FragmentId],
+    ) -> Self
+    where
+        P: Clone,
+    {
+        let mut clone = self.clone_reset();
+        *clone.state.get_mut() = self.state.lock().await.fork();
+        clone.mark_all_solved(assumptions.iter().copied()).await;
+
+        clone
+    }
+
+    /// Run the solver until all enqueued fragments and their transitive dependencies are either
+    /// solved or proven to be part of at least one cycle. See the module docs for the limitations
+    /// when `concurrency > 1`.
+    ///
+    /// Returns a [`SolveReport`] with the final [`Status`] and all fragments that are part of at
+    /// least one cycle, if any. See [`Solver::punted_iter`].
+    ///
+    /// Returns an error if fetching dependencies or any evaluation returns an error. Fragments
+    /// that were still being evaluated at that point are queued again, so the [`Solver`] can be
+    /// run again afterwards.
+    ///
+    /// If another call to [`Solver::run`] is in progress, this call will wait for it to return
+    /// first.
+    ///
+    /// The run is driven by the returned [`SolverHandle`]. Dropping it before it resolves cancels
+    /// the run. See [`SolverHandle`] for details.
+    ///
+    /// The handle is [`Send`] so it can be spawned on a multithreaded executor, which requires
+    /// `P: Sync`. Use [`Solver::run_detailed`] to run problems that are not [`Sync`].
+    ///
+    /// # Known Issues
+    ///
+    /// - If [`Solver::enqueue_fragment`] is called while [`Solver::run`] is executing, those new
+    ///   fragments may not be solved.
+    pub fn run(&self, concurrency: NonZeroUsize) -> SolverHandle<'_, P>
+    where
+        P: Sync,
+        P::Error: Send,
+    {
+        SolverHandle::new(Box::pin(async move {
+            Ok(self.run_detailed(concurrency).await?.into())
+        }))
+    }
+
+    /// Like [`Solver::run`], but returns a [`DetailedSolveReport`] that also lists the fragments
+    /// evaluated during the run in the order they were evaluated.
+    pub async fn run_detailed(
+        &self,
+        concurrency: NonZeroUsize,
+    ) -> Result<DetailedSolveReport, P::Error> {
+        let run = self.begin_run().await;
+        let start = self.state.lock().await.solved_order.len();
+        let res = self.run_steps(run.id(), concurrency).await;
+        let report = run.finish().await;
+        res?;
+
+        let state = self.state.lock().await;
+        let evaluated_in_order = state.solved_order[start..]
+            .iter()
+            .copied()
+            .filter(|id| !state.assumed.contains(id))
+            .collect();
+
+        Ok(DetailedSolveReport {
+            status: report.status,
+            evaluated_in_order,
+            punted: report.punted,
+            failed: report.failed,
+            blocked: report.blocked,
+        })
+    }
+
+    /// Fetch the dependencies of all queued fragments and everything reachable from them, up to
+    /// `concurrency` fragments at a time, without evaluating anything. Afterwards the whole
+    /// dependency graph can be inspected, for example with [`Solver::fanin`],
+    /// [`Solver::fanout`], and [`Solver::longest_path`]. Evaluation proceeds as usual on the next
+    /// [run](Solver::run), which will not fetch the same dependencies again.
+    ///
+    /// Dependencies of already evaluated fragments are not fetched.
+    pub async fn expand_all_dependencies(&self, concurrency: NonZeroUsize) {
+        let queued = self
+            .state
+            .lock()
+            .await
+            .to_solve
+            .iter()
+            .copied()
+            .collect::<Vec<_>>();
+        self.discover(queued, concurrency).await;
+    }
+
+    // Fetch the dependencies of `roots` and everything reachable from them, up to `concurrency`
+    // batches at a time. Fetched dependencies are recorded and kept for the step that takes each
+    // fragment. Returns all discovered fragments that were not evaluated yet
+    async fn discover<I>(
+        &self,
+        roots: I,
+        concurrency: NonZeroUsize,
+    ) -> Vec<FragmentId>
+    where
+        I: IntoIterator<Item = FragmentId>,
+    {
+        let mut discovered = Vec::new();
+        let mut visited = Set::new();
+        let mut queue = roots.into_iter().collect::<VecDeque<_>>();
+        let mut fetches = FuturesUnordered::new();
+        loop {
+            while fetches.len() < concurrency.get() {
+                let mut batch = Vec::new();
+                while batch.len() < self.config.dependency_batch_size.get() {
+                    let id = match queue.pop_front() {
+                        Some(id) => id,
+                        None => break,
+                    };
+                    if visited.insert(id) && !self.is_evaluated(id).await {
+                        batch.push(id);
+                    }
+                }
+                if batch.is_empty() {
+                    break;
+                }
+
+                discovered.extend(batch.iter().copied());
+                fetches.push(async move {
+                    let mut dependencies = batch
+                        .iter()
+                        .map(|&id| {
+                            Vec::with_capacity(
+                                self.problem_instance
+                                    .dependencies_hint(id)
+                                    .unwrap_or(0),
+                            )
+                        })
+                        .collect::<Vec<_>>();
+                    self.dependency_fetches
+                        .fetch_add(batch.len(), Ordering::Relaxed);
+                    let fetched = self
+                        .problem_instance
+                        .dependencies_for_batch(&batch, &mut dependencies)
+                        .await;
+                    if fetched.is_err() {
+                        // Nothing is recorded for the batch then, so steps fetch the
+                        // dependencies again and report the error
+                        dependencies.clear();
+                    }
+                    for (&id, dependencies) in
+                        batch.iter().zip(&mut dependencies)
+                    {
+                        self.problem_instance
+                            .preferred_dependencies_order(id, dependencies)
+                            .await;
+                    }
+
+                    (batch, dependencies)
+                });
+            }
+
+            let (batch, dependencies) = match fetches.next().await {
+                Some(fetched) => fetched,
+                None => return discovered,
+            };
+            let mut state = self.state.lock().await;
+            for (id, dependencies) in batch.into_iter().zip(dependencies) {
+                let mut recorded = dependencies.clone();
+                state.add_ordering_constraints(id, &mut recorded);
+                queue.extend(recorded.iter().copied());
+                state.dependency_graph.insert(id, recorded);
+                state.prefetched.insert(id, dependencies);
+            }
+        }
+    }
+
+    /// Discover `root` and all of its transitive dependencies, enqueue all of them and then
+    /// [run](Solver::run) the solver. Unlike [`Solver::enqueue_fragment`] followed by
+    /// [`Solver::run`], every dependency is fetched before the first evaluation, so all queued
+    /// fragments are known when the run starts.
+    ///
+    /// Dependencies of already evaluated fragments are not fetched.
+    pub async fn solve_transitive_closure(
+        &self,
+        root: FragmentId,
+        concurrency: NonZeroUsize,
+    ) -> Result<SolveReport, P::Error> {
+        for id in self.discover([root], concurrency).await {
+            self.enqueue_fragment(id).await;
+        }
+
+        Ok(self.run_detailed(concurrency).await?.into())
+    }
+
+    /// Like [`Solver::run`], but steps take the queued fragment for which `priority` returns the
+    /// highest value first, instead of the first queued fragment. Ties are broken by taking the
+    /// fragment with the lowest ID. `priority` is called once for each fragment that is queued
+    /// when the run starts and once each time a fragment is enqueued during the run.
+    ///
+    /// Boosts from [`Solver::priority_boost`] are added to the priority returned by `priority`.
+    pub async fn run_with_priority_function<F>(
+        &self,
+        concurrency: NonZeroUsize,
+        priority: F,
+    ) -> Result<SolveReport, P::Error>
+    where
+        F: Fn(FragmentId) -> u64 + Send + Sync + 'static,
+    {
+        let run = self.begin_run().await;
+        {
+            let mut state = self.state.lock().await;
+            let queued_priorities = state
+                .to_solve
+                .iter()
+                .map(|&id| (id, priority(id)))
+                .collect();
+            state.queued_priorities = queued_priorities;
+            state.priority_function = Some(Box::new(priority));
+            state.rebuild_priority_queue();
+        }
+        let res = self.run_steps(run.id(), concurrency).await;
+        {
+            let mut state = self.state.lock().await;
+            state.priority_function = None;
+            state.queued_priorities.clear();
+            state.rebuild_priority_queue();
+        }
+        let report = run.finish().await;
+        res?;
+
+        Ok(report)
+    }
+
+    /// Like [`Solver::run`], but only solves the queued fragments that are transitively reachable
+    /// from `ids`, including `ids` themselves. Other queued fragments stay queued for a later run,
+    /// even if they become ready to be evaluated during this run. The dependencies of everything
+    /// reachable from `ids` are fetched before the run starts.
+    ///
+    /// The returned [`SolveReport`] covers the whole solver, not just the subset.
+    pub async fn run_subset(
+        &self,
+        ids: &[FragmentId],
+        concurrency: NonZeroUsize,
+    ) -> Result<SolveReport, P::Error> {
+        let subset = self
+            .discover(ids.iter().copied(), concurrency)
+            .await
+            .into_iter()
+            .collect();
+
+        let run = self.begin_run().await;
+        self.state.lock().await.subset = Some(subset);
+        let res = self.run_steps(run.id(), concurrency).await;
+        self.state.lock().await.subset = None;
+        let report = run.finish().await;
+        res?;
+
+        Ok(report)
+    }
+
+    /// Like [`Solver::run`], but with the concurrency from [`with_default_concurrency`], and
+    /// returns a [`SolveReport`].
+    ///
+    /// Only available with the `std` feature.
+    #[cfg(feature = "std")]
+    pub async fn run_default(&self) -> Result<SolveReport, P::Error> {
+        let run = self.begin_run().await;
+        let res = self.run_steps(run.id(), with_default_concurrency()).await;
+        let report = run.finish().await;
+        res?;
+
+        Ok(report)
+    }
+
+    /// Blocking version of [`Solver::run`] for synchronous code, such as build scripts. The run is
+    /// driven on the current thread, so no async runtime is needed.
+    ///
+    /// Must not be called from async code, as it blocks the current thread until the run is done.
+    /// [`Problem`] implementations that need a specific runtime, such as `tokio` timers, will not
+    /// work either.
+    #[cfg(feature = "std")]
+    pub fn run_to_completion_sync(
+        &self,
+        concurrency: NonZeroUsize,
+    ) -> Result<SolveReport, P::Error> {
+        futures::executor::block_on(async {
+            Ok(self.run_detailed(concurrency).await?.into())
+        })
+    }
+
+    /// Like [`Solver::run`], but evaluation errors do not stop the run. Instead, `handler` is
+    /// called with each error and the run continues with all fragments that do not depend on a
+    /// failed fragment. Fragments that do are *blocked* and will never be evaluated.
+    ///
+    /// Returns a [`SolveReport`] with the number of failed and blocked fragments. Note that its
+    /// [status](SolveReport::status) only reflects punted fragments.
+    pub async fn run_with_error_handler<H>(
+        &self,
+        concurrency: NonZeroUsize,
+        handler: H,
+    ) -> SolveReport
+    where
+        H: Fn(FragmentId, P::Error) + Send + Sync,
+    {
+        let run = self.begin_run().await;
+        let mut steps = FuturesUnordered::new();
+        while let ControlFlow::Break((id, err)) = self
+            .drive_steps(
+                &mut steps,
+                concurrency,
+                || self.run_step(run.id()),
+                |_| ControlFlow::Continue(()),
+                |id, err| ControlFlow::Break((id, err)),
+            )
+            .await
+        {
+            self.mark_blocked(id, &mut *self.state.lock().await);
+            handler(id, err);
+        }
+
+        run.finish().await
+    }
+
+    /// Like [`Solver::run`], but returns as soon as fetching dependencies or an evaluation fails,
+    /// without waiting for other steps to finish. Their evaluations are cancelled and their
+    /// fragments are queued again, so they can still be evaluated by a later run.
+    ///
+    /// Returns the fragment that failed together with its error, or `None` if no step failed.
+    pub async fn run_until_first_error(
+        &self,
+        concurrency: NonZeroUsize,
+    ) -> Option<(FragmentId, P::Error)> {
+        let run = self.begin_run().await;
+        let mut steps = FuturesUnordered::new();
+        let first_error = self
+            .drive_steps(
+                &mut steps,
+                concurrency,
+                || self.run_step(run.id()),
+                |_| ControlFlow::Continue(()),
+                |id, err| ControlFlow::Break((id, err)),
+            )
+            .await
+            .break_value();
+        // Dropping the remaining steps cancels their evaluations. Their fragments are queued
+        // again when the run finishes
+        drop(steps);
+        let _ = run.finish().await;
+
+        first_error
+    }
+
+    // Wait for other runs to finish, then notify the problem instance and event subscribers
+    // that a new run is starting
+    async fn begin_run(&self) -> RunGuard<'_, P> {
+        let lock = self.run_lock.lock().await;
+        self.start_run(Some(lock)).await
+    }
+
+    // Like `begin_run`, for callers that already hold the run lock, such as to start several runs
+    // in a row without other runs in between
+    #[cfg(feature = "benchmarking")]
+    async fn begin_locked_run(
+        &self,
+        _run_lock: &MutexGuard<'_, ()>,
+    ) -> RunGuard<'_, P> {
+        self.start_run(None).await
+    }
+
+    async fn start_run<'a>(
+        &'a self,
+        lock: Option<MutexGuard<'a, ()>>,
+    ) -> RunGuard<'a, P> {
+        self.requeue_cancelled_run(&mut *self.state.lock().await);
+        let id = self.run_count.fetch_add(1, Ordering::Relaxed) + 1;
+        let run = RunGuard::new(self, id, lock);
+
+        self.running.store(true, Ordering::Release);
+        #[cfg(feature = "events")]
+        self.emit(SolverEvent::SolverStarted);
+        self.problem_instance.on_start().await;
+
+        run
+    }
+
+    // Notify the problem instance and event subscribers that a run finished
+    async fn finish_run(&self) -> SolveReport {
+        let report = self.report().await;
+        self.problem_instance.on_finish(&report).await;
+        #[cfg(feature = "events")]
+        {
+            if !report.punted.is_empty() {
+                self.emit(SolverEvent::CycleFinalized {
+                    members: report.punted.clone(),
+                });
+            }
+            self.emit(SolverEvent::SolverFinished {
+                status: report.status,
+                report: report.clone(),
+            });
+        }
+        self.running.store(false, Ordering::Release);
+
+        report
+    }
+
+    async fn run_steps(
+        &self,
+        run: usize,
+        concurrency: NonZeroUsize,
+    ) -> Result<(), P::Error> {
+        let mut steps = FuturesUnordered::new();
+        match self
+            .drive_steps(
+                &mut steps,
+                concurrency,
+                || self.run_step(run),
+                |_| ControlFlow::Continue(()),
+                |_, err| ControlFlow::Break(err),
+            )
+            .await
+        {
+            ControlFlow::Continue(()) => Ok(()),
+            ControlFlow::Break(err) => Err(err),
+        }
+    }
+
+    // Keep up to `concurrency` steps started by `start_step` in `steps` until no more progress can
+    // be made, passing the result of each step to `on_progress` or `on_error`. Returns as soon as
+    // either returns `ControlFlow::Break`, leaving the other steps in `steps`, so the caller can
+    // handle the break and call this again to carry on. Cancelling the returned future loses no
+    // step results either
+    async fn drive_steps<S, T, F, E, B>(
+        &self,
+        steps: &mut FuturesUnordered<S>,
+        concurrency: NonZeroUsize,
+        mut start_step: T,
+        mut on_progress: F,
+        mut on_error: E,
+    ) -> ControlFlow<B>
+    where
+        S: Future<Output = Result<Progress, (FragmentId, P::Error)>>,
+        T: FnMut() -> S,
+        F: FnMut(Progress) -> ControlFlow<B>,
+        E: FnMut(FragmentId, P::Error) -> ControlFlow<B>,
+    {
+        loop {
+            // A step that finds nothing to do does not mean we are done: the steps still running
+            // may queue more fragments when they finish
+            while steps.len() < concurrency.get()
+                && self.can_make_progress().await
+            {
+                steps.push(start_step());
+            }
+            let flow = match steps.next().await {
+                Some(Ok(progress)) => on_progress(progress),
+                Some(Err((id, err))) => on_error(id, err),
+                // Enqueuing missing fragments gives the run more to do
+                None if self.config.auto_enqueue_missing
+                    && !self.missing_fragments().await.is_empty() =>
+                {
+                    continue
+                }
+                None => return ControlFlow::Continue(()),
+            };
+            if flow.is_break() {
+                return flow;
+            }
+        }
+    }
+
+    /// Enqueue a fragment and run the solver. Equivalent to [`Solver::enqueue_fragment`]
+    /// followed by [`Solver::run`], but returns a [`SolveReport`].
+    pub async fn solve_fragment(
+        &self,
+        id: FragmentId,
+        concurrency: NonZeroUsize,
+    ) -> Result<SolveReport, P::Error> {
+        self.solve_fragments(iter::once(id), concurrency).await
+    }
+
+    /// Enqueue multiple fragments and run the solver. Equivalent to calling
+    /// [`Solver::enqueue_fragment`] for each fragment followed by [`Solver::run`], but returns a
+    /// [`SolveReport`].
+    pub async fn solve_fragments<I>(
+        &self,
+        ids: I,
+        concurrency: NonZeroUsize,
+    ) -> Result<SolveReport, P::Error>
+    where
+        I: IntoIterator<Item = FragmentId>,
+    {
+        for id in ids {
+            self.enqueue_fragment(id).await;
+        }
+        Ok(self.run_detailed(concurrency).await?.into())
+    }
+
+    /// Evaluate a fragment right away instead of waiting for a step to take it from the queue,
+    /// such as when an external event guarantees its evaluation will succeed now. Fragments
+    /// waiting on it are queued again as usual.
+    ///
+    /// Does nothing if `id` was already solved or is being handled by a step, so it is never
+    /// evaluated twice, nor if the solver doesn't know about `id` yet, as its dependencies were
+    /// never fetched. Fragments blocked by a failed dependency are not evaluated either. A queued
+    /// or punted fragment is taken out of the queue first. Evaluating a punted fragment skips its
+    /// unevaluated dependencies, which is reported by [`ImmediateOutcome::EvaluatedEarly`].
+    ///
+    /// Returns an error if [`Problem::evaluate`] returned an error. The fragment is failed in
+    /// that case.
+    pub async fn evaluate_immediately(
+        &self,
+        id: FragmentId,
+    ) -> Result<ImmediateOutcome, P::Error> {
+        let pending_count = {
+            let mut state = self.state.lock().await;
+            match state.lifecycle(id) {
+                FragmentLifecycle::Unknown => {
+                    return Ok(ImmediateOutcome::Unknown)
+                }
+                FragmentLifecycle::Evaluated
+                | FragmentLifecycle::Skipped
+                | FragmentLifecycle::InFlight => {
+                    return Ok(ImmediateOutcome::AlreadyHandled)
+                }
+                FragmentLifecycle::Blocked => {
+                    return Ok(ImmediateOutcome::Blocked)
+                }
+                _ => (),
+            }
+
+            if state.to_solve.remove(&id) {
+                state.queued_priorities.remove(&id);
+            }
+            let pending_count = state.punted.remove(&id).unwrap_or(0);
+            state.record_attempt(id);
+            state.take_in_flight(id, None);
+
+            pending_count
+        };
+
+        let step = self.steps_taken.load(Ordering::Relaxed);
+        self.evaluate_in_flight(id, step, None)
+            .await
+            .map_err(|(_, err)| err)?;
+
+        Ok(if pending_count > 0 {
+            ImmediateOutcome::EvaluatedEarly { pending_count }
+        } else {
+            ImmediateOutcome::Evaluated
+        })
+    }
+
+    /// Run a single solver step for a single fragment.
+    ///
+    /// Returns `false` if there are no more fragments that can be evaluated.
+    ///
+    /// Returns an error if [`Problem::direct_dependencies`] or [`Problem::evaluate`] was called
+    /// and returned an error. The fragment is failed either way.
+    ///
+    /// # Known Issues
     ///
     /// - If [`Solver::step`] is not run to completion the [`Solver`] may be left in an
     ///   inconsistent state.
+    #[must_use = "must check whether evaluation succeeded"]
     pub async fn step(&self) -> Result<bool, P::Error> {
+        self.step_impl()
+            .await
+            .map(|progress| !matches!(progress, Progress::Idle))
+            .map_err(|(_, err)| err)
+    }
+
+    /// Call [`Solver::step`] one at a time until no more progress is possible, including calls to
+    /// [`Problem::on_start`] and [`Problem::on_finish`] as with [`Solver::run`]. Useful to measure
+    /// how fast iterative problems converge.
+    ///
+    /// Returns the number of steps that evaluated, punted, or blocked a fragment, not including
+    /// the final step that found nothing to do. Unlike [`Solver::steps_taken`], this only counts
+    /// the steps of this call and the count is exact, as steps never run concurrently.
+    ///
+    /// Returns the first error returned by [`Problem::direct_dependencies`] or
+    /// [`Problem::evaluate`].
+    pub async fn step_count_until_stable(&self) -> Result<usize, P::Error> {
+        let run = self.begin_run().await;
+        let mut count = 0;
+        let res = loop {
+            match self.run_step(run.id()).await {
+                Ok(Progress::Idle) => break Ok(count),
+                Ok(_) => count += 1,
+                Err((_, err)) => break Err(err),
+            }
+        };
+        let _ = run.finish().await;
+
+        res
+    }
+
+    /// Get the number of steps this solver had taken when `id` was evaluated, including the step
+    /// that evaluated it, or `None` if `id` was not evaluated. Steps are counted as in
+    /// [`Solver::steps_taken`], so steps of earlier runs and steps that found nothing to do are
+    /// included.
+    ///
+    /// Fragments [assumed to be evaluated](Solver::assume_evaluated) return `None`.
+    pub async fn steps_to_solve(&self, id: FragmentId) -> Option<usize> {
+        self.state.lock().await.solved_at_step.get(&id).copied()
+    }
+
+    /// Run up to `max` solver steps concurrently, one for each fragment that is currently queued.
+    /// Dependencies are fetched and fragments evaluated concurrently, as if by [`Solver::run`]
+    /// with a concurrency of `max`, which saves the overhead of awaiting each [`Solver::step`]
+    /// in turn.
+    ///
+    /// Returns the number of fragments that were evaluated, punted, or blocked, which is `0` if
+    /// no fragment can be evaluated.
+    ///
+    /// Returns the first error returned by [`Problem::evaluate`], once all other steps returned.
+    ///
+    /// # Known Issues
+    ///
+    /// - If an evaluation fails, the [`Solver`] may be left in an inconsistent state, as with
+    ///   [`Solver::step`].
+    #[must_use = "must check whether evaluation succeeded"]
+    pub async fn step_batch(&self, max: usize) -> Result<usize, P::Error> {
+        let count = max.min(self.state.lock().await.to_solve.len());
+        let mut steps = (0..count)
+            .map(|_| self.step_impl())
+            .collect::<FuturesUnordered<_>>();
+
+        // Dropping the other steps would leave their fragments in flight, so they are all awaited
+        // even after a step failed
+        let mut processed = 0;
+        let mut first_error = None;
+        while let Some(res) = steps.next().await {
+            match res {
+                Ok(Progress::Idle) => (),
+                Ok(_) => processed += 1,
+                Err((_, err)) => {
+                    first_error.get_or_insert(err);
+                }
+            }
+        }
+
+        match first_error {
+            Some(err) => Err(err),
+            None => Ok(processed),
+        }
+    }
+
+    async fn step_impl(&self) -> Result<Progress, (FragmentId, P::Error)> {
+        self.step_until(None, None).await
+    }
+
+    // Like `step_impl`, but the fragment is tagged as taken by run `run`, so it is queued again if
+    // the run is cancelled
+    async fn run_step(
+        &self,
+        run: usize,
+    ) -> Result<Progress, (FragmentId, P::Error)> {
+        self.step_until(None, Some(run)).await
+    }
+
+    // Like `step_impl`, but cancels `Problem::evaluate` and queues the fragment again if
+    // `deadline` completes first
+    async fn step_until(
+        &self,
+        deadline: Option<Deadline>,
+        run: Option<usize>,
+    ) -> Result<Progress, (FragmentId, P::Error)> {
+        let step = self.steps_taken.fetch_add(1, Ordering::Relaxed) + 1;
+
         let item = {
             let mut state = self.state.lock().await;
+            // The steps of a cancelled run were dropped with it
+            self.requeue_cancelled_run(&mut state);
 
-            state
-                .to_solve
-                .iter()
-                .next()
-                .copied()
-                .map(|x| state.to_solve.take(&x).unwrap())
+            let item = state.next_to_solve();
+            if let Some(id) = item {
+                state.to_solve.remove(&id);
+                state.queued_priorities.remove(&id);
+                state.take_in_flight(id, run);
+            }
+
+            item
         };
 
         match item {
             Some(id) => {
-                let mut dependencies = self.dependencies.lock().await;
+                // Take the scratch vector out so other steps can fetch dependencies at the same
+                // time. They allocate their own vector while this one is taken
+                let mut dependencies =
+                    mem::take(&mut *self.dependencies.lock().await);
                 dependencies.clear();
-                self.problem_instance
-                    .direct_dependencies(id, &mut dependencies)
-                    .await;
+                let prefetched = self.state.lock().await.prefetched.remove(&id);
+                match prefetched {
+                    Some(prefetched) => dependencies.extend(prefetched),
+                    None => {
+                        if let Some(hint) =
+                            self.problem_instance.dependencies_hint(id)
+                        {
+                            dependencies.reserve(hint);
+                        }
+                        self.dependency_fetches.fetch_add(1, Ordering::Relaxed);
+                        let fetched = self
+                            .problem_instance
+                            .direct_dependencies(id, &mut dependencies)
+                            .await;
+                        if let Err(err) = fetched {
+                            let mut state = self.state.lock().await;
+                            state.release_in_flight(id);
+                            state.failed.insert(id);
+                            Self::notify_if_done(&mut state);
+
+                            return Err((id, err));
+                        }
+                        self.problem_instance
+                            .preferred_dependencies_order(id, &mut dependencies)
+                            .await;
+                    }
+                }
                 let mut state = self.state.lock().await;
+                state.dep_cache.insert(id, dependencies.clone());
+                state.add_ordering_constraints(id, &mut dependencies);
+                state.dependency_graph.insert(id, dependencies.clone());
+                #[cfg(feature = "events")]
+                self.emit(SolverEvent::DependenciesFetched {
+                    id,
+                    dep_count: dependencies.len(),
+                });
                 dependencies.retain(|x| !state.solved.contains(x));
 
-                if dependencies.is_empty() {
+                if dependencies.iter().any(|x| {
+                    state.failed.contains(x) || state.blocked.contains(x)
+                }) {
+                    state.release_in_flight(id);
+                    state.blocked.insert(id);
+                    self.mark_blocked(id, &mut state);
+                    Self::notify_if_done(&mut state);
+                    drop(state);
+                    *self.dependencies.lock().await = dependencies;
+
+                    Ok(Progress::Blocked)
+                } else if dependencies.is_empty() {
                     // Drop all locks before calling `evaluate`to allow other calls to `step` to
                     // progress while `evaluate` is running. And we only need to lock `self.state`
                     // again if `evaluate` is successful
-                    drop(dependencies);
+                    state.record_attempt(id);
                     drop(state);
+                    *self.dependencies.lock().await = dependencies;
 
-                    match self.problem_instance.evaluate(id).await {
-                        Ok(()) => {
-                            // TODO: take a deeper look here to make sure there are no possible
-                            // race condition between dropping the state lock and locking it again
-                            // here
-                            self.mark_solved(id, &mut *self.state.lock().await);
-
-                            Ok(true)
-                        }
-                        Err(err) => Err(err),
-                    }
+                    self.evaluate_in_flight(id, step, deadline).await
                 } else {
+                    state.release_in_flight(id);
                     self.mark_punted(id, &dependencies, &mut state);
+                    Self::notify_if_done(&mut state);
+                    drop(state);
+                    *self.dependencies.lock().await = dependencies;
+                    if let Some(hook) = &self.punted_hook {
+                        hook(id);
+                    }
+
+                    Ok(Progress::Punted(id))
+                }
+            }
+            None => Ok(Progress::Idle),
+        }
+    }
+
+    // Evaluate `id`, which must be in `in_flight` with all of its dependencies solved. No locks
+    // may be held
+    async fn evaluate_in_flight(
+        &self,
+        id: FragmentId,
+        step: usize,
+        deadline: Option<Deadline>,
+    ) -> Result<Progress, (FragmentId, P::Error)> {
+        if let Some(hook) = &self.pre_hook {
+            hook(id);
+        }
+        self.evaluations_attempted.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "events")]
+        self.emit(SolverEvent::FragmentEvaluating { id });
+        #[cfg(feature = "std")]
+        let start = std::time::Instant::now();
+        let evaluation = self.problem_instance.evaluate(id);
+        let res = match deadline {
+            Some(deadline) => match select(evaluation, deadline).await {
+                Either::Left((res, _)) => Some(res),
+                Either::Right(((), _)) => None,
+            },
+            None => Some(evaluation.await),
+        };
+        if let Some(hook) = &self.post_hook {
+            hook(id, res.as_ref().is_some_and(Result::is_ok));
+        }
+        let res = match res {
+            Some(res) => res,
+            None => {
+                // `id` was not evaluated, so it must be retried later
+                let mut state = self.state.lock().await;
+                state.release_in_flight(id);
+                state.interrupted.insert(id);
+                self.push_to_solve(id, &mut state);
 
-                    Ok(true)
+                return Ok(Progress::TimedOut);
+            }
+        };
+
+        match res {
+            Ok(output) => {
+                // Other `step`s may have run while `evaluate` was running. One of
+                // them may have punted a dependent of `id` after seeing `id` as
+                // unsolved, which would normally push `id` back to `to_solve` and
+                // evaluate it a second time. `id` stays in `in_flight` until we get
+                // here, and both `mark_punted` and `enqueue_fragment` skip in-flight
+                // fragments, so the dependent is only registered in `pending_on` and
+                // gets requeued by `mark_solved` below
+                #[cfg(feature = "std")]
+                let duration = start.elapsed();
+                #[cfg(feature = "events")]
+                self.emit(SolverEvent::FragmentSolved { id, duration });
+                let mut state = self.state.lock().await;
+                #[cfg(feature = "std")]
+                state.run_stats.record_evaluation(id, duration);
+                #[cfg(debug_assertions)]
+                {
+                    let count = state.evaluation_count.entry(id).or_default();
+                    *count += 1;
+                    assert!(
+                        *count == 1,
+                        "fragment {:?} was evaluated {} times",
+                        id,
+                        count,
+                    );
                 }
+                state.solved_at_step.insert(id, step);
+                state.outputs.insert(id, Box::new(output));
+                self.mark_solved(id, &mut state);
+                drop(state);
+                if let Some(hook) = &self.solved_hook {
+                    hook(id);
+                }
+
+                Ok(Progress::Evaluated(id))
+            }
+            Err(err) => {
+                let mut state = self.state.lock().await;
+                #[cfg(feature = "std")]
+                state.run_stats.record_evaluation(id, start.elapsed());
+                state.release_in_flight(id);
+                state.failed.insert(id);
+                Self::notify_if_done(&mut state);
+
+                Err((id, err))
+            }
+        }
+    }
+
+    // Assume all `ids` are evaluated. Returns the number of fragments that were not solved before
+    // and how many of those were punted
+    fn assume_all<I>(&self, ids: I, state: &mut State) -> (usize, usize)
+    where
+        I: IntoIterator<Item = FragmentId>,
+    {
+        let mut unique = Set::new();
+        let ids = ids
+            .into_iter()
+            .filter(|id| !state.solved.contains(id) && unique.insert(*id))
+            .collect::<Vec<_>>();
+
+        // Unqueue and unpunt everything first. Otherwise solving one of the fragments could requeue
+        // another
+        let mut punted = 0;
+        for id in &ids {
+            state.to_solve.remove(id);
+            if state.punted.remove(id).is_some() {
+                punted += 1;
             }
-            None => Ok(false),
         }
+        for id in ids.iter().copied() {
+            state.assumed.insert(id);
+            self.mark_solved(id, state);
+        }
+
+        (ids.len(), punted)
     }
 
     fn mark_solved(&self, id: FragmentId, state: &mut State) {
-        state.solved.insert(id);
+        state.release_in_flight(id);
+        state.failed.remove(&id);
+        state.blocked.remove(&id);
+        state.priorities.remove(&id);
+        if state.solved.insert(id) {
+            state.solved_order.push(id);
+        }
+        for dependent in state.unblock_dependents(id) {
+            self.push_to_solve(dependent, state);
+        }
 
         if let Some(dependents) = state.pending_on.remove(&id) {
             for dependent in dependents {
-                if *state.punted.get(&dependent).unwrap() == 1 {
-                    state.punted.remove(&dependent);
-                    state.to_solve.insert(dependent);
-                } else {
-                    *state.punted.get_mut(&dependent).unwrap() -= 1;
+                // Dependents that are not punted anymore were blocked
+                if let Some(pending) = state.punted.get_mut(&dependent) {
+                    *pending -= 1;
+                    if *pending == 0 {
+                        state.punted.remove(&dependent);
+                        self.push_to_solve(dependent, state);
+                    }
+                }
+            }
+        }
+        Self::notify_if_done(state);
+    }
+
+    // Block all punted fragments that are waiting on `id`, directly or transitively
+    fn mark_blocked(&self, id: FragmentId, state: &mut State) {
+        let mut queue = Vec::from([id]);
+        while let Some(id) = queue.pop() {
+            for dependent in state.pending_on.remove(&id).into_iter().flatten()
+            {
+                if state.punted.remove(&dependent).is_some() {
+                    state.blocked.insert(dependent);
+                    queue.push(dependent);
                 }
             }
         }
@@ -387,14 +3109,25 @@ where
         dependencies: &[FragmentId],
         state: &mut State,
     ) {
-        state.punted.insert(id, dependencies.len());
+        // A fragment that depends on itself is never unblocked by its own evaluation, so it is not
+        // counted or registered as its own dependent. Such a fragment stays punted even after all
+        // other dependencies are evaluated, as it is punted again the next time it is taken
+        let dependencies = dependencies.iter().copied().filter(|&x| x != id);
+        let pending = dependencies.clone().count();
+        state.punted.insert(id, pending);
+        self.punts.fetch_add(1, Ordering::Relaxed);
+        #[cfg(feature = "events")]
+        self.emit(SolverEvent::FragmentPunted {
+            id,
+            pending_deps: pending,
+        });
 
-        for dependency in dependencies.iter().copied() {
-            if dependency != id
-                && !state.solved.contains(&dependency)
+        for dependency in dependencies {
+            if !state.solved.contains(&dependency)
                 && !state.punted.contains_key(&dependency)
+                && !state.in_flight.contains(&dependency)
             {
-                state.to_solve.insert(dependency);
+                self.push_to_solve(dependency, state);
             }
             state.pending_on.entry(dependency).or_default().push(id);
         }
@@ -402,6 +3135,8 @@ where
 }
 
 /// Current status of a [`Solver`] instance.
+///
+/// Displayed as `done`, `done_with_cycles`, or `pending`. Use [`str::parse`] to convert back.
 #[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
 pub enum Status {
     /// All fragments have been successfully evaluated and no cycles were found.
@@ -414,3 +3149,736 @@ pub enum Status {
     /// The solver is still running and there are still fragments that may be evaluated.
     Pending,
 }
+
+impl fmt::Display for Status {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(match self {
+            Self::Done => "done",
+            Self::DoneWithCycles => "done_with_cycles",
+            Self::Pending => "pending",
+        })
+    }
+}
+
+impl FromStr for Status {
+    type Err = ParseStatusError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "done" => Ok(Self::Done),
+            "done_with_cycles" => Ok(Self::DoneWithCycles),
+            "pending" => Ok(Self::Pending),
+            _ => Err(ParseStatusError),
+        }
+    }
+}
+
+/// Error returned when parsing a string that is not a [`Status`] as formatted by its `Display`
+/// implementation.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct ParseStatusError;
+
+impl fmt::Display for ParseStatusError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("invalid solver status")
+    }
+}
+
+impl Error for ParseStatusError {}
+
+// Make a hook that calls `previous`, if any, and then `hook`
+fn chain_fragment_hooks<F>(
+    previous: Option<Arc<FragmentHook>>,
+    hook: F,
+) -> Arc<FragmentHook>
+where
+    F: Fn(FragmentId) + Send + Sync + 'static,
+{
+    match previous {
+        Some(previous) => Arc::new(move |id| {
+            previous(id);
+            hook(id);
+        }),
+        None => Arc::new(hook),
+    }
+}
+
+// Like `chain_fragment_hooks`, for hooks added with `Solver::with_post_hook`
+fn chain_post_hooks<F>(
+    previous: Option<Arc<PostHook>>,
+    hook: F,
+) -> Arc<PostHook>
+where
+    F: Fn(FragmentId, bool) + Send + Sync + 'static,
+{
+    match previous {
+        Some(previous) => Arc::new(move |id, success| {
+            previous(id, success);
+            hook(id, success);
+        }),
+        None => Arc::new(hook),
+    }
+}
+
+/// Breadth-first search for the shortest path of recorded dependency edges leading from `from` to
+/// `to`, only going through fragments accepted by `filter`. If `from == to`, a cycle is searched
+/// for instead.
+fn find_dependency_path<F>(
+    state: &State,
+    from: FragmentId,
+    to: FragmentId,
+    filter: F,
+) -> Option<Vec<FragmentId>>
+where
+    F: Fn(FragmentId) -> bool,
+{
+    let mut parents = Map::new();
+    let mut queue = VecDeque::new();
+    queue.push_back(from);
+
+    while let Some(id) = queue.pop_front() {
+        let dependencies = match state.dependency_graph.get(&id) {
+            Some(dependencies) => dependencies,
+            None => continue,
+        };
+
+        for dependency in dependencies.iter().copied() {
+            if dependency == to {
+                let mut path = Vec::from([to]);
+                let mut current = id;
+                loop {
+                    path.push(current);
+                    if current == from {
+                        break;
+                    }
+                    current = parents[&current];
+                }
+                path.reverse();
+
+                return Some(path);
+            }
+
+            if dependency == from
+                || parents.contains_key(&dependency)
+                || !filter(dependency)
+            {
+                continue;
+            }
+            parents.insert(dependency, id);
+            queue.push_back(dependency);
+        }
+    }
+
+    None
+}
+
+/// Generation of every solved fragment. Fragments are solved after all of their dependencies, so
+/// a single pass over `solved_order` is enough.
+fn generations(state: &State) -> Map<FragmentId, usize> {
+    let mut generations = Map::new();
+    for &id in &state.solved_order {
+        let generation = state
+            .dependency_graph
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .filter_map(|dependency| generations.get(dependency))
+            .map(|generation| generation + 1)
+            .max()
+            .unwrap_or(0);
+        generations.insert(id, generation);
+    }
+
+    generations
+}
+
+/// Levels of the recorded dependency graph found by a breadth-first traversal from the fragments
+/// without dependencies. Fragments only enter a level once all of their dependencies are in
+/// earlier levels, so fragments in or behind cycles are never reached.
+fn topological_levels(state: &State) -> Vec<Vec<FragmentId>> {
+    // Number of distinct dependencies not in a level yet, and the inverse of `dependency_graph`
+    let mut remaining = Map::<FragmentId, usize>::new();
+    let mut dependents = Map::<FragmentId, Vec<FragmentId>>::new();
+    for (&id, dependencies) in &state.dependency_graph {
+        let dependencies = dependencies.iter().copied().collect::<Set<_>>();
+        remaining.insert(id, dependencies.len());
+        for dependency in dependencies {
+            remaining.entry(dependency).or_insert(0);
+            dependents.entry(dependency).or_default().push(id);
+        }
+    }
+
+    let mut level = remaining
+        .iter()
+        .filter(|(_, &count)| count == 0)
+        .map(|(&id, _)| id)
+        .collect::<Vec<_>>();
+    let mut levels = Vec::new();
+    while !level.is_empty() {
+        level.sort();
+        let mut next = Vec::new();
+        for id in &level {
+            for &dependent in dependents.get(id).into_iter().flatten() {
+                let count = remaining.get_mut(&dependent).unwrap();
+                *count -= 1;
+                if *count == 0 {
+                    next.push(dependent);
+                }
+            }
+        }
+        levels.push(level);
+        level = next;
+    }
+
+    levels
+}
+
+/// Size of the largest antichain in the recorded dependency graph, computed as the number of
+/// fragments minus a maximum matching between fragments and their transitive dependencies. See
+/// [`Solver::max_parallelism`].
+fn max_antichain(state: &State) -> usize {
+    let mut ids = Vec::new();
+    let mut index = Map::new();
+    for (&id, dependencies) in &state.dependency_graph {
+        for id in iter::once(id).chain(dependencies.iter().copied()) {
+            index.entry(id).or_insert_with(|| {
+                ids.push(id);
+
+                ids.len() - 1
+            });
+        }
+    }
+    let falses = || iter::repeat_n(false, ids.len()).collect::<Vec<_>>();
+
+    // Transitive dependencies of each fragment, by index
+    let mut reachable = Vec::with_capacity(ids.len());
+    for start in 0..ids.len() {
+        let mut seen = falses();
+        let mut dependencies = Vec::new();
+        let mut stack = Vec::from([start]);
+        while let Some(i) = stack.pop() {
+            for dependency in
+                state.dependency_graph.get(&ids[i]).into_iter().flatten()
+            {
+                let j = index[dependency];
+                if j != start && !seen[j] {
+                    seen[j] = true;
+                    dependencies.push(j);
+                    stack.push(j);
+                }
+            }
+        }
+        reachable.push(dependencies);
+    }
+
+    // Kuhn's algorithm
+    let mut matched = iter::repeat_n(None, ids.len()).collect::<Vec<_>>();
+    let mut matching = 0;
+    for i in 0..ids.len() {
+        if augment_matching(i, &reachable, &mut matched, &mut falses()) {
+            matching += 1;
+        }
+    }
+
+    ids.len() - matching
+}
+
+// Try to find an augmenting path starting at `i`
+fn augment_matching(
+    i: usize,
+    reachable: &[Vec<usize>],
+    matched: &mut [Option<usize>],
+    visited: &mut [bool],
+) -> bool {
+    for &j in &reachable[i] {
+        if !visited[j] {
+            visited[j] = true;
+            if matched[j].is_none_or(|k| {
+                augment_matching(k, reachable, matched, visited)
+            }) {
+                matched[j] = Some(i);
+
+                return true;
+            }
+        }
+    }
+
+    false
+}
+
+/// Sizes of all connected components of the recorded dependency graph, in no particular order.
+/// See [`Solver::component_sizes`].
+fn component_sizes(state: &State) -> Vec<usize> {
+    fn find(
+        parents: &mut Map<FragmentId, FragmentId>,
+        id: FragmentId,
+    ) -> FragmentId {
+        let parent = parents[&id];
+        if parent == id {
+            id
+        } else {
+            let root = find(parents, parent);
+            parents.insert(id, root);
+
+            root
+        }
+    }
+
+    let mut parents = Map::new();
+    let known = state
+        .to_solve
+        .iter()
+        .chain(state.punted.keys())
+        .chain(&state.in_flight)
+        .chain(&state.failed)
+        .chain(&state.blocked)
+        .chain(&state.solved_order)
+        .chain(state.dependency_graph.keys())
+        .chain(state.dependency_graph.values().flatten());
+    for &id in known {
+        parents.insert(id, id);
+    }
+    for (&id, dependencies) in &state.dependency_graph {
+        for &dependency in dependencies {
+            let root = find(&mut parents, id);
+            let dependency_root = find(&mut parents, dependency);
+            parents.insert(root, dependency_root);
+        }
+    }
+
+    let mut sizes = Map::<_, usize>::new();
+    let ids = parents.keys().copied().collect::<Vec<_>>();
+    for id in ids {
+        *sizes.entry(find(&mut parents, id)).or_default() += 1;
+    }
+
+    sizes.into_values().collect()
+}
+
+/// Greedy approximation of a minimum feedback vertex set of the punted fragments. See
+/// [`Solver::cycle_break_suggestions`].
+fn feedback_vertex_set(state: &State) -> Vec<FragmentId> {
+    let mut dependencies = Map::<FragmentId, Set<FragmentId>>::new();
+    let mut dependents = Map::<FragmentId, Set<FragmentId>>::new();
+    for &id in state.punted.keys() {
+        dependencies.entry(id).or_default();
+        dependents.entry(id).or_default();
+        for &dependency in state.dependency_graph.get(&id).into_iter().flatten()
+        {
+            if state.punted.contains_key(&dependency) {
+                dependencies.entry(id).or_default().insert(dependency);
+                dependents.entry(dependency).or_default().insert(id);
+            }
+        }
+    }
+    let remove =
+        |id,
+         dependencies: &mut Map<FragmentId, Set<FragmentId>>,
+         dependents: &mut Map<FragmentId, Set<FragmentId>>| {
+            for dependency in dependencies.remove(&id).into_iter().flatten() {
+                if let Some(x) = dependents.get_mut(&dependency) {
+                    x.remove(&id);
+                }
+            }
+            for dependent in dependents.remove(&id).into_iter().flatten() {
+                if let Some(x) = dependencies.get_mut(&dependent) {
+                    x.remove(&id);
+                }
+            }
+        };
+
+    let mut suggestions = Vec::new();
+    loop {
+        // Fragments without dependents or dependencies left can't be part of a cycle
+        while let Some(id) = dependencies
+            .keys()
+            .copied()
+            .find(|id| dependencies[id].is_empty() || dependents[id].is_empty())
+        {
+            remove(id, &mut dependencies, &mut dependents);
+        }
+
+        // Fragments that depend on themselves must always be broken
+        let next = dependencies.iter().max_by_key(|&(&id, x)| {
+            (
+                x.contains(&id),
+                x.len() * dependents[&id].len(),
+                Reverse(id),
+            )
+        });
+        let Some((&id, _)) = next else {
+            break;
+        };
+        suggestions.push(id);
+        remove(id, &mut dependencies, &mut dependents);
+    }
+    suggestions.sort_unstable();
+
+    suggestions
+}
+
+/// Sizes of the strongly connected components of punted fragments that contain a cycle, found
+/// with an iterative version of Tarjan's algorithm. See [`Solver::cycle_size_histogram`].
+fn cycle_sizes(state: &State) -> Vec<usize> {
+    let punted_dependencies = |id: FragmentId| {
+        state
+            .dependency_graph
+            .get(&id)
+            .into_iter()
+            .flatten()
+            .copied()
+            .filter(|dependency| state.punted.contains_key(dependency))
+            .collect::<Vec<_>>()
+    };
+
+    // Visit index and lowest index reachable of each visited fragment
+    let mut indexes = Map::<FragmentId, (usize, usize)>::new();
+    let mut stack = Vec::new();
+    let mut on_stack = Set::new();
+    let mut sizes = Vec::new();
+    for root in state.punted.keys().copied() {
+        if indexes.contains_key(&root) {
+            continue;
+        }
+
+        indexes.insert(root, (indexes.len(), indexes.len()));
+        stack.push(root);
+        on_stack.insert(root);
+        // Fragments being visited, their dependencies, and the next dependency to visit
+        let mut visiting = Vec::from([(root, punted_dependencies(root), 0)]);
+        while let Some((id, dependencies, next)) = visiting.last_mut() {
+            let id = *id;
+            if let Some(&dependency) = dependencies.get(*next) {
+                *next += 1;
+                match indexes.get(&dependency) {
+                    None => {
+                        indexes
+                            .insert(dependency, (indexes.len(), indexes.len()));
+                        stack.push(dependency);
+                        on_stack.insert(dependency);
+                        visiting.push((
+                            dependency,
+                            punted_dependencies(dependency),
+                            0,
+                        ));
+                    }
+                    Some(&(index, _)) if on_stack.contains(&dependency) => {
+                        let low = &mut indexes.get_mut(&id).unwrap().1;
+                        *low = (*low).min(index);
+                    }
+                    Some(_) => (),
+                }
+            } else {
+                let (index, low) = indexes[&id];
+                visiting.pop();
+                if let Some(&(parent, _, _)) = visiting.last() {
+                    let parent_low = &mut indexes.get_mut(&parent).unwrap().1;
+                    *parent_low = (*parent_low).min(low);
+                }
+                if index == low {
+                    let mut size = 0;
+                    loop {
+                        let member = stack.pop().unwrap();
+                        on_stack.remove(&member);
+                        size += 1;
+                        if member == id {
+                            break;
+                        }
+                    }
+                    if size > 1 || punted_dependencies(id).contains(&id) {
+                        sizes.push(size);
+                    }
+                }
+            }
+        }
+    }
+
+    sizes
+}
+
+/// Longest path in the recorded dependency graph. See [`Solver::longest_path`].
+fn longest_path(state: &State) -> Vec<FragmentId> {
+    // Length of and next fragment in the longest path starting at each visited fragment
+    let mut longest = Map::<FragmentId, (usize, Option<FragmentId>)>::new();
+    let mut on_stack = Set::new();
+    for root in state.dependency_graph.keys().copied() {
+        // Iterative post-order DFS. Fragments are finished only after all of their dependencies
+        let mut stack = Vec::from([(root, false)]);
+        while let Some((id, expanded)) = stack.pop() {
+            let dependencies =
+                state.dependency_graph.get(&id).into_iter().flatten();
+            if expanded {
+                on_stack.remove(&id);
+                let next = dependencies
+                    .filter_map(|dependency| {
+                        longest
+                            .get(dependency)
+                            .map(|&(len, _)| (len, *dependency))
+                    })
+                    .max_by_key(|&(len, _)| len);
+                longest.insert(
+                    id,
+                    match next {
+                        Some((len, dependency)) => (len + 1, Some(dependency)),
+                        None => (1, None),
+                    },
+                );
+            } else if !longest.contains_key(&id) && on_stack.insert(id) {
+                stack.push((id, true));
+                stack.extend(
+                    dependencies
+                        .copied()
+                        .filter(|dependency| !on_stack.contains(dependency))
+                        .map(|dependency| (dependency, false)),
+                );
+            }
+        }
+    }
+
+    let mut path = Vec::new();
+    let mut next = longest
+        .iter()
+        .max_by_key(|(_, &(len, _))| len)
+        .map(|(&id, _)| id);
+    while let Some(id) = next {
+        path.push(id);
+        next = longest[&id].1;
+    }
+
+    path
+}
+
+/// Explanation of why a fragment is part of a dependency cycle. See [`Solver::explain_cycle`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct CycleExplanation {
+    /// Shortest dependency cycle through the fragment, starting and ending with it.
+    pub path: Vec<FragmentId>,
+
+    /// Human-readable description of the cycle, built with [`Problem::describe_fragment`].
+    pub description: String,
+}
+
+/// Number of fragments in each state. See [`Solver::detailed_summary`].
+///
+/// Displays as `Solved {solved}/{total} ({pending} pending, {punted} punted, {in_flight}
+/// in-flight)`, followed by `, {failed} failed, {blocked} blocked` before the closing
+/// parenthesis if any fragment failed or is blocked.
+#[derive(Clone, Copy, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DetailedSummary {
+    /// Fragments that were evaluated or assumed to be evaluated.
+    pub solved: usize,
+
+    /// Fragments queued to be considered for evaluation.
+    pub pending: usize,
+
+    /// Fragments waiting on dependencies. See [`Solver::punted_iter`].
+    pub punted: usize,
+
+    /// Fragments whose dependencies are being fetched or that are being evaluated.
+    pub in_flight: usize,
+
+    /// Fragments for which [`Problem::evaluate`] returned an error.
+    pub failed: usize,
+
+    /// Fragments that can never be evaluated because one of their transitive dependencies
+    /// failed.
+    pub blocked: usize,
+}
+
+impl DetailedSummary {
+    /// Get the total number of fragments known to the solver.
+    pub fn total(&self) -> usize {
+        self.solved
+            + self.pending
+            + self.punted
+            + self.in_flight
+            + self.failed
+            + self.blocked
+    }
+}
+
+impl fmt::Display for DetailedSummary {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "Solved {}/{} ({} pending, {} punted, {} in-flight",
+            self.solved,
+            self.total(),
+            self.pending,
+            self.punted,
+            self.in_flight,
+        )?;
+        if self.failed > 0 || self.blocked > 0 {
+            write!(f, ", {} failed, {} blocked", self.failed, self.blocked)?;
+        }
+
+        f.write_str(")")
+    }
+}
+
+/// Part of the dependency graph needed to evaluate some fragments. See
+/// [`Solver::dependency_subgraph`].
+#[derive(Clone, Debug, Default, PartialEq, Eq, Hash)]
+pub struct DependencySubgraph {
+    /// All fragments in the subgraph, sorted by ID.
+    pub nodes: Vec<FragmentId>,
+
+    /// All `(dependent, dependency)` edges between fragments in the subgraph, sorted.
+    pub edges: Vec<(FragmentId, FragmentId)>,
+}
+
+impl DependencySubgraph {
+    /// Get the number of fragments in the subgraph.
+    pub fn fragment_count(&self) -> usize {
+        self.nodes.len()
+    }
+
+    /// Render the subgraph in the Graphviz DOT language, with edges pointing from dependents to
+    /// their dependencies.
+    pub fn to_dot(&self) -> String {
+        let mut out = String::from("digraph {\n");
+        for id in &self.nodes {
+            out += &format!("    {};\n", id.0);
+        }
+        for (dependent, dependency) in &self.edges {
+            out += &format!("    {} -> {};\n", dependent.0, dependency.0);
+        }
+        out += "}\n";
+
+        out
+    }
+}
+
+/// Everything the solver knows about a fragment. See [`Solver::explain`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct FragmentExplanation {
+    /// Current lifecycle state of the fragment.
+    pub state: FragmentLifecycle,
+
+    /// Direct dependencies that were not evaluated yet. See [`Solver::what_blocks`].
+    pub unsatisfied_dependencies: Vec<FragmentId>,
+
+    /// Direct dependencies that were already evaluated.
+    pub satisfied_dependencies: Vec<FragmentId>,
+
+    /// Punted fragments waiting for this fragment to be evaluated. See
+    /// [`Solver::fragments_pending_on`].
+    pub blocked_dependents: Vec<FragmentId>,
+
+    /// Shortest dependency cycle through the fragment, starting and ending with it, if it is
+    /// punted and part of a cycle. See [`Solver::explain_cycle`].
+    pub cycle_path: Option<Vec<FragmentId>>,
+
+    /// Human-readable description of the fragment from [`Problem::describe_fragment`].
+    pub description: String,
+}
+
+/// Lifecycle state of a single fragment. See [`Solver::fragment_state`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum FragmentLifecycle {
+    /// The fragment was never enqueued nor found as a dependency of an enqueued fragment.
+    Unknown,
+
+    /// The fragment is waiting to be considered for evaluation.
+    Queued,
+
+    /// The fragment's dependencies are being fetched or the fragment is being evaluated.
+    InFlight,
+
+    /// The fragment was successfully evaluated.
+    Evaluated,
+
+    /// The fragment was punted and is waiting on `pending_count` dependencies.
+    Punted {
+        /// Number of dependencies that haven't been evaluated yet.
+        pending_count: usize,
+    },
+
+    /// The fragment was never evaluated but is assumed to be. See [`Solver::assume_evaluated`].
+    Skipped,
+
+    /// Evaluating the fragment returned an error.
+    Failed,
+
+    /// The fragment can never be evaluated because one of its transitive dependencies failed.
+    Blocked,
+}
+
+/// Outcome of a [`Solver::evaluate_immediately`] call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ImmediateOutcome {
+    /// The fragment was evaluated.
+    Evaluated,
+
+    /// The fragment was punted and was evaluated anyway, before `pending_count` of its
+    /// dependencies.
+    EvaluatedEarly {
+        /// Number of dependencies that weren't evaluated yet.
+        pending_count: usize,
+    },
+
+    /// The fragment was already evaluated, assumed to be, or being handled by a step, so it was
+    /// not evaluated again.
+    AlreadyHandled,
+
+    /// The fragment depends on a failed fragment, directly or transitively, so it was not
+    /// evaluated. It can still be [assumed to be evaluated](Solver::assume_evaluated).
+    Blocked,
+
+    /// The solver doesn't know about the fragment, so it was not evaluated. Its dependencies
+    /// have never been fetched, so it must be [enqueued](Solver::enqueue_fragment) first.
+    Unknown,
+}
+
+/// Discarding the result of [`Solver::run`] or [`Solver::step`], or a [`SolverCheckpoint`], must
+/// produce a warning:
+///
+/// ```
+/// #![deny(unused_must_use)]
+/// # use gpp_solver::{Problem, Solver};
+/// # use std::num::NonZeroUsize;
+/// async fn run_and_check<P: Problem + Sync>(solver: &Solver<P>)
+/// where
+///     P::Error: Send,
+/// {
+///     let _ = solver.run(NonZeroUsize::new(1).unwrap()).await;
+///     let _ = solver.step().await;
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// # use gpp_solver::{Problem, Solver};
+/// # use std::num::NonZeroUsize;
+/// async fn run_and_ignore<P: Problem + Sync>(solver: &Solver<P>)
+/// where
+///     P::Error: Send,
+/// {
+///     solver.run(NonZeroUsize::new(1).unwrap()).await;
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// # use gpp_solver::{Problem, Solver};
+/// async fn step_and_ignore<P: Problem>(solver: &Solver<P>) {
+///     solver.step().await;
+/// }
+/// ```
+///
+/// ```compile_fail
+/// #![deny(unused_must_use)]
+/// # use gpp_solver::SolverCheckpoint;
+/// fn checkpoint() -> SolverCheckpoint {
+///     SolverCheckpoint {
+///         solved: Vec::new(),
+///         pending: Vec::new(),
+///     }
+/// }
+///
+/// fn checkpoint_and_ignore() {
+///     checkpoint();
+/// }
+/// ```
+#[cfg(doctest)]
+pub struct MustUseDoctests;
@@ -0,0 +1,287 @@
+use crate::{
+    reexported::{mem, Arc, Box, Error, NonZeroUsize, Vec},
+    FragmentId, Problem, Progress, SolveReport, Solver, State,
+};
+use core::{fmt, ops::ControlFlow};
+use futures::{
+    future::{self, Either},
+    pin_mut,
+    stream::{FuturesUnordered, StreamExt},
+};
+use std::{panic, time::Duration};
+use tokio::time::{self, Instant};
+
+/// Outcome of a [`Solver::step_with_timeout`] call.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum StepOutcome {
+    /// A fragment was evaluated, punted, or blocked.
+    Progressed,
+
+    /// There are no fragments that can be evaluated.
+    Empty,
+
+    /// Evaluation took longer than the timeout and was cancelled. The fragment was queued again
+    /// so it is retried by the next step.
+    Timeout,
+}
+
+/// Error returned by [`Solver::run_with_concurrency_schedule`].
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub enum ScheduleError<E> {
+    /// The schedule has no entries.
+    Empty,
+
+    /// The times of the schedule are not strictly increasing.
+    NotIncreasing,
+
+    /// Fetching dependencies or evaluating a fragment returned an error.
+    Evaluation(E),
+}
+
+impl<E> fmt::Display for ScheduleError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::Empty => f.write_str("concurrency schedule is empty"),
+            Self::NotIncreasing => f.write_str(
+                "concurrency schedule times are not strictly increasing",
+            ),
+            Self::Evaluation(err) => write!(f, "evaluation failed: {}", err),
+        }
+    }
+}
+
+impl<E> Error for ScheduleError<E>
+where
+    E: Error + 'static,
+{
+    fn source(&self) -> Option<&(dyn Error + 'static)> {
+        match self {
+            Self::Empty | Self::NotIncreasing => None,
+            Self::Evaluation(err) => Some(err),
+        }
+    }
+}
+
+impl<P> Solver<P>
+where
+    P: Problem + Send + Sync + 'static,
+    P::Error: Send + 'static,
+{
+    /// Like [`Solver::run`], including calls to [`Problem::on_start`] and [`Problem::on_finish`],
+    /// but each step is spawned as a separate `tokio` task so that up to
+    /// `concurrency` calls to [`Problem::direct_dependencies`] and [`Problem::evaluate`] run in
+    /// parallel on the `tokio` thread pool.
+    ///
+    /// Must be called from within a `tokio` runtime.
+    ///
+    /// All tasks share the state of the solver behind a single lock. It is only held while a
+    /// step takes a fragment or records a result, never while calling into the [`Problem`], so
+    /// fetches and evaluations in different tasks never wait on each other.
+    ///
+    /// Returns an interator with all fragments that are part of at least one cycle, if any. See
+    /// [`Solver::punted_iter`].
+    ///
+    /// Returns an error if any evaluation returns an error. Steps that are still running when that
+    /// happens are awaited first, so no evaluation is left running in the background.
+    ///
+    /// # Panics
+    ///
+    /// If any call to [`Problem::direct_dependencies`] or [`Problem::evaluate`] panics, the panic
+    /// is propagated to the caller.
+    #[must_use = "must check whether evaluation succeeded"]
+    pub async fn run_parallel_core(
+        self: &Arc<Self>,
+        concurrency: NonZeroUsize,
+    ) -> Result<Vec<FragmentId>, P::Error> {
+        let run = self.begin_run().await;
+        let res = self.run_spawned_steps(concurrency).await;
+        let _ = run.finish().await;
+        res?;
+
+        Ok(self.punted_iter().await)
+    }
+
+    /// Like [`Solver::enqueue_fragment`], but `id` is only enqueued once `after` has passed. Until
+    /// then it is kept in a separate delayed queue, so a run that finishes in the meantime does
+    /// not wait for it. A background `tokio` task enqueues it when it is due, unless the solver
+    /// was dropped by then. See also [`Solver::drain_delayed`].
+    ///
+    /// Must be called from within a `tokio` runtime with time enabled.
+    pub async fn schedule_evaluation_after(
+        self: &Arc<Self>,
+        id: FragmentId,
+        after: Instant,
+    ) {
+        self.state
+            .lock()
+            .await
+            .delayed_queue
+            .entry(after)
+            .or_default()
+            .push(id);
+
+        let solver = Arc::downgrade(self);
+        tokio::spawn(async move {
+            time::sleep_until(after).await;
+            if let Some(solver) = solver.upgrade() {
+                let mut state = solver.state.lock().await;
+                // Only leave fragments that are due in the delayed queue while enqueuing it
+                let mut later = state.delayed_queue.split_off(&after);
+                if let Some(due) = later.remove(&after) {
+                    state.delayed_queue.insert(after, due);
+                }
+                solver.enqueue_delayed(&mut state);
+                state.delayed_queue = later;
+            }
+        });
+    }
+
+    async fn run_spawned_steps(
+        self: &Arc<Self>,
+        concurrency: NonZeroUsize,
+    ) -> Result<(), P::Error> {
+        let mut steps = FuturesUnordered::new();
+        let spawn_step = || {
+            let solver = self.clone();
+            let step = tokio::spawn(async move { solver.step_impl().await });
+
+            async move {
+                step.await.unwrap_or_else(|err| {
+                    panic::resume_unwind(err.into_panic())
+                })
+            }
+        };
+        match self
+            .drive_steps(
+                &mut steps,
+                concurrency,
+                spawn_step,
+                |_| ControlFlow::Continue(()),
+                |_, err| ControlFlow::Break(err),
+            )
+            .await
+        {
+            ControlFlow::Continue(()) => Ok(()),
+            ControlFlow::Break(err) => {
+                // Spawned tasks are not cancelled by dropping their handles. Join them so the run
+                // only finishes once they are done with the solver
+                while steps.next().await.is_some() {}
+
+                Err(err)
+            }
+        }
+    }
+}
+
+impl<P> Solver<P>
+where
+    P: Problem,
+{
+    /// Enqueue all fragments added through [`Solver::schedule_evaluation_after`] right away,
+    /// whether they are due or not.
+    pub async fn drain_delayed(&self) {
+        self.enqueue_delayed(&mut *self.state.lock().await);
+    }
+
+    // Enqueue and remove all fragments in `state.delayed_queue`
+    fn enqueue_delayed(&self, state: &mut State) {
+        for id in mem::take(&mut state.delayed_queue).into_values().flatten() {
+            if !state.solved.contains(&id) && !state.in_flight.contains(&id) {
+                self.push_to_solve(id, state);
+            }
+        }
+    }
+
+    /// Like [`Solver::run`], but concurrency changes over time according to `schedule`. Each entry
+    /// is the time since the start of the run at which its concurrency takes effect. The
+    /// concurrency of the first entry is used from the start, regardless of its time.
+    ///
+    /// When concurrency drops, running steps are left to finish and no new steps are started
+    /// until fewer steps than the new concurrency are running.
+    ///
+    /// Must be called from within a `tokio` runtime with time enabled.
+    ///
+    /// Returns [`ScheduleError::Empty`] or [`ScheduleError::NotIncreasing`] without running
+    /// anything if `schedule` is empty or if its times are not strictly increasing, and
+    /// [`ScheduleError::Evaluation`] if fetching dependencies or an evaluation fails.
+    pub async fn run_with_concurrency_schedule(
+        &self,
+        schedule: Vec<(Duration, NonZeroUsize)>,
+    ) -> Result<SolveReport, ScheduleError<P::Error>> {
+        if schedule.is_empty() {
+            return Err(ScheduleError::Empty);
+        }
+        if schedule.windows(2).any(|window| window[0].0 >= window[1].0) {
+            return Err(ScheduleError::NotIncreasing);
+        }
+
+        let run = self.begin_run().await;
+        let res = self.run_scheduled_steps(run.id(), &schedule).await;
+        let report = run.finish().await;
+        res.map_err(ScheduleError::Evaluation)?;
+
+        Ok(report)
+    }
+
+    /// Like [`Solver::step`], but cancels [`Problem::evaluate`] if it takes longer than `timeout`.
+    /// The fragment is then queued again as if it was never taken, so a later step retries it.
+    /// Fetching dependencies is not subject to the timeout.
+    ///
+    /// Must be called from within a `tokio` runtime with time enabled.
+    ///
+    /// Returns an error if [`Problem::evaluate`] returned an error before the timeout.
+    #[must_use = "must check whether evaluation succeeded"]
+    pub async fn step_with_timeout(
+        &self,
+        timeout: Duration,
+    ) -> Result<StepOutcome, P::Error> {
+        let deadline = Box::pin(time::sleep(timeout));
+        match self.step_until(Some(deadline), None).await {
+            Ok(Progress::Idle) => Ok(StepOutcome::Empty),
+            Ok(Progress::TimedOut) => Ok(StepOutcome::Timeout),
+            Ok(_) => Ok(StepOutcome::Progressed),
+            Err((_, err)) => Err(err),
+        }
+    }
+
+    async fn run_scheduled_steps(
+        &self,
+        run: usize,
+        schedule: &[(Duration, NonZeroUsize)],
+    ) -> Result<(), P::Error> {
+        let start = Instant::now();
+        let mut concurrency = schedule[0].1;
+        let mut changes = schedule[1..].iter().copied();
+        let mut next_change = changes.next();
+        let mut steps = FuturesUnordered::new();
+        loop {
+            let run_steps = self.drive_steps(
+                &mut steps,
+                concurrency,
+                || self.run_step(run),
+                |_| ControlFlow::Continue(()),
+                |_, err| ControlFlow::Break(err),
+            );
+            let change = async move {
+                match next_change {
+                    Some((at, _)) => time::sleep_until(start + at).await,
+                    None => future::pending().await,
+                }
+            };
+            pin_mut!(run_steps, change);
+            // Steps keep running while concurrency changes, as `drive_steps` is safe to cancel
+            match future::select(run_steps, change).await {
+                Either::Left((ControlFlow::Continue(()), _)) => return Ok(()),
+                Either::Left((ControlFlow::Break(err), _)) => return Err(err),
+                Either::Right(_) => {
+                    // Always `Some` as we would wait forever otherwise
+                    concurrency = next_change.unwrap().1;
+                    next_change = changes.next();
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,89 @@
+//! Integration with the `petgraph` crate. Only available with the `petgraph` feature.
+//!
+//! Node indexes can be converted to and from [`FragmentId`]s, so they can also be used as the ID
+//! type of a [`TypedSolver`](crate::TypedSolver).
+
+pub use petgraph;
+
+use crate::{
+    reexported::{convert::Infallible, Box, Mutex, Vec},
+    FragmentId, Problem,
+};
+use async_trait::async_trait;
+use petgraph::{graph::NodeIndex, visit::EdgeRef, Directed, Graph};
+
+impl From<NodeIndex> for FragmentId {
+    fn from(index: NodeIndex) -> Self {
+        Self(index.index())
+    }
+}
+
+impl From<FragmentId> for NodeIndex {
+    fn from(id: FragmentId) -> Self {
+        Self::new(id.0)
+    }
+}
+
+/// [`Problem`] backed by a directed `petgraph` graph, where each node is a fragment and each
+/// outgoing edge points to a direct dependency. Evaluation always succeeds and only records the
+/// order in which nodes were evaluated.
+pub struct PetgraphProblem<N, E> {
+    graph: Graph<N, E, Directed>,
+    evaluation_order: Mutex<Vec<NodeIndex>>,
+}
+
+impl<N, E> PetgraphProblem<N, E> {
+    /// Create a new [`PetgraphProblem`] from a dependency graph.
+    pub fn new(graph: Graph<N, E, Directed>) -> Self {
+        Self {
+            graph,
+            evaluation_order: Mutex::new(Vec::new()),
+        }
+    }
+
+    /// Get the wrapped dependency graph.
+    pub fn graph(&self) -> &Graph<N, E, Directed> {
+        &self.graph
+    }
+
+    /// Get the nodes evaluated so far, in evaluation order.
+    pub async fn evaluation_order(&self) -> Vec<NodeIndex> {
+        self.evaluation_order.lock().await.clone()
+    }
+
+    /// Consume `self` and return the dependency graph and the nodes evaluated, in evaluation
+    /// order.
+    pub fn into_parts(self) -> (Graph<N, E, Directed>, Vec<NodeIndex>) {
+        (self.graph, self.evaluation_order.into_inner())
+    }
+}
+
+#[async_trait]
+impl<N, E> Problem for PetgraphProblem<N, E>
+where
+    N: Send + Sync,
+    E: Send + Sync,
+{
+    type Error = Infallible;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependecies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        dependecies.extend(
+            self.graph
+                .edges(id.into())
+                .map(|x| FragmentId::from(x.target())),
+        );
+
+        Ok(())
+    }
+
+    async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        self.evaluation_order.lock().await.push(id.into());
+
+        Ok(())
+    }
+}
@@ -0,0 +1,113 @@
+use crate::{
+    reexported::{Box, Mutex, NonZeroUsize, String, Vec},
+    FragmentId, Problem, SolveReport,
+};
+use async_trait::async_trait;
+use lru::LruCache;
+
+/// [`Problem`] wrapper that caches the results of [`Problem::direct_dependencies`] of the wrapped
+/// problem. Useful for incremental re-solves where most dependencies stay the same: a new
+/// [`Solver`](crate::Solver) over the same [`CachingProblem`] will only fetch dependencies of
+/// fragments that were [invalidated](CachingProblem::invalidate) or evicted.
+///
+/// At most `capacity` dependency lists are cached. The least recently used list is evicted first.
+pub struct CachingProblem<P> {
+    inner: P,
+    cache: Mutex<LruCache<FragmentId, Vec<FragmentId>>>,
+}
+
+impl<P> CachingProblem<P> {
+    /// Wrap `inner`, caching up to `capacity` dependency lists.
+    pub fn new(inner: P, capacity: NonZeroUsize) -> Self {
+        Self {
+            inner,
+            cache: Mutex::new(LruCache::new(capacity)),
+        }
+    }
+
+    /// Remove the cached dependencies of a fragment, so they are fetched again from the wrapped
+    /// problem the next time they are needed.
+    pub async fn invalidate(&self, id: FragmentId) {
+        self.cache.lock().await.pop(&id);
+    }
+
+    /// Remove all cached dependencies.
+    pub async fn clear(&self) {
+        self.cache.lock().await.clear();
+    }
+
+    /// Get the wrapped problem.
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Consume `self` and return the wrapped problem.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+#[async_trait]
+impl<P> Problem for CachingProblem<P>
+where
+    P: Problem + Send + Sync,
+{
+    type Error = P::Error;
+    type Output = P::Output;
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        if let Some(cached) = self.cache.lock().await.get(&id) {
+            dependencies.extend_from_slice(cached);
+
+            return Ok(());
+        }
+
+        // Don't hold the lock while fetching so other fragments can be looked up meanwhile. Errors
+        // are not cached so the next fetch tries again
+        let start = dependencies.len();
+        self.inner.direct_dependencies(id, dependencies).await?;
+        self.cache
+            .lock()
+            .await
+            .put(id, dependencies[start..].to_vec());
+
+        Ok(())
+    }
+
+    async fn evaluate(
+        &self,
+        id: FragmentId,
+    ) -> Result<Self::Output, Self::Error> {
+        self.inner.evaluate(id).await
+    }
+
+    fn dependencies_hint(&self, id: FragmentId) -> Option<usize> {
+        self.inner.dependencies_hint(id)
+    }
+
+    async fn preferred_dependencies_order(
+        &self,
+        id: FragmentId,
+        dependencies: &mut [FragmentId],
+    ) {
+        self.inner
+            .preferred_dependencies_order(id, dependencies)
+            .await
+    }
+
+    async fn on_start(&self) {
+        self.inner.on_start().await
+    }
+
+    async fn on_finish(&self, report: &SolveReport) {
+        self.inner.on_finish(report).await
+    }
+
+    fn describe_fragment(&self, id: FragmentId) -> String {
+        self.inner.describe_fragment(id)
+    }
+}
@@ -0,0 +1,169 @@
+use crate::{
+    reexported::{Box, Error, Mutex, String, Vec},
+    FragmentId, Problem, SolveReport,
+};
+use async_trait::async_trait;
+use core::{fmt, ops::Range};
+use futures_timer::Delay;
+use rand::{Rng, SeedableRng};
+use std::time::Duration;
+
+/// [`Problem`] wrapper that randomly fails or delays evaluations of the wrapped problem. Useful to
+/// stress test error handling and retry logic.
+///
+/// Before each evaluation, [`ChaosMonkeyProblem`] sleeps for a random duration in `delay_range`
+/// and then fails with [`ChaosError::RandomFailure`] with probability `failure_rate`. Otherwise
+/// evaluation is delegated to the wrapped problem. Dependencies are never affected.
+pub struct ChaosMonkeyProblem<P, R> {
+    inner: P,
+    rng: Mutex<R>,
+    failure_rate: f64,
+    delay_range: Range<Duration>,
+}
+
+impl<P, R> ChaosMonkeyProblem<P, R>
+where
+    R: Rng,
+{
+    /// Wrap `inner`, using `rng` as the source of randomness.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `failure_rate` is not in `0.0..=1.0`.
+    pub fn new(
+        inner: P,
+        rng: R,
+        failure_rate: f64,
+        delay_range: Range<Duration>,
+    ) -> Self {
+        assert!(
+            (0.0..=1.0).contains(&failure_rate),
+            "failure rate must be between 0 and 1",
+        );
+
+        Self {
+            inner,
+            rng: Mutex::new(rng),
+            failure_rate,
+            delay_range,
+        }
+    }
+
+    /// Wrap `inner`, using an RNG seeded with `seed` so failures and delays are reproducible.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `failure_rate` is not in `0.0..=1.0`.
+    pub fn with_seed(
+        inner: P,
+        seed: u64,
+        failure_rate: f64,
+        delay_range: Range<Duration>,
+    ) -> Self
+    where
+        R: SeedableRng,
+    {
+        Self::new(inner, R::seed_from_u64(seed), failure_rate, delay_range)
+    }
+
+    /// Consume `self` and return the wrapped problem.
+    pub fn into_inner(self) -> P {
+        self.inner
+    }
+}
+
+#[async_trait]
+impl<P, R> Problem for ChaosMonkeyProblem<P, R>
+where
+    P: Problem + Send + Sync,
+    R: Rng + Send,
+{
+    type Error = ChaosError<P::Error>;
+    type Output = P::Output;
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        self.inner
+            .direct_dependencies(id, dependencies)
+            .await
+            .map_err(ChaosError::Inner)
+    }
+
+    async fn evaluate(
+        &self,
+        id: FragmentId,
+    ) -> Result<Self::Output, Self::Error> {
+        let (delay, fail) = {
+            let mut rng = self.rng.lock().await;
+            let delay = if self.delay_range.is_empty() {
+                Duration::ZERO
+            } else {
+                rng.gen_range(self.delay_range.clone())
+            };
+
+            (delay, rng.gen_bool(self.failure_rate))
+        };
+
+        if !delay.is_zero() {
+            Delay::new(delay).await;
+        }
+        if fail {
+            Err(ChaosError::RandomFailure)
+        } else {
+            self.inner.evaluate(id).await.map_err(ChaosError::Inner)
+        }
+    }
+
+    fn dependencies_hint(&self, id: FragmentId) -> Option<usize> {
+        self.inner.dependencies_hint(id)
+    }
+
+    async fn preferred_dependencies_order(
+        &self,
+        id: FragmentId,
+        dependencies: &mut [FragmentId],
+    ) {
+        self.inner
+            .preferred_dependencies_order(id, dependencies)
+            .await
+    }
+
+    async fn on_start(&self) {
+        self.inner.on_start().await
+    }
+
+    async fn on_finish(&self, report: &SolveReport) {
+        self.inner.on_finish(report).await
+    }
+
+    fn describe_fragment(&self, id: FragmentId) -> String {
+        self.inner.describe_fragment(id)
+    }
+}
+
+/// Error type of [`ChaosMonkeyProblem`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum ChaosError<E> {
+    /// The evaluation was randomly chosen to fail.
+    RandomFailure,
+
+    /// The wrapped problem failed to fetch the dependencies of the fragment or to evaluate it.
+    Inner(E),
+}
+
+impl<E> fmt::Display for ChaosError<E>
+where
+    E: fmt::Display,
+{
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::RandomFailure => write!(f, "random evaluation failure"),
+            Self::Inner(err) => err.fmt(f),
+        }
+    }
+}
+
+impl<E> Error for ChaosError<E> where E: Error {}
@@ -0,0 +1,105 @@
+use crate::{
+    reexported::{Box, Error, Future, Vec},
+    FragmentId, Problem,
+};
+use async_trait::async_trait;
+use futures::future::BoxFuture;
+
+/// Boxed error type used by [`ClosureProblem`].
+pub type BoxError = Box<dyn Error + Send + Sync>;
+
+/// [`Problem`] implemented by a pair of closures, one for each method of the trait. Useful for
+/// one-off problems and tests where writing a full struct would be too verbose.
+///
+/// See also [`closure_problem!`](crate::closure_problem) for an even more compact syntax.
+pub struct ClosureProblem<D, E> {
+    deps_fn: D,
+    eval_fn: E,
+}
+
+impl<D, E> ClosureProblem<D, E>
+where
+    D: Fn(FragmentId) -> BoxFuture<'static, Vec<FragmentId>>,
+    E: Fn(FragmentId) -> BoxFuture<'static, Result<(), BoxError>>,
+{
+    /// Create a new [`ClosureProblem`]. `deps_fn` returns the direct dependencies of a fragment
+    /// and `eval_fn` evaluates it. See [`box_dependencies`] and [`box_evaluation`] for help
+    /// boxing the returned futures.
+    pub fn new(deps_fn: D, eval_fn: E) -> Self {
+        Self { deps_fn, eval_fn }
+    }
+}
+
+#[async_trait]
+impl<D, E> Problem for ClosureProblem<D, E>
+where
+    D: Fn(FragmentId) -> BoxFuture<'static, Vec<FragmentId>> + Send + Sync,
+    E: Fn(FragmentId) -> BoxFuture<'static, Result<(), BoxError>> + Send + Sync,
+{
+    type Error = BoxError;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependecies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        dependecies.extend((self.deps_fn)(id).await);
+
+        Ok(())
+    }
+
+    async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        (self.eval_fn)(id).await
+    }
+}
+
+/// Box a future returning the dependencies of a fragment for use with [`ClosureProblem`].
+pub fn box_dependencies<F>(future: F) -> BoxFuture<'static, Vec<FragmentId>>
+where
+    F: Future<Output = Vec<FragmentId>> + Send + 'static,
+{
+    Box::pin(future)
+}
+
+/// Box a future evaluating a fragment for use with [`ClosureProblem`].
+pub fn box_evaluation<F>(future: F) -> BoxFuture<'static, Result<(), BoxError>>
+where
+    F: Future<Output = Result<(), BoxError>> + Send + 'static,
+{
+    Box::pin(future)
+}
+
+/// Create a [`ClosureProblem`] from two closures returning `async` blocks:
+///
+/// ```
+/// # use gpp_solver::{closure_problem, FragmentId};
+/// let problem = closure_problem!(
+///     deps: |id| async {
+///         if id.0 > 0 {
+///             vec![FragmentId(id.0 - 1)]
+///         } else {
+///             vec![]
+///         }
+///     },
+///     eval: |_id| async { Ok(()) },
+/// );
+/// ```
+///
+/// The `async` blocks are always `move` so they can capture the fragment ID.
+#[macro_export]
+macro_rules! closure_problem {
+    (
+        deps: |$deps_id:ident| async $(move)? $deps:block,
+        eval: |$eval_id:ident| async $(move)? $eval:block $(,)?
+    ) => {
+        $crate::problems::ClosureProblem::new(
+            move |$deps_id: $crate::FragmentId| {
+                $crate::problems::box_dependencies(async move $deps)
+            },
+            move |$eval_id: $crate::FragmentId| {
+                $crate::problems::box_evaluation(async move $eval)
+            },
+        )
+    };
+}
@@ -0,0 +1,64 @@
+use crate::{
+    reexported::{convert::Infallible, Box, IntoIterator, Map, Vec},
+    FragmentId, Problem,
+};
+use async_trait::async_trait;
+
+/// [`Problem`] backed by a static adjacency list mapping each fragment to its direct
+/// dependencies. Fragments that are not in the list have no dependencies. Evaluation is a no-op
+/// that always succeeds.
+///
+/// This is useful for dependency graphs that are fully known ahead of time, such as those read
+/// from configuration files. With the `serde` feature, a [`HashMapProblem`] can be deserialized
+/// directly from a map of fragment IDs to lists of fragment IDs.
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(
+    feature = "serde",
+    derive(serde::Serialize, serde::Deserialize),
+    serde(transparent)
+)]
+pub struct HashMapProblem {
+    deps: Map<FragmentId, Vec<FragmentId>>,
+}
+
+impl HashMapProblem {
+    /// Create a new [`HashMapProblem`] from `(dependent, dependency)` edges.
+    pub fn from_edges<I>(edges: I) -> Self
+    where
+        I: IntoIterator<Item = (FragmentId, FragmentId)>,
+    {
+        let mut deps = Map::<_, Vec<_>>::new();
+        for (dependent, dependency) in edges {
+            deps.entry(dependent).or_default().push(dependency);
+        }
+
+        Self { deps }
+    }
+
+    /// Create a new [`HashMapProblem`] from a map of fragments to their direct dependencies.
+    pub fn from_adjacency_list(deps: Map<FragmentId, Vec<FragmentId>>) -> Self {
+        Self { deps }
+    }
+}
+
+#[async_trait]
+impl Problem for HashMapProblem {
+    type Error = Infallible;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependecies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        if let Some(deps) = self.deps.get(&id) {
+            dependecies.extend_from_slice(deps);
+        }
+
+        Ok(())
+    }
+
+    async fn evaluate(&self, _: FragmentId) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
@@ -0,0 +1,106 @@
+use crate::{
+    problems::HashMapProblem,
+    reexported::{convert::Infallible, Box, Iterator, Vec},
+    FragmentId, FragmentIdRange, Problem,
+};
+use async_trait::async_trait;
+use quickcheck::{Arbitrary, Gen};
+
+/// Largest number of fragments in a [`MockProblem`] generated by [`Arbitrary`].
+const MAX_ARBITRARY_FRAGMENTS: usize = 20;
+
+/// [`Problem`] with fragments `0..fragment_count` and an explicit list of dependency edges, meant
+/// for property testing.
+///
+/// The [`Arbitrary`] implementation generates random DAGs with up to 20 fragments, where
+/// fragments only depend on fragments with smaller IDs. Use [`MockProblem::with_dependency`] to
+/// add cycles.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct MockProblem {
+    fragment_count: usize,
+    edges: Vec<(FragmentId, FragmentId)>,
+    inner: HashMapProblem,
+}
+
+impl MockProblem {
+    /// Create a new [`MockProblem`] with fragments `0..fragment_count` and `(dependent,
+    /// dependency)` edges.
+    pub fn new(
+        fragment_count: usize,
+        edges: Vec<(FragmentId, FragmentId)>,
+    ) -> Self {
+        Self {
+            fragment_count,
+            inner: HashMapProblem::from_edges(edges.iter().copied()),
+            edges,
+        }
+    }
+
+    /// Consume `self` and return a copy with an extra dependency edge.
+    pub fn with_dependency(
+        mut self,
+        dependent: FragmentId,
+        dependency: FragmentId,
+    ) -> Self {
+        self.edges.push((dependent, dependency));
+
+        Self::new(self.fragment_count, self.edges)
+    }
+
+    /// Get an iterator over all fragments of this problem.
+    pub fn fragments(&self) -> FragmentIdRange {
+        FragmentId::range(FragmentId::ZERO, FragmentId(self.fragment_count))
+    }
+
+    /// Get all `(dependent, dependency)` edges of this problem.
+    pub fn edges(&self) -> &[(FragmentId, FragmentId)] {
+        &self.edges
+    }
+}
+
+impl Arbitrary for MockProblem {
+    fn arbitrary(g: &mut Gen) -> Self {
+        let fragment_count =
+            usize::arbitrary(g) % (MAX_ARBITRARY_FRAGMENTS + 1);
+        let mut edges = Vec::new();
+        for dependent in 0..fragment_count {
+            for dependency in 0..dependent {
+                // Keep graphs fairly sparse
+                if u8::arbitrary(g) % 4 == 0 {
+                    edges.push((FragmentId(dependent), FragmentId(dependency)));
+                }
+            }
+        }
+
+        Self::new(fragment_count, edges)
+    }
+}
+
+impl Arbitrary for FragmentId {
+    fn arbitrary(g: &mut Gen) -> Self {
+        // Keep IDs small so graphs built from them stay tractable
+        Self(usize::arbitrary(g) % 1024)
+    }
+
+    fn shrink(&self) -> Box<dyn Iterator<Item = Self>> {
+        Box::new(self.0.shrink().map(FragmentId))
+    }
+}
+
+#[async_trait]
+impl Problem for MockProblem {
+    type Error = Infallible;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependecies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        self.inner.direct_dependencies(id, dependecies).await
+    }
+
+    async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        self.inner.evaluate(id).await
+    }
+}
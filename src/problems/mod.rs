@@ -0,0 +1,25 @@
+//! Ready-made [`Problem`](crate::Problem) implementations.
+
+#[cfg(feature = "lru")]
+pub use crate::problems::caching::CachingProblem;
+#[cfg(feature = "chaos")]
+pub use crate::problems::chaos::{ChaosError, ChaosMonkeyProblem};
+pub use crate::problems::closure::{
+    box_dependencies, box_evaluation, BoxError, ClosureProblem,
+};
+pub use crate::problems::hash_map::HashMapProblem;
+#[cfg(feature = "quickcheck")]
+pub use crate::problems::mock::MockProblem;
+#[cfg(any(test, feature = "testing-helpers"))]
+pub use crate::problems::tracing::TracingProblem;
+
+#[cfg(feature = "lru")]
+mod caching;
+#[cfg(feature = "chaos")]
+mod chaos;
+mod closure;
+mod hash_map;
+#[cfg(feature = "quickcheck")]
+mod mock;
+#[cfg(any(test, feature = "testing-helpers"))]
+mod tracing;
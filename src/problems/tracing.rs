@@ -0,0 +1,99 @@
+use crate::{
+    reexported::{Box, Mutex, String, Vec},
+    EvaluationTrace, FragmentId, Problem, SolveReport,
+};
+use async_trait::async_trait;
+
+/// [`Problem`] wrapper that records an [`EvaluationTrace`] of all dependency fetches and
+/// successful evaluations of the wrapped problem. Replay the trace with
+/// [`Solver::replay_from_trace`](crate::Solver::replay_from_trace).
+pub struct TracingProblem<P> {
+    inner: P,
+    trace: Mutex<EvaluationTrace>,
+}
+
+impl<P> TracingProblem<P> {
+    /// Wrap `inner` with an empty trace.
+    pub fn new(inner: P) -> Self {
+        Self {
+            inner,
+            trace: Mutex::new(EvaluationTrace::default()),
+        }
+    }
+
+    /// Get a copy of the trace recorded so far.
+    pub async fn trace(&self) -> EvaluationTrace {
+        self.trace.lock().await.clone()
+    }
+
+    /// Get the wrapped problem.
+    pub fn inner(&self) -> &P {
+        &self.inner
+    }
+
+    /// Consume `self` and return the wrapped problem and the recorded trace.
+    pub fn into_parts(self) -> (P, EvaluationTrace) {
+        (self.inner, self.trace.into_inner())
+    }
+}
+
+#[async_trait]
+impl<P> Problem for TracingProblem<P>
+where
+    P: Problem + Send + Sync,
+{
+    type Error = P::Error;
+    type Output = P::Output;
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        let start = dependencies.len();
+        self.inner.direct_dependencies(id, dependencies).await?;
+        self.trace
+            .lock()
+            .await
+            .dep_responses
+            .push((id, dependencies[start..].to_vec()));
+
+        Ok(())
+    }
+
+    async fn evaluate(
+        &self,
+        id: FragmentId,
+    ) -> Result<Self::Output, Self::Error> {
+        let output = self.inner.evaluate(id).await?;
+        self.trace.lock().await.eval_order.push(id);
+
+        Ok(output)
+    }
+
+    fn dependencies_hint(&self, id: FragmentId) -> Option<usize> {
+        self.inner.dependencies_hint(id)
+    }
+
+    async fn preferred_dependencies_order(
+        &self,
+        id: FragmentId,
+        dependencies: &mut [FragmentId],
+    ) {
+        self.inner
+            .preferred_dependencies_order(id, dependencies)
+            .await
+    }
+
+    async fn on_start(&self) {
+        self.inner.on_start().await
+    }
+
+    async fn on_finish(&self, report: &SolveReport) {
+        self.inner.on_finish(report).await
+    }
+
+    fn describe_fragment(&self, id: FragmentId) -> String {
+        self.inner.describe_fragment(id)
+    }
+}
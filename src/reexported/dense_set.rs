@@ -0,0 +1,108 @@
+use crate::reexported::{iter, PhantomData, Vec};
+
+const BITS: usize = usize::BITS as usize;
+
+/// A set of small integer-like values backed by a bitfield. Membership tests, insertions, and
+/// removals are all O(1). The bitfield grows as needed to fit the largest value ever inserted, so
+/// this is only a good fit for dense IDs.
+#[derive(Clone)]
+pub struct DenseBitSet<T> {
+    words: Vec<usize>,
+    len: usize,
+    _phantom: PhantomData<T>,
+}
+
+impl<T> DenseBitSet<T>
+where
+    T: Copy + From<usize> + Into<usize>,
+{
+    /// Create an empty set.
+    pub fn new() -> Self {
+        Self {
+            words: Vec::new(),
+            len: 0,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Add a value to the set. Returns `true` if the value was not in the set.
+    pub fn insert(&mut self, value: T) -> bool {
+        let (word, mask) = Self::position(value);
+        if word >= self.words.len() {
+            self.words.resize(word + 1, 0);
+        }
+
+        let was_present = self.words[word] & mask != 0;
+        if !was_present {
+            self.words[word] |= mask;
+            self.len += 1;
+        }
+
+        !was_present
+    }
+
+    /// Check whether a value is in the set.
+    pub fn contains(&self, value: &T) -> bool {
+        let (word, mask) = Self::position(*value);
+
+        self.words.get(word).is_some_and(|x| x & mask != 0)
+    }
+
+    /// Remove a value from the set. Returns `true` if the value was in the set.
+    pub fn remove(&mut self, value: &T) -> bool {
+        let (word, mask) = Self::position(*value);
+
+        match self.words.get_mut(word) {
+            Some(x) if *x & mask != 0 => {
+                *x &= !mask;
+                self.len -= 1;
+
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Get the number of values in the set.
+    pub fn len(&self) -> usize {
+        self.len
+    }
+
+    /// Check whether the set is empty.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Get an iterator over all values in the set, in ascending order.
+    pub fn iter(&self) -> impl Iterator<Item = T> + '_ {
+        self.words.iter().enumerate().flat_map(|(i, &word)| {
+            let mut word = word;
+
+            iter::from_fn(move || {
+                if word == 0 {
+                    None
+                } else {
+                    let bit = word.trailing_zeros() as usize;
+                    word &= word - 1;
+
+                    Some(T::from(i * BITS + bit))
+                }
+            })
+        })
+    }
+
+    fn position(value: T) -> (usize, usize) {
+        let index = value.into();
+
+        (index / BITS, 1 << (index % BITS))
+    }
+}
+
+impl<T> Default for DenseBitSet<T>
+where
+    T: Copy + From<usize> + Into<usize>,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
@@ -3,8 +3,11 @@
 //!
 //! Modules:
 //!
+//! - [`atomic`]: rust's `sync::atomic` module. Can come from `std` or the `core` crate.
+//! - [`convert`]: rust's `convert` module. Can come from `std` or the `core` crate.
 //! - [`iter`]: rust's `iter` module. Can come from `std` or the `core` crate.
 //! - [`mem`]: rust's `mem` module. Can come from `std` or the `alloc` crate.
+//! - [`task`]: rust's `task` module. Can come from `std` or the `core` crate.
 //!
 //! Macros:
 //!
@@ -15,19 +18,38 @@
 //! Structs:
 //!
 //! - [`Arc`]: rust's `Arc` struct. Can come from `std` or the `alloc` crate.
+//! - [`BTreeMap`]: rust's `BTreeMap` struct. Can come from `std` or the `alloc` crate.
+//! - [`BinaryHeap`]: rust's `BinaryHeap` struct. Can come from `std` or the `alloc` crate.
 //! - [`Box`]: rust's `Box` struct. Can come from `std` or the `alloc` crate.
+//! - [`DenseBitSet`]: a bitfield-backed set for dense integer IDs. Only available with the
+//!   `dense-ids` feature.
+//! - [`DenseSet`]: a set for fragment IDs, either [`DenseBitSet`] with the `dense-ids` feature, or
+//!   [`Set`].
+//! - [`InlineVec`]: a vector for short lists. Either `SmallVec` from the `smallvec` crate with
+//!   room for 8 inline items, or rust's `Vec` struct.
 //! - [`Map`]: one of rust's map types, either `HashMap` from `std` or `BTreeMap` from the `alloc`
-//!   crate.
+//!   crate. `BTreeMap` is also used with `std` during testing and with the `deterministic`
+//!   feature.
 //! - [`Mutex`]: a futures-aware mutex. Can come from `futures`, `tokio`, or the `async-lock`
 //!   crates.
+//! - [`MutexGuard`]: the guard of [`Mutex`]. Comes from the same crate.
 //! - [`NonZeroUsize`]: rust's `NonZeroUsize` struct. Can come from `std` or the `core` crate.
+//! - [`PhantomData`]: rust's `PhantomData` struct. Can come from `std` or the `core` crate.
 //! - [`Pin`]: rust's `Pin` struct. Can come from `std` or the `core` crate.
 //! - [`Set`]: one of rust's set types, either `HashSet` from `std` or `BTreeSet` from the `alloc`
-//!   crate.
+//!   crate. `BTreeSet` is also used with `std` during testing and with the `deterministic`
+//!   feature.
+//! - [`String`]: rust's `String` struct. Can come from `std` or the `alloc` crate.
 //! - [`Vec`]: rust's `Vec` struct. Can come from `std` or the `alloc` crate.
+//! - [`VecDeque`]: rust's `VecDeque` struct. Can come from `std` or the `alloc` crate.
 //!
 //! Traits:
 //!
+//! - [`DoubleEndedIterator`]: rust's `DoubleEndedIterator` trait. Can come from `std` or the
+//!   `core` crate.
+//! - [`Error`]: rust's `Error` trait. Can come from `std` or the `core` crate.
+//! - [`ExactSizeIterator`]: rust's `ExactSizeIterator` trait. Can come from `std` or the `core`
+//!   crate.
 //! - [`Future`]: rust's `Future` trait. Can come from `std` or the `core` crate.
 //! - [`IntoIterator`]: rust's `IntoIterator` trait. Can come from `std` or the `core` crate.
 //! - [`Iterator`]: rust's `IntoIterator` trait. Can come from `std` or the `core` crate.
@@ -41,72 +63,128 @@ compile_error!(
     "Must enable one of: futures-lock, tokio-lock, or async-std-lock"
 );
 
+#[cfg(all(
+    feature = "sync-only",
+    any(feature = "tokio-lock", feature = "async-std-lock")
+))]
+compile_error!("sync-only can't be used with tokio-lock or async-std-lock");
+
 #[cfg(test)]
 pub use crate::reexported::test::*;
 
 #[cfg(test)]
 mod test;
 
+extern crate alloc;
+
+// B-tree collections have a deterministic iteration order. They are always used without `std`,
+// and with `std` during testing or when the `deterministic` feature is enabled
+#[cfg(any(not(feature = "std"), test, feature = "deterministic"))]
+pub type Map<K, V> = alloc::collections::BTreeMap<K, V>;
+#[cfg(any(not(feature = "std"), test, feature = "deterministic"))]
+pub type Set<T> = alloc::collections::BTreeSet<T>;
+#[cfg(not(any(not(feature = "std"), test, feature = "deterministic")))]
+pub type Map<K, V> = std::collections::HashMap<K, V>;
+#[cfg(not(any(not(feature = "std"), test, feature = "deterministic")))]
+pub type Set<T> = std::collections::HashSet<T>;
+
 feature_cfg! {
     for "std";
 
-    use std::collections::{HashMap, HashSet};
+    pub use std::collections::{BTreeMap, BinaryHeap, VecDeque};
 
     pub use std::{
         boxed::Box,
+        convert,
+        error::Error,
         format,
         future::Future,
-        iter::{self, IntoIterator, Iterator},
+        iter::{
+            self, DoubleEndedIterator, ExactSizeIterator, IntoIterator,
+            Iterator,
+        },
+        marker::PhantomData,
         mem,
         num::NonZeroUsize,
         pin::Pin,
-        sync::Arc,
+        sync::{atomic, Arc},
+        string::String,
+        task,
         vec::Vec,
     };
-
-    pub type Map<K, V> = HashMap<K, V>;
-    pub type Set<T> = HashSet<T>;
 }
 
 feature_cfg! {
     for !"std";
 
-    extern crate alloc;
-
-    use alloc::collections::{BTreeMap, BTreeSet};
+    pub use alloc::collections::{BTreeMap, BinaryHeap, VecDeque};
 
     pub use alloc::{
         boxed::Box,
         format,
+        string::String,
         sync::Arc,
         vec::Vec,
     };
     pub use core::{
+        convert,
+        error::Error,
         future::Future,
-        iter::{self, IntoIterator, Iterator},
+        iter::{
+            self, DoubleEndedIterator, ExactSizeIterator, IntoIterator,
+            Iterator,
+        },
+        marker::PhantomData,
         mem,
         num::NonZeroUsize,
         pin::Pin,
+        sync::atomic,
+        task,
     };
+}
+
+feature_cfg! {
+    for "smallvec";
+
+    pub type InlineVec<T> = smallvec::SmallVec<[T; 8]>;
+}
+
+feature_cfg! {
+    for !"smallvec";
+
+    pub type InlineVec<T> = Vec<T>;
+}
+
+feature_cfg! {
+    for "dense-ids";
+
+    mod dense_set;
+
+    pub use self::dense_set::DenseBitSet;
+
+    pub type DenseSet<T> = DenseBitSet<T>;
+}
+
+feature_cfg! {
+    for !"dense-ids";
 
-    pub type Map<K, V> = BTreeMap<K, V>;
-    pub type Set<T> = BTreeSet<T>;
+    pub type DenseSet<T> = Set<T>;
 }
 
 feature_cfg! {
     for "futures-lock";
 
-    pub use futures::lock::Mutex;
+    pub use futures::lock::{Mutex, MutexGuard};
 }
 
 feature_cfg! {
     for "tokio-lock";
 
-    pub use tokio::sync::Mutex;
+    pub use tokio::sync::{Mutex, MutexGuard};
 }
 
 feature_cfg! {
     for "async-std-lock";
 
-    pub use async_lock::Mutex;
+    pub use async_lock::{Mutex, MutexGuard};
 }
@@ -0,0 +1,96 @@
+use crate::{
+    reexported::{mem, NonZeroUsize, Vec},
+    FragmentId, Problem, SolveReport, Solver,
+};
+use std::time::{Duration, Instant};
+
+// Number of evaluations kept in `SolverStatistics::slowest_5`
+const SLOWEST: usize = 5;
+
+/// Statistics for a single run. See [`Solver::run_with_stats`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct SolverStatistics {
+    /// Number of times [`Problem::evaluate`] was called, successful or not.
+    pub total_evaluations: usize,
+
+    /// Number of times a fragment was punted because some of its dependencies were not evaluated
+    /// yet.
+    pub total_punts: usize,
+
+    /// Number of times the direct dependencies of a fragment were fetched. See
+    /// [`Solver::dependency_fetches`].
+    pub total_dependency_fetches: usize,
+
+    /// Largest number of fragments that were queued to be solved at the same time.
+    pub peak_queue_depth: usize,
+
+    /// How long the run took, not including waiting for concurrent runs to finish.
+    pub wall_time: Duration,
+
+    /// The slowest [`Problem::evaluate`] calls, slowest first. Holds at most 5 entries.
+    pub slowest_5: Vec<(FragmentId, Duration)>,
+}
+
+// Run-scoped statistics that need to be updated while holding the state lock
+#[derive(Debug, Default)]
+pub(crate) struct RunStats {
+    peak_queue_depth: usize,
+    slowest: Vec<(FragmentId, Duration)>,
+}
+
+impl RunStats {
+    pub(crate) fn record_queue_depth(&mut self, depth: usize) {
+        self.peak_queue_depth = self.peak_queue_depth.max(depth);
+    }
+
+    pub(crate) fn record_evaluation(&mut self, id: FragmentId, took: Duration) {
+        let index = self.slowest.partition_point(|&(_, other)| other >= took);
+        if index < SLOWEST {
+            self.slowest.insert(index, (id, took));
+            self.slowest.truncate(SLOWEST);
+        }
+    }
+}
+
+impl<P> Solver<P>
+where
+    P: Problem,
+{
+    /// Like [`Solver::run`], but returns a [`SolveReport`] together with [`SolverStatistics`] for
+    /// this run only. Statistics are captured before the run ends, so they are not affected by
+    /// other runs.
+    ///
+    /// Only available with the `std` feature.
+    pub async fn run_with_stats(
+        &self,
+        concurrency: NonZeroUsize,
+    ) -> Result<(SolveReport, SolverStatistics), P::Error> {
+        let run = self.begin_run().await;
+        let started = Instant::now();
+        let evaluations = self.evaluations_attempted();
+        let punts = self.punts();
+        let dependency_fetches = self.dependency_fetches();
+        {
+            let mut state = self.state.lock().await;
+            let depth = state.to_solve.len();
+            state.run_stats = RunStats::default();
+            state.run_stats.record_queue_depth(depth);
+        }
+
+        let res = self.run_steps(run.id(), concurrency).await;
+        let report = run.finish().await;
+        let run_stats = mem::take(&mut self.state.lock().await.run_stats);
+        let stats = SolverStatistics {
+            total_evaluations: self.evaluations_attempted() - evaluations,
+            total_punts: self.punts() - punts,
+            total_dependency_fetches: self.dependency_fetches()
+                - dependency_fetches,
+            peak_queue_depth: run_stats.peak_queue_depth,
+            wall_time: started.elapsed(),
+            slowest_5: run_stats.slowest,
+        };
+        res?;
+
+        Ok((report, stats))
+    }
+}
@@ -0,0 +1,155 @@
+use crate::{
+    reexported::{NonZeroUsize, Vec},
+    FragmentId, Problem, Progress, SolveReport, Solver,
+};
+use core::ops::ControlFlow;
+use futures::{
+    channel::mpsc,
+    future,
+    stream::{self, FuturesUnordered, Stream, StreamExt},
+};
+
+/// Event yielded by [`Solver::run_streaming`] as the run progresses.
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub enum EvaluationEvent {
+    /// The fragment was successfully evaluated.
+    Evaluated(FragmentId),
+
+    /// The fragment was punted as some of its dependencies haven't been evaluated yet. It may
+    /// be evaluated later in the run.
+    Punted(FragmentId),
+
+    /// The run finished with these fragments still punted, so they are all part of or depend on
+    /// dependency cycles. Same as [`SolveReport::punted`].
+    CycleDetected(Vec<FragmentId>),
+
+    /// The run finished. Always the last event.
+    Done(SolveReport),
+}
+
+impl<P> Solver<P>
+where
+    P: Problem,
+{
+    /// Consume `self` and return a [`Stream`] that drives the solver, yielding each fragment as
+    /// soon as it is evaluated, in evaluation order.
+    ///
+    /// The stream ends once there are no more fragments that can be evaluated, that is, once the
+    /// solver reaches either [`Status::Done`](crate::Status::Done) or
+    /// [`Status::DoneWithCycles`](crate::Status::DoneWithCycles). If an evaluation fails, the
+    /// error is yielded as the last item of the stream.
+    ///
+    /// Steps are run one at a time so that fragments are yielded in the exact order they were
+    /// evaluated. Polling the stream runs the solver the same way as [`Solver::run`], including
+    /// the calls to [`Problem::on_start`] and [`Problem::on_finish`].
+    pub fn into_stream(
+        self,
+    ) -> impl Stream<Item = Result<FragmentId, P::Error>> {
+        let (sender, receiver) = mpsc::unbounded();
+        // The run guard borrows the solver for the whole run, so the run is driven by a single
+        // future that owns the solver and sends items as they come
+        let driver = async move {
+            let run = self.begin_run().await;
+            let mut steps = FuturesUnordered::new();
+            let _ = self
+                .drive_steps(
+                    &mut steps,
+                    NonZeroUsize::MIN,
+                    || self.run_step(run.id()),
+                    |progress| {
+                        if let Progress::Evaluated(id) = progress {
+                            // The receiver may have been dropped already
+                            let _ = sender.unbounded_send(Ok(id));
+                        }
+
+                        ControlFlow::Continue(())
+                    },
+                    |_, err| {
+                        let _ = sender.unbounded_send(Err(err));
+
+                        ControlFlow::Break(())
+                    },
+                )
+                .await;
+            drop(steps);
+            let _ = run.finish().await;
+        };
+
+        stream::select(
+            receiver,
+            stream::once(driver).filter_map(|()| future::ready(None)),
+        )
+    }
+
+    /// Like [`Solver::run`], but returns a [`Stream`] of [`EvaluationEvent`]s that are yielded
+    /// as soon as fragments are evaluated or punted. Nothing runs until the stream is polled.
+    ///
+    /// Once the run finishes, [`EvaluationEvent::CycleDetected`] is yielded if any fragment is
+    /// still punted, followed by [`EvaluationEvent::Done`]. If an evaluation fails, the error is
+    /// yielded and the run finishes early without checking for cycles, but `Done` is still the
+    /// last item.
+    ///
+    /// Dropping the stream cancels the run the same way as dropping the future returned by
+    /// [`Solver::run`].
+    pub fn run_streaming(
+        &self,
+        concurrency: NonZeroUsize,
+    ) -> impl Stream<Item = Result<EvaluationEvent, P::Error>> + '_ {
+        let run = (None, FuturesUnordered::new());
+
+        // Events left to yield after the run finished are kept in reverse order
+        stream::unfold(
+            (Some(run), Vec::new()),
+            move |(run, mut tail)| async move {
+                if let Some(event) = tail.pop() {
+                    return Some((event, (None, tail)));
+                }
+                let (run, mut steps) = run?;
+                let run = match run {
+                    Some(run) => run,
+                    None => self.begin_run().await,
+                };
+
+                // Steps are kept between events, so the run carries on where it left off
+                let res = self
+                    .drive_steps(
+                        &mut steps,
+                        concurrency,
+                        || self.run_step(run.id()),
+                        |progress| match progress {
+                            Progress::Evaluated(id) => ControlFlow::Break(Ok(
+                                EvaluationEvent::Evaluated(id),
+                            )),
+                            Progress::Punted(id) => ControlFlow::Break(Ok(
+                                EvaluationEvent::Punted(id),
+                            )),
+                            _ => ControlFlow::Continue(()),
+                        },
+                        |_, err| ControlFlow::Break(Err(err)),
+                    )
+                    .await
+                    .break_value();
+                if let Some(Ok(event)) = res {
+                    return Some((Ok(event), (Some((Some(run), steps)), tail)));
+                }
+
+                drop(steps);
+                let report = run.finish().await;
+
+                tail.push(Ok(EvaluationEvent::Done(report.clone())));
+                // Punted fragments may just be waiting on the failed evaluation
+                if res.is_none() && !report.punted.is_empty() {
+                    tail.push(Ok(EvaluationEvent::CycleDetected(
+                        report.punted,
+                    )));
+                }
+                let event = match res {
+                    Some(Err(err)) => Err(err),
+                    _ => tail.pop()?,
+                };
+
+                Some((event, (None, tail)))
+            },
+        )
+    }
+}
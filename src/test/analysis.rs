@@ -0,0 +1,543 @@
+use crate::{
+    problems::HashMapProblem,
+    reexported::{test, BTreeMap, Vec},
+    test::{PetgraphProblem, CONCURRENCY},
+    FragmentId, Problem, Solver, Status,
+};
+use petgraph::Graph;
+
+// 0 depends on 1 and 2, which both depend on 3
+async fn solved_diamond() -> Solver<PetgraphProblem> {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    let p3 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p0, p2, ());
+    dependency_graph.add_edge(p1, p3, ());
+    dependency_graph.add_edge(p2, p3, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue_fragment(p0.index().into()).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    solver
+}
+
+fn path(ids: &[usize]) -> Vec<FragmentId> {
+    ids.iter().copied().map(FragmentId).collect()
+}
+
+fn sorted(mut ids: Vec<FragmentId>) -> Vec<FragmentId> {
+    ids.sort();
+
+    ids
+}
+
+#[test]
+async fn shortest_dependency_path_should_find_a_shortest_path() {
+    let solver = solved_diamond().await;
+
+    let found = solver
+        .shortest_dependency_path(FragmentId(0), FragmentId(3))
+        .await
+        .unwrap();
+    assert!(found == path(&[0, 1, 3]) || found == path(&[0, 2, 3]));
+    assert_eq!(
+        solver
+            .shortest_dependency_path(FragmentId(0), FragmentId(2))
+            .await,
+        Some(path(&[0, 2])),
+    );
+    assert_eq!(
+        solver
+            .shortest_dependency_path(FragmentId(1), FragmentId(3))
+            .await,
+        Some(path(&[1, 3])),
+    );
+}
+
+#[test]
+async fn shortest_dependency_path_to_self_should_be_trivial() {
+    let solver = solved_diamond().await;
+
+    assert_eq!(
+        solver
+            .shortest_dependency_path(FragmentId(2), FragmentId(2))
+            .await,
+        Some(path(&[2])),
+    );
+}
+
+#[test]
+async fn shortest_dependency_path_should_follow_dependency_direction() {
+    let solver = solved_diamond().await;
+
+    assert_eq!(
+        solver
+            .shortest_dependency_path(FragmentId(3), FragmentId(0))
+            .await,
+        None,
+    );
+    assert_eq!(
+        solver
+            .shortest_dependency_path(FragmentId(1), FragmentId(2))
+            .await,
+        None,
+    );
+    assert_eq!(
+        solver
+            .shortest_dependency_path(FragmentId(0), FragmentId(4))
+            .await,
+        None,
+    );
+}
+
+#[test]
+async fn explain_cycle_should_return_the_cycle_path() {
+    // 0 -> 1 -> 2 -> 0, and 3 depends on the cycle without being part of it
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    let p3 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p2, ());
+    dependency_graph.add_edge(p2, p0, ());
+    dependency_graph.add_edge(p3, p0, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue_fragment(p3.index().into()).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap().punted;
+
+    let explanation = solver.explain_cycle(FragmentId(0)).await.unwrap();
+    assert_eq!(explanation.path, path(&[0, 1, 2, 0]));
+    assert_eq!(
+        explanation.description,
+        "fragment 0 -> fragment 1 -> fragment 2 -> fragment 0",
+    );
+    assert_eq!(
+        solver.explain_cycle(FragmentId(1)).await.unwrap().path,
+        path(&[1, 2, 0, 1]),
+    );
+    assert_eq!(solver.explain_cycle(FragmentId(3)).await, None);
+}
+
+#[test]
+async fn explain_cycle_should_ignore_fragments_that_are_not_punted() {
+    let solver = solved_diamond().await;
+
+    assert_eq!(solver.explain_cycle(FragmentId(0)).await, None);
+    assert_eq!(solver.explain_cycle(FragmentId(4)).await, None);
+}
+
+#[test]
+async fn topological_order_should_list_dependencies_first() {
+    let solver = solved_diamond().await;
+    let order = solver.topological_order().await;
+
+    assert!(order == path(&[3, 1, 2, 0]) || order == path(&[3, 2, 1, 0]));
+}
+
+#[test]
+async fn topological_order_should_include_assumed_fragments() {
+    let solver = Solver::new(PetgraphProblem::new(Graph::new()));
+    solver.assume_evaluated(FragmentId(1)).await;
+    solver.assume_evaluated(FragmentId(0)).await;
+    solver.assume_evaluated(FragmentId(1)).await;
+
+    assert_eq!(solver.topological_order().await, path(&[1, 0]));
+}
+
+#[test]
+async fn fragments_pending_on_and_what_blocks_should_be_consistent() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    let p3 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p0, p2, ());
+    dependency_graph.add_edge(p1, p3, ());
+    dependency_graph.add_edge(p2, p3, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue_fragment(p0.index().into()).await;
+    // Punt 0, 1, and 2
+    for _ in 0..3 {
+        solver.step().await.unwrap();
+    }
+
+    assert_eq!(
+        sorted(solver.what_blocks(FragmentId(0)).await),
+        path(&[1, 2])
+    );
+    assert_eq!(solver.what_blocks(FragmentId(1)).await, path(&[3]));
+    assert_eq!(solver.what_blocks(FragmentId(3)).await, path(&[]));
+    assert_eq!(
+        solver.fragments_pending_on(FragmentId(3)).await,
+        path(&[1, 2])
+    );
+    assert_eq!(solver.fragments_pending_on(FragmentId(0)).await, path(&[]));
+    for id in 0..4 {
+        let id = FragmentId(id);
+        for dependency in solver.what_blocks(id).await {
+            assert!(solver
+                .fragments_pending_on(dependency)
+                .await
+                .contains(&id));
+        }
+        for dependent in solver.fragments_pending_on(id).await {
+            assert!(solver.what_blocks(dependent).await.contains(&id));
+        }
+    }
+
+    // Evaluating 3 unblocks 1 and 2, but not 0
+    solver.step().await.unwrap();
+    assert_eq!(solver.what_blocks(FragmentId(1)).await, path(&[]));
+    assert_eq!(solver.fragments_pending_on(FragmentId(3)).await, path(&[]));
+    assert_eq!(
+        sorted(solver.what_blocks(FragmentId(0)).await),
+        path(&[1, 2])
+    );
+}
+
+#[test]
+async fn fragment_dependency_list_should_return_fetched_dependencies() {
+    let solver = solved_diamond().await;
+
+    assert_eq!(
+        solver
+            .fragment_dependency_list(FragmentId(0))
+            .await
+            .map(sorted),
+        Some(path(&[1, 2]))
+    );
+    assert_eq!(
+        solver.fragment_dependency_list(FragmentId(2)).await,
+        Some(path(&[3]))
+    );
+    assert_eq!(
+        solver.fragment_dependency_list(FragmentId(3)).await,
+        Some(path(&[]))
+    );
+    assert_eq!(solver.fragment_dependency_list(FragmentId(4)).await, None);
+}
+
+#[test]
+async fn fragment_dependency_list_should_be_none_before_fetching() {
+    let solver = Solver::new(HashMapProblem::from_edges([(
+        FragmentId(0),
+        FragmentId(1),
+    )]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    assert_eq!(solver.fragment_dependency_list(FragmentId(0)).await, None);
+    // Punt 0
+    solver.step().await.unwrap();
+    assert_eq!(
+        solver.fragment_dependency_list(FragmentId(0)).await,
+        Some(path(&[1]))
+    );
+    assert_eq!(solver.fragment_dependency_list(FragmentId(1)).await, None);
+}
+
+#[test]
+async fn expand_all_dependencies_should_fetch_the_whole_graph_without_evaluating(
+) {
+    // 0 depends on 1 and 2, which both depend on 3, which depends on 4
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    let p3 = dependency_graph.add_node(());
+    let p4 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p0, p2, ());
+    dependency_graph.add_edge(p1, p3, ());
+    dependency_graph.add_edge(p2, p3, ());
+    dependency_graph.add_edge(p3, p4, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue_fragment(p0.index().into()).await;
+    solver.expand_all_dependencies(CONCURRENCY).await;
+
+    assert_eq!(solver.evaluations_attempted(), 0);
+    assert_eq!(solver.fanout(FragmentId(0)).await, 2);
+    assert_eq!(solver.fanout(FragmentId(4)).await, 0);
+    assert_eq!(solver.fanin(FragmentId(3)).await, 2);
+    assert_eq!(solver.fanin(FragmentId(0)).await, 0);
+    let longest_path = solver.longest_path().await;
+    assert_eq!(longest_path.len(), 4);
+    assert_eq!(longest_path[0], FragmentId(0));
+    assert_eq!(longest_path[2..], path(&[3, 4]));
+
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert_eq!(solver.into_problem_instance().into_evaluated().len(), 5,);
+}
+
+#[test]
+async fn longest_path_should_ignore_cycles() {
+    let solver = solved_diamond().await;
+    assert_eq!(solver.longest_path().await.len(), 3);
+
+    let mut dependency_graph = Graph::<(), ()>::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p0, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue_fragment(p0.index().into()).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+    assert_eq!(solver.longest_path().await, path(&[0, 1]));
+}
+
+async fn expanded(edges: &[(usize, usize)]) -> Solver<HashMapProblem> {
+    let solver = Solver::new(HashMapProblem::from_edges(
+        edges
+            .iter()
+            .map(|&(from, to)| (FragmentId(from), FragmentId(to))),
+    ));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.expand_all_dependencies(CONCURRENCY).await;
+
+    solver
+}
+
+#[test]
+async fn edge_count_of_a_star_should_be_the_number_of_leaves() {
+    let solver = expanded(&[(0, 1), (0, 2), (0, 3), (0, 4)]).await;
+
+    assert_eq!(solver.total_edge_count().await, 4);
+    assert_eq!(solver.average_fanout().await, 0.8);
+}
+
+#[test]
+async fn edge_count_of_a_chain_should_be_one_less_than_its_length() {
+    let solver = expanded(&[(0, 1), (1, 2), (2, 3), (3, 4)]).await;
+
+    assert_eq!(solver.total_edge_count().await, 4);
+    assert_eq!(solver.average_fanout().await, 0.8);
+}
+
+#[test]
+async fn edge_count_of_a_diamond_should_be_four() {
+    let solver = expanded(&[(0, 1), (0, 2), (1, 3), (2, 3)]).await;
+
+    assert_eq!(solver.total_edge_count().await, 4);
+    assert_eq!(solver.average_fanout().await, 1.0);
+}
+
+#[test]
+async fn edge_count_should_be_zero_before_fetching_dependencies() {
+    let solver = Solver::new(());
+
+    assert_eq!(solver.total_edge_count().await, 0);
+    assert_eq!(solver.average_fanout().await, 0.0);
+}
+
+#[test]
+async fn max_parallelism_of_a_star_should_be_the_number_of_leaves() {
+    let solver = expanded(&[(0, 1), (0, 2), (0, 3), (0, 4)]).await;
+
+    assert_eq!(solver.max_parallelism().await, 4);
+}
+
+#[test]
+async fn max_parallelism_of_a_chain_should_be_one() {
+    let solver = expanded(&[(0, 1), (1, 2), (2, 3), (3, 4)]).await;
+
+    assert_eq!(solver.max_parallelism().await, 1);
+}
+
+#[test]
+async fn max_parallelism_of_a_diamond_should_be_two() {
+    let solver = expanded(&[(0, 1), (0, 2), (1, 3), (2, 3)]).await;
+
+    assert_eq!(solver.max_parallelism().await, 2);
+}
+
+#[test]
+async fn independent_fragments_should_be_separate_components() {
+    let solver = Solver::new(HashMapProblem::default());
+    for id in 0..5 {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    assert_eq!(solver.component_sizes().await, BTreeMap::from([(1, 5)]));
+    assert_eq!(solver.largest_component_size().await, 1);
+}
+
+#[test]
+async fn component_sizes_should_ignore_edge_directions() {
+    // Two pairs and a singleton
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(3), FragmentId(2)),
+    ]));
+    for id in [0, 2, 3, 4] {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(
+        solver.component_sizes().await,
+        BTreeMap::from([(1, 1), (2, 2)]),
+    );
+    assert_eq!(solver.largest_component_size().await, 2);
+}
+
+#[test]
+async fn connected_graph_should_be_a_single_component() {
+    // 0 depends on 1 to 9, and every odd fragment also depends on the next fragment
+    let solver = expanded(&[
+        (0, 1),
+        (0, 3),
+        (0, 5),
+        (0, 7),
+        (0, 9),
+        (1, 2),
+        (3, 4),
+        (5, 6),
+        (7, 8),
+        (9, 8),
+    ])
+    .await;
+
+    assert_eq!(solver.component_sizes().await, BTreeMap::from([(10, 1)]));
+    assert_eq!(solver.largest_component_size().await, 10);
+}
+
+#[test]
+async fn empty_solver_should_have_no_components() {
+    let solver = Solver::new(HashMapProblem::default());
+
+    assert!(solver.component_sizes().await.is_empty());
+    assert_eq!(solver.largest_component_size().await, 0);
+}
+
+#[test]
+async fn run_detailed_should_list_evaluations_in_topological_order() {
+    // 0 depends on 1 and 2, which both depend on 3. 4 is assumed so it must not be listed
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    let p3 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p0, p2, ());
+    dependency_graph.add_edge(p1, p3, ());
+    dependency_graph.add_edge(p2, p3, ());
+    let edges = dependency_graph
+        .raw_edges()
+        .iter()
+        .map(|edge| (edge.source().index(), edge.target().index()))
+        .collect::<Vec<_>>();
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.assume_evaluated(FragmentId(4)).await;
+    solver.enqueue_fragment(p0.index().into()).await;
+    let report = solver.run_detailed(CONCURRENCY).await.unwrap();
+
+    assert_eq!(report.status, Status::Done);
+    assert!(report.punted.is_empty());
+    assert_eq!(report.evaluated_in_order.len(), 4);
+    let position = |id: usize| {
+        report
+            .evaluated_in_order
+            .iter()
+            .position(|&x| x == FragmentId(id))
+            .unwrap()
+    };
+    for (dependent, dependency) in edges {
+        assert!(position(dependency) < position(dependent));
+    }
+    assert_eq!(
+        report.evaluated_in_order,
+        solver
+            .into_problem_instance()
+            .into_evaluated()
+            .into_iter()
+            .map(|id| FragmentId(id.index()))
+            .collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+async fn dependencies_of_should_match_direct_dependencies() {
+    // 0 depends on 1 and 2, which both depend on 3
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(0), FragmentId(2)),
+        (FragmentId(1), FragmentId(3)),
+        (FragmentId(2), FragmentId(3)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(solver.dep_cache_size().await, 4);
+    let mut cached = Vec::new();
+    for id in FragmentId::range(FragmentId(0), FragmentId(4)) {
+        cached.push(solver.dependencies_of(id).await.unwrap());
+    }
+    assert_eq!(solver.dependencies_of(FragmentId(4)).await, None);
+
+    let problem = solver.into_problem_instance();
+    for (id, cached) in
+        FragmentId::range(FragmentId(0), FragmentId(4)).zip(cached)
+    {
+        let mut dependencies = Vec::new();
+        problem
+            .direct_dependencies(id, &mut dependencies)
+            .await
+            .unwrap();
+        assert_eq!(cached, dependencies);
+    }
+}
+
+#[test]
+async fn dependencies_of_should_exclude_ordering_constraints() {
+    let solver = Solver::new(HashMapProblem::from_edges([(
+        FragmentId(0),
+        FragmentId(1),
+    )]));
+    solver
+        .add_ordering_constraint(FragmentId(2), FragmentId(0))
+        .await;
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(
+        solver.dependencies_of(FragmentId(0)).await,
+        Some(path(&[1]))
+    );
+    assert_eq!(
+        solver
+            .fragment_dependency_list(FragmentId(0))
+            .await
+            .map(sorted),
+        Some(path(&[1, 2]))
+    );
+}
+
+#[test]
+async fn clear_dep_cache_should_not_affect_solving() {
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(2)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    // Punt 0
+    solver.step().await.unwrap();
+    assert_eq!(solver.dep_cache_size().await, 1);
+
+    solver.clear_dep_cache().await;
+    assert_eq!(solver.dep_cache_size().await, 0);
+    assert_eq!(solver.dependencies_of(FragmentId(0)).await, None);
+
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert!(solver.is_evaluated(FragmentId(0)).await);
+    assert_eq!(solver.dep_cache_size().await, 3);
+}
@@ -0,0 +1,129 @@
+use crate::{
+    reexported::{test, Box, Mutex, NonZeroUsize, Vec},
+    test::CONCURRENCY,
+    FragmentId, Problem, Solver, SolverConfig,
+};
+use async_trait::async_trait;
+use void::Void;
+
+const FRAGMENTS: usize = 100;
+
+// A chain of fragments, each depending on the previous one, that records every call to fetch
+// dependencies
+#[derive(Default)]
+struct BatchRecorder {
+    batched: bool,
+    // Number of IDs in each call to `dependencies_for_batch`
+    batches: Mutex<Vec<usize>>,
+    direct_calls: Mutex<usize>,
+}
+
+#[async_trait]
+impl Problem for BatchRecorder {
+    type Error = Void;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        *self.direct_calls.lock().await += 1;
+        if id.0 > 0 {
+            dependencies.push(FragmentId(id.0 - 1));
+        }
+
+        Ok(())
+    }
+
+    async fn dependencies_for_batch(
+        &self,
+        ids: &[FragmentId],
+        dependencies: &mut [Vec<FragmentId>],
+    ) -> Result<(), Self::Error> {
+        self.batches.lock().await.push(ids.len());
+        if self.batched {
+            for (&id, dependencies) in ids.iter().zip(dependencies) {
+                if id.0 > 0 {
+                    dependencies.push(FragmentId(id.0 - 1));
+                }
+            }
+        } else {
+            for (&id, dependencies) in ids.iter().zip(dependencies) {
+                self.direct_dependencies(id, dependencies).await?;
+            }
+        }
+
+        Ok(())
+    }
+
+    async fn evaluate(&self, _: FragmentId) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+fn solver(problem: BatchRecorder) -> Solver<BatchRecorder> {
+    Solver::with_config(
+        problem,
+        SolverConfig {
+            dependency_batch_size: NonZeroUsize::new(10).unwrap(),
+            ..SolverConfig::default()
+        },
+    )
+}
+
+#[test]
+async fn expand_all_dependencies_should_fetch_in_batches() {
+    let solver = solver(BatchRecorder {
+        batched: true,
+        ..BatchRecorder::default()
+    });
+    for id in 0..FRAGMENTS {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    solver.expand_all_dependencies(CONCURRENCY).await;
+    // Run one step at a time so no fragment is punted and fetched again while its dependency is
+    // being evaluated
+    let punted = solver.run(NonZeroUsize::MIN).await.unwrap().punted;
+
+    assert!(punted.is_empty());
+    assert_eq!(solver.evaluations_attempted(), FRAGMENTS);
+    assert_eq!(solver.dependency_fetches(), FRAGMENTS);
+    let problem = solver.into_problem_instance();
+    assert_eq!(problem.batches.into_inner(), &[10; 10]);
+    assert_eq!(problem.direct_calls.into_inner(), 0);
+}
+
+#[test]
+async fn solve_transitive_closure_should_batch_discovered_fragments() {
+    let solver = solver(BatchRecorder {
+        batched: true,
+        ..BatchRecorder::default()
+    });
+
+    let report = solver
+        .solve_transitive_closure(FragmentId(FRAGMENTS - 1), CONCURRENCY)
+        .await
+        .unwrap();
+
+    assert!(report.punted.is_empty());
+    assert_eq!(solver.evaluations_attempted(), FRAGMENTS);
+    // Each fragment is only discovered after the previous one was fetched
+    let problem = solver.into_problem_instance();
+    assert_eq!(problem.batches.into_inner().len(), FRAGMENTS);
+}
+
+#[test]
+async fn default_dependencies_for_batch_should_call_direct_dependencies() {
+    let solver = solver(BatchRecorder::default());
+    for id in 0..FRAGMENTS {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    solver.expand_all_dependencies(CONCURRENCY).await;
+
+    let problem = solver.into_problem_instance();
+    assert_eq!(problem.batches.into_inner().len(), 10);
+    assert_eq!(problem.direct_calls.into_inner(), FRAGMENTS);
+}
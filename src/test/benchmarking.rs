@@ -0,0 +1,42 @@
+use crate::{
+    reexported::{test, NonZeroUsize},
+    test::chain,
+    FragmentId, Status,
+};
+
+const CONCURRENCY: NonZeroUsize = NonZeroUsize::new(2).unwrap();
+
+#[test]
+async fn benchmark_problem_should_only_measure_measure_rounds() {
+    let solver = chain(3);
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    let report = solver.benchmark_problem(CONCURRENCY, 3, 5).await.unwrap();
+
+    // Every round evaluates the whole chain again
+    assert_eq!(solver.evaluations_attempted(), 3 * 8);
+    assert!(report.min_duration <= report.mean_duration);
+    assert!(report.mean_duration <= report.max_duration);
+    assert!(report.min_duration <= report.p99_duration);
+    assert!(report.p99_duration <= report.max_duration);
+    assert!(report.throughput_fragments_per_sec > 0.0);
+}
+
+#[test]
+async fn benchmark_problem_should_restore_the_solver_state() {
+    let solver = chain(3);
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    let _ = solver.benchmark_problem(CONCURRENCY, 0, 2).await.unwrap();
+
+    assert_eq!(solver.status().await, Status::Pending);
+    assert!(!solver.is_evaluated(FragmentId(0)).await);
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert!(solver.is_evaluated(FragmentId(0)).await);
+}
+
+#[test]
+#[should_panic(expected = "at least one round must be measured")]
+async fn benchmark_problem_should_require_measure_rounds() {
+    let _ = chain(3).benchmark_problem(CONCURRENCY, 1, 0).await;
+}
@@ -0,0 +1,89 @@
+use crate::{
+    problems::{CachingProblem, HashMapProblem},
+    reexported::{test, Box, Mutex, NonZeroUsize, Vec},
+    test::CONCURRENCY,
+    FragmentId, Problem, Solver,
+};
+use async_trait::async_trait;
+use core::convert::Infallible;
+
+const CAPACITY: NonZeroUsize = NonZeroUsize::new(16).unwrap();
+
+// Records every `direct_dependencies` call
+struct FetchRecorder {
+    inner: HashMapProblem,
+    fetched: Mutex<Vec<FragmentId>>,
+}
+
+#[async_trait]
+impl Problem for FetchRecorder {
+    type Error = Infallible;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        self.fetched.lock().await.push(id);
+        self.inner.direct_dependencies(id, dependencies).await
+    }
+
+    async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        self.inner.evaluate(id).await
+    }
+}
+
+async fn solve(
+    problem: CachingProblem<FetchRecorder>,
+) -> (CachingProblem<FetchRecorder>, Vec<FragmentId>) {
+    let solver = Solver::new(problem);
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert_eq!(solver.topological_order().await.len(), 3);
+
+    let problem = solver.into_problem_instance();
+    let fetched = problem.inner().fetched.lock().await.drain(..).collect();
+
+    (problem, fetched)
+}
+
+fn problem(capacity: NonZeroUsize) -> CachingProblem<FetchRecorder> {
+    CachingProblem::new(
+        FetchRecorder {
+            inner: HashMapProblem::from_edges([
+                (FragmentId(0), FragmentId(1)),
+                (FragmentId(1), FragmentId(2)),
+            ]),
+            fetched: Mutex::new(Vec::new()),
+        },
+        capacity,
+    )
+}
+
+#[test]
+async fn unchanged_graph_should_not_fetch_dependencies_again() {
+    let (problem, fetched) = solve(problem(CAPACITY)).await;
+    // Punted fragments are fetched again when they are retried, but those fetches already hit the
+    // cache
+    assert_eq!(fetched, [FragmentId(0), FragmentId(1), FragmentId(2)]);
+
+    let (_, fetched) = solve(problem).await;
+    assert!(fetched.is_empty());
+}
+
+#[test]
+async fn invalidation_should_only_refetch_the_affected_fragment() {
+    let (problem, _) = solve(problem(CAPACITY)).await;
+    problem.invalidate(FragmentId(1)).await;
+
+    let (_, fetched) = solve(problem).await;
+    assert_eq!(fetched, [FragmentId(1)]);
+}
+
+#[test]
+async fn evicted_fragments_should_be_fetched_again() {
+    let (problem, _) = solve(problem(NonZeroUsize::new(1).unwrap())).await;
+    let (_, fetched) = solve(problem).await;
+    assert!(fetched.contains(&FragmentId(0)));
+}
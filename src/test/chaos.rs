@@ -0,0 +1,98 @@
+use crate::{
+    problems::{ChaosError, ChaosMonkeyProblem, HashMapProblem},
+    reexported::test,
+    test::CONCURRENCY,
+    FragmentId, FragmentLifecycle, Solver,
+};
+use rand::rngs::SmallRng;
+use std::time::Duration;
+
+const DELAY: std::ops::Range<Duration> =
+    Duration::ZERO..Duration::from_millis(2);
+
+fn tree() -> HashMapProblem {
+    HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(0), FragmentId(2)),
+        (FragmentId(1), FragmentId(3)),
+    ])
+}
+
+#[test]
+async fn chaos_monkey_should_fail_everything_with_a_failure_rate_of_one() {
+    let solver = Solver::new(ChaosMonkeyProblem::<_, SmallRng>::with_seed(
+        HashMapProblem::default(),
+        0,
+        1.0,
+        DELAY,
+    ));
+    for id in 0..10 {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    for _ in 0..10 {
+        assert_eq!(solver.step().await, Err(ChaosError::RandomFailure));
+    }
+    for id in 0..10 {
+        assert_eq!(
+            solver.fragment_state(FragmentId(id)).await,
+            FragmentLifecycle::Failed,
+        );
+    }
+}
+
+#[test]
+async fn chaos_monkey_should_match_the_inner_problem_with_a_failure_rate_of_zero(
+) {
+    let plain = Solver::new(tree());
+    plain.enqueue_fragment(FragmentId(0)).await;
+    let plain_punted = plain.run(CONCURRENCY).await.unwrap().punted;
+
+    let chaotic = Solver::new(ChaosMonkeyProblem::<_, SmallRng>::with_seed(
+        tree(),
+        0,
+        0.0,
+        DELAY,
+    ));
+    chaotic.enqueue_fragment(FragmentId(0)).await;
+    let chaotic_punted = chaotic.run(CONCURRENCY).await.unwrap().punted;
+
+    assert_eq!(chaotic_punted, plain_punted);
+    chaotic.assert_no_cycles().await;
+    chaotic
+        .assert_all_solved(&[
+            FragmentId(0),
+            FragmentId(1),
+            FragmentId(2),
+            FragmentId(3),
+        ])
+        .await;
+}
+
+#[test]
+async fn chaos_monkey_should_be_reproducible_with_the_same_seed() {
+    async fn failures(seed: u64) -> usize {
+        let solver = Solver::new(ChaosMonkeyProblem::<_, SmallRng>::with_seed(
+            HashMapProblem::default(),
+            seed,
+            0.5,
+            Duration::ZERO..Duration::ZERO,
+        ));
+        for id in 0..100 {
+            solver.enqueue_fragment(FragmentId(id)).await;
+        }
+
+        let mut failures = 0;
+        while solver.can_make_progress().await {
+            if solver.step().await.is_err() {
+                failures += 1;
+            }
+        }
+
+        failures
+    }
+
+    let first = failures(42).await;
+    assert_eq!(failures(42).await, first);
+    assert!(first > 0 && first < 100);
+}
@@ -0,0 +1,124 @@
+use crate::{
+    reexported::{
+        atomic::{AtomicUsize, Ordering},
+        test, Arc, Box, Mutex, NonZeroUsize, Vec,
+    },
+    FragmentId, Problem, Solver, SolverCheckpoint,
+};
+use async_trait::async_trait;
+
+const FRAGMENTS: usize = 100;
+
+// Evaluate one fragment at a time so the crash always happens at the same point
+const CONCURRENCY: NonZeroUsize = NonZeroUsize::MIN;
+
+// A chain where each fragment depends on the previous one. Evaluation number `crash_after + 1`
+// fails, simulating a crash
+struct CrashingProblem {
+    crash_after: Option<usize>,
+    evaluations: AtomicUsize,
+}
+
+impl CrashingProblem {
+    fn new(crash_after: Option<usize>) -> Self {
+        Self {
+            crash_after,
+            evaluations: AtomicUsize::new(0),
+        }
+    }
+}
+
+#[async_trait]
+impl Problem for CrashingProblem {
+    type Error = &'static str;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        if id.0 > 0 {
+            dependencies.push(FragmentId(id.0 - 1));
+        }
+
+        Ok(())
+    }
+
+    async fn evaluate(&self, _: FragmentId) -> Result<(), Self::Error> {
+        let evaluations = self.evaluations.fetch_add(1, Ordering::SeqCst);
+        if Some(evaluations) == self.crash_after {
+            Err("crash")
+        } else {
+            Ok(())
+        }
+    }
+}
+
+#[test]
+async fn restored_checkpoints_should_resume_after_a_crash() {
+    let checkpoints = Arc::new(Mutex::new(Vec::new()));
+    let solver = Solver::new(CrashingProblem::new(Some(50)));
+    for id in 0..FRAGMENTS {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    let res = solver
+        .run_with_checkpointing(CONCURRENCY, 10, {
+            let checkpoints = checkpoints.clone();
+            move |checkpoint: &SolverCheckpoint| {
+                checkpoints.try_lock().unwrap().push(checkpoint.clone());
+            }
+        })
+        .await;
+    assert_eq!(res, Err("crash"));
+
+    let checkpoints = checkpoints.lock().await;
+    assert_eq!(checkpoints.len(), 5);
+    let last = checkpoints.last().unwrap();
+    assert_eq!(last.solved, (0..50).map(FragmentId).collect::<Vec<_>>());
+    assert_eq!(
+        last.pending,
+        (50..FRAGMENTS).map(FragmentId).collect::<Vec<_>>()
+    );
+
+    let restored = Solver::new(CrashingProblem::new(None));
+    restored.restore_from_snapshot(last).await;
+    assert!(restored.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(restored.evaluations_attempted(), 50);
+    restored
+        .assert_all_solved(&(0..FRAGMENTS).map(FragmentId).collect::<Vec<_>>())
+        .await;
+}
+
+#[test]
+async fn run_with_checkpointing_should_not_save_before_enough_evaluations() {
+    let saves = Arc::new(AtomicUsize::new(0));
+    let solver = Solver::new(CrashingProblem::new(None));
+    for id in 0..5 {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    let report = solver
+        .run_with_checkpointing(CONCURRENCY, 10, {
+            let saves = saves.clone();
+            move |_: &SolverCheckpoint| {
+                saves.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .await
+        .unwrap();
+
+    assert!(report.punted.is_empty());
+    assert_eq!(saves.load(Ordering::SeqCst), 0);
+}
+
+#[test]
+#[should_panic(expected = "checkpoint_every must not be zero")]
+async fn zero_checkpoint_interval_should_panic() {
+    let solver = Solver::new(CrashingProblem::new(None));
+    let _ = solver
+        .run_with_checkpointing(CONCURRENCY, 0, |_: &SolverCheckpoint| {})
+        .await;
+}
@@ -0,0 +1,148 @@
+use crate::{
+    problems::HashMapProblem,
+    reexported::{test, NonZeroUsize},
+    test::CONCURRENCY,
+    FragmentId, FragmentLifecycle, Solver, SolverConfig, Status,
+};
+
+#[test]
+async fn clone_reset_should_solve_independently_of_the_original() {
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(2), FragmentId(1)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let clone = solver.clone_reset();
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    clone.assert_no_cycles().await;
+    assert!(!clone.is_evaluated(FragmentId(1)).await);
+    clone.enqueue_fragment(FragmentId(2)).await;
+    assert!(clone.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(
+        solver.topological_order().await,
+        [FragmentId(1), FragmentId(0)],
+    );
+    assert_eq!(
+        clone.topological_order().await,
+        [FragmentId(1), FragmentId(2)]
+    );
+    assert_eq!(solver.evaluations_attempted(), 2);
+    assert_eq!(clone.evaluations_attempted(), 2);
+}
+
+#[test]
+async fn clone_reset_should_keep_the_configuration() {
+    let config = SolverConfig {
+        concurrency: NonZeroUsize::new(3).unwrap(),
+        dependency_batch_size: NonZeroUsize::new(5).unwrap(),
+        auto_enqueue_missing: true,
+    };
+    let solver = Solver::with_config(HashMapProblem::default(), config);
+
+    assert_eq!(*solver.clone_reset().config(), config);
+}
+
+// 0 -> 1 -> 2 -> 0 cycle, and 3 depends on the cycle
+async fn solved_cycle() -> Solver<HashMapProblem> {
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(2)),
+        (FragmentId(2), FragmentId(0)),
+        (FragmentId(3), FragmentId(0)),
+    ]));
+    solver.enqueue_fragment(FragmentId(3)).await;
+    assert!(!solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    solver
+}
+
+#[test]
+async fn clone_for_assumption_testing_should_propagate_assumptions() {
+    let solver = solved_cycle().await;
+
+    let clone = solver.clone_for_assumption_testing(&[FragmentId(2)]).await;
+
+    assert_eq!(
+        clone.fragment_state(FragmentId(1)).await,
+        FragmentLifecycle::Queued
+    );
+    assert_eq!(
+        clone.fragment_state(FragmentId(2)).await,
+        FragmentLifecycle::Skipped
+    );
+    assert_eq!(
+        clone.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Punted { pending_count: 1 }
+    );
+    assert!(clone.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    clone.assert_no_cycles().await;
+    assert_eq!(
+        clone.topological_order().await,
+        [FragmentId(2), FragmentId(1), FragmentId(0), FragmentId(3)]
+    );
+}
+
+#[test]
+async fn clone_for_assumption_testing_should_not_affect_the_original() {
+    let solver = solved_cycle().await;
+
+    let clone = solver.clone_for_assumption_testing(&[FragmentId(2)]).await;
+    assert!(clone.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(solver.status().await, Status::DoneWithCycles);
+    assert_eq!(
+        solver.cycle_participants().await,
+        [FragmentId(0), FragmentId(1), FragmentId(2), FragmentId(3)]
+    );
+    assert!(solver.topological_order().await.is_empty());
+    assert_eq!(clone.evaluations_attempted(), 3);
+    assert_eq!(solver.evaluations_attempted(), 0);
+}
+
+#[test]
+async fn clone_for_assumption_testing_should_not_see_later_changes_to_the_original(
+) {
+    let solver = Solver::new(HashMapProblem::from_edges([(
+        FragmentId(0),
+        FragmentId(1),
+    )]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    let clone = solver.clone_for_assumption_testing(&[]).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    solver.enqueue_fragment(FragmentId(2)).await;
+
+    assert_eq!(
+        clone.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Queued
+    );
+    assert_eq!(
+        clone.fragment_state(FragmentId(2)).await,
+        FragmentLifecycle::Unknown
+    );
+    assert!(clone.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert_eq!(
+        clone.topological_order().await,
+        solver.topological_order().await
+    );
+}
+
+#[test]
+async fn clone_for_assumption_testing_should_depend_on_the_assumptions() {
+    let solver = solved_cycle().await;
+
+    let break_at_0 =
+        solver.clone_for_assumption_testing(&[FragmentId(0)]).await;
+    let break_at_1 =
+        solver.clone_for_assumption_testing(&[FragmentId(1)]).await;
+    assert!(break_at_0.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert!(break_at_1.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    let order_0 = break_at_0.topological_order().await;
+    let order_1 = break_at_1.topological_order().await;
+    assert_eq!(order_0[0], FragmentId(0));
+    assert_eq!(order_1[0], FragmentId(1));
+    assert_ne!(order_0, order_1);
+}
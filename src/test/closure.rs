@@ -0,0 +1,52 @@
+use crate::{
+    reexported::test,
+    test::{PetgraphProblem, CONCURRENCY},
+    Solver, Status,
+};
+use petgraph::Graph;
+
+#[test]
+async fn transitive_closure_of_diamond_should_evaluate_all_fragments() {
+    let mut dependency_graph = Graph::new();
+    let a = dependency_graph.add_node(());
+    let b = dependency_graph.add_node(());
+    let c = dependency_graph.add_node(());
+    let d = dependency_graph.add_node(());
+    dependency_graph.add_edge(a, b, ());
+    dependency_graph.add_edge(a, c, ());
+    dependency_graph.add_edge(b, d, ());
+    dependency_graph.add_edge(c, d, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    let report = solver
+        .solve_transitive_closure(a.index().into(), CONCURRENCY)
+        .await
+        .unwrap();
+
+    assert_eq!(report.status, Status::Done);
+    assert_eq!(solver.max_queue_depth(), 4);
+    assert_eq!(solver.evaluations_attempted(), 4);
+    assert_eq!(
+        solver.into_problem_instance().into_evaluated(),
+        &[d, b, c, a]
+    );
+}
+
+#[test]
+async fn transitive_closure_should_skip_evaluated_fragments() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.assume_evaluated(p1.index().into()).await;
+    let report = solver
+        .solve_transitive_closure(p0.index().into(), CONCURRENCY)
+        .await
+        .unwrap();
+
+    assert_eq!(report.status, Status::Done);
+
+    assert_eq!(solver.into_problem_instance().into_evaluated(), &[p0]);
+}
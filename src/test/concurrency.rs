@@ -0,0 +1,173 @@
+use crate::{
+    reexported::{test, Box, Mutex, NonZeroUsize, Vec},
+    test::{GatedProblem, CONCURRENCY},
+    FragmentId, FragmentLifecycle, Problem, Solver, Status,
+};
+use async_trait::async_trait;
+use core::time::Duration;
+use futures::{future, pin_mut};
+use void::Void;
+
+#[test]
+async fn concurrent_runs_should_be_serialized() {
+    let solver = Solver::new(GatedProblem::default());
+    let open_gate = solver.problem_instance.gate(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    {
+        let first = solver.run(CONCURRENCY);
+        let second = solver.run(CONCURRENCY);
+        pin_mut!(first, second);
+
+        // The first run is blocked evaluating the only fragment. Without serialization the
+        // second run would find nothing left to do and return right away
+        assert!(future::poll_immediate(&mut first).await.is_none());
+        assert!(future::poll_immediate(&mut second).await.is_none());
+
+        open_gate.send(()).unwrap();
+        let (first, second) = future::join(first, second).await;
+
+        assert!(first.unwrap().punted.is_empty());
+        assert!(second.unwrap().punted.is_empty());
+    }
+    assert_eq!(
+        solver.into_problem_instance().into_evaluated(),
+        &[FragmentId(0)],
+    );
+}
+
+#[test]
+async fn is_running_should_only_be_true_during_a_run() {
+    let solver = Solver::new(GatedProblem::default());
+    let open_gate = solver.problem_instance.gate(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(!solver.is_running());
+
+    {
+        let run = solver.run(CONCURRENCY);
+        pin_mut!(run);
+
+        // The run is blocked evaluating the only fragment
+        assert!(future::poll_immediate(&mut run).await.is_none());
+        assert!(solver.is_running());
+
+        open_gate.send(()).unwrap();
+        assert!(run.await.unwrap().punted.is_empty());
+    }
+    assert!(!solver.is_running());
+}
+
+#[test]
+async fn remove_fragment_should_only_remove_queued_fragments_during_a_run() {
+    let solver = Solver::new(GatedProblem::default());
+    let open_gate = solver.problem_instance.gate(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(1)).await;
+
+    {
+        let run = solver.run(NonZeroUsize::MIN);
+        pin_mut!(run);
+
+        // The run is blocked evaluating fragment 0 while fragment 1 is still queued
+        assert!(future::poll_immediate(&mut run).await.is_none());
+        assert!(!solver.remove_fragment(FragmentId(0)).await);
+        assert!(solver.remove_fragment(FragmentId(1)).await);
+        assert!(!solver.remove_fragment(FragmentId(1)).await);
+
+        open_gate.send(()).unwrap();
+        assert!(run.await.unwrap().punted.is_empty());
+    }
+    assert_eq!(solver.status().await, Status::Done);
+    assert_eq!(
+        solver.into_problem_instance().into_evaluated(),
+        &[FragmentId(0)],
+    );
+}
+
+// Fragment `n` depends on `n / 2` and `n / 3`, and each evaluation takes a few milliseconds
+struct CountingProblem {
+    evaluations: Mutex<Vec<usize>>,
+}
+
+#[async_trait]
+impl Problem for CountingProblem {
+    type Error = Void;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        if id.0 > 0 {
+            dependencies.extend([FragmentId(id.0 / 2), FragmentId(id.0 / 3)]);
+        }
+
+        Ok(())
+    }
+
+    async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        let delay = Duration::from_millis((id.0 % 4) as u64);
+        tokio::time::sleep(delay).await;
+        self.evaluations.lock().await[id.0] += 1;
+
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn fragments_should_be_evaluated_once_under_high_concurrency() {
+    const FRAGMENTS: usize = 500;
+
+    let solver = Solver::new(CountingProblem {
+        evaluations: Mutex::new(Vec::from([0; FRAGMENTS])),
+    });
+    // Enqueue everything so dependents get punted while their dependencies are being evaluated
+    for id in 0..FRAGMENTS {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    let punted = solver
+        .run(NonZeroUsize::new(32).unwrap())
+        .await
+        .unwrap()
+        .punted;
+
+    assert!(punted.is_empty());
+    for id in 0..FRAGMENTS {
+        assert!(solver.is_evaluated(FragmentId(id)).await);
+    }
+    assert_eq!(solver.evaluations_attempted(), FRAGMENTS);
+    assert!(solver
+        .into_problem_instance()
+        .evaluations
+        .into_inner()
+        .iter()
+        .all(|&count| count == 1));
+}
+
+#[test]
+async fn failed_runs_should_requeue_fragments_of_other_steps() {
+    let solver = Solver::new(GatedProblem::default().with_failing(&[0]));
+    let fail = solver.problem_instance.gate(FragmentId(0)).await;
+    let _open_gate = solver.problem_instance.gate(FragmentId(1)).await;
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(1)).await;
+
+    {
+        let run = solver.run(CONCURRENCY);
+        pin_mut!(run);
+
+        // Fragment 1 is still being evaluated when the evaluation of fragment 0 fails
+        assert!(future::poll_immediate(&mut run).await.is_none());
+        fail.send(()).unwrap();
+        assert_eq!(run.await, Err(FragmentId(0)));
+    }
+    assert_eq!(
+        solver.fragment_state(FragmentId(1)).await,
+        FragmentLifecycle::Queued
+    );
+
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert!(solver.is_evaluated(FragmentId(1)).await);
+}
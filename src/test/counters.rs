@@ -0,0 +1,116 @@
+use crate::{
+    problems::HashMapProblem,
+    reexported::test,
+    test::{PetgraphProblem, CONCURRENCY},
+    FragmentId, Solver,
+};
+use petgraph::Graph;
+
+#[test]
+async fn counters_should_be_zero_for_a_new_solver() {
+    let solver = Solver::new(());
+
+    assert_eq!(solver.steps_taken(), 0);
+    assert_eq!(solver.evaluations_attempted(), 0);
+    assert_eq!(solver.max_queue_depth(), 0);
+    assert_eq!(solver.punts(), 0);
+    assert_eq!(solver.dependency_fetches(), 0);
+}
+
+#[test]
+async fn counters_should_match_a_stepped_chain() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p2, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue_fragment(p0.index().into()).await;
+    while solver.step().await.unwrap() {}
+
+    // Punt p0, punt p1, evaluate p2, p1, and p0, and a final step with nothing to do
+    assert_eq!(solver.steps_taken(), 6);
+    assert_eq!(solver.evaluations_attempted(), 3);
+    assert_eq!(solver.max_queue_depth(), 1);
+    assert_eq!(solver.punts(), 2);
+    assert_eq!(solver.dependency_fetches(), 5);
+}
+
+#[test]
+async fn counters_should_match_a_run_tree() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    let p3 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p0, p2, ());
+    dependency_graph.add_edge(p0, p3, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue_fragment(p0.index().into()).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(solver.evaluations_attempted(), 4);
+    assert_eq!(solver.max_queue_depth(), 3);
+    assert!(solver.steps_taken() >= 6);
+}
+
+#[test]
+async fn step_count_until_stable_should_count_steps_of_a_chain() {
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(2)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    // Punt 0 and 1, then evaluate 2, 1, and 0
+    assert_eq!(solver.step_count_until_stable().await.unwrap(), 5);
+    assert_eq!(solver.steps_to_solve(FragmentId(2)).await, Some(3));
+    assert_eq!(solver.steps_to_solve(FragmentId(1)).await, Some(4));
+    assert_eq!(solver.steps_to_solve(FragmentId(0)).await, Some(5));
+
+    // Already stable
+    assert_eq!(solver.step_count_until_stable().await.unwrap(), 0);
+}
+
+#[test]
+async fn step_count_until_stable_should_count_steps_of_a_tree() {
+    // 0 depends on 1 and 2, 1 depends on 3 and 4
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(0), FragmentId(2)),
+        (FragmentId(1), FragmentId(3)),
+        (FragmentId(1), FragmentId(4)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    // Punt 0 and 1, then evaluate every fragment once
+    assert_eq!(solver.step_count_until_stable().await.unwrap(), 7);
+    assert_eq!(solver.steps_to_solve(FragmentId(0)).await, Some(7));
+    assert!(
+        solver.steps_to_solve(FragmentId(1)).await
+            > solver.steps_to_solve(FragmentId(3)).await
+    );
+    assert!(
+        solver.steps_to_solve(FragmentId(1)).await
+            > solver.steps_to_solve(FragmentId(4)).await
+    );
+}
+
+#[test]
+async fn steps_to_solve_should_be_none_for_unevaluated_fragments() {
+    let solver = Solver::new(HashMapProblem::from_edges([(
+        FragmentId(0),
+        FragmentId(1),
+    )]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.assume_evaluated(FragmentId(1)).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(solver.steps_to_solve(FragmentId(0)).await, Some(1));
+    assert_eq!(solver.steps_to_solve(FragmentId(1)).await, None);
+    assert_eq!(solver.steps_to_solve(FragmentId(2)).await, None);
+}
@@ -1,7 +1,8 @@
 use crate::{
-    reexported::{test, Set},
+    problems::HashMapProblem,
+    reexported::{test, BTreeMap, Set, Vec},
     test::{PetgraphProblem, CONCURRENCY},
-    FragmentId, Solver, Status,
+    FragmentId, FragmentLifecycle, Solver, Status,
 };
 use petgraph::{graph::NodeIndex, Graph};
 
@@ -17,6 +18,7 @@ async fn should_be_able_to_punt_a_self_cycle() {
         .run(CONCURRENCY)
         .await
         .unwrap()
+        .punted
         .into_iter()
         .collect::<Set<_>>();
 
@@ -25,6 +27,43 @@ async fn should_be_able_to_punt_a_self_cycle() {
     assert_eq!(solver.into_problem_instance().into_evaluated(), &[]);
 }
 
+#[test]
+async fn self_dependencies_should_not_count_as_pending() {
+    let solver = Solver::new(HashMapProblem::from_edges([(
+        FragmentId(0),
+        FragmentId(0),
+    )]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.step().await.unwrap());
+
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Punted { pending_count: 0 }
+    );
+    assert_eq!(solver.fragments_pending_on(FragmentId(0)).await, &[]);
+    assert!(!solver.step().await.unwrap());
+    assert_eq!(solver.status().await, Status::DoneWithCycles);
+}
+
+#[test]
+async fn self_dependencies_should_keep_fragments_punted_after_other_dependencies(
+) {
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(0)),
+        (FragmentId(0), FragmentId(1)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    let report = solver.run_detailed(CONCURRENCY).await.unwrap();
+    assert_eq!(report.status, Status::DoneWithCycles);
+    assert_eq!(report.punted, &[FragmentId(0)]);
+    assert!(solver.is_evaluated(FragmentId(1)).await);
+
+    // Breaking the cycle must not underflow the pending count of 0
+    assert_eq!(solver.mark_all_solved([FragmentId(0)]).await, 1);
+    solver.assert_no_cycles().await;
+}
+
 #[test]
 async fn should_be_able_to_punt_a_two_node_cycle() {
     let mut dependency_graph = Graph::new();
@@ -40,6 +79,7 @@ async fn should_be_able_to_punt_a_two_node_cycle() {
         .run(CONCURRENCY)
         .await
         .unwrap()
+        .punted
         .into_iter()
         .collect::<Set<_>>();
 
@@ -64,6 +104,7 @@ async fn should_be_able_to_punt_a_self_cycle_within_a_two_node_cycle() {
         .run(CONCURRENCY)
         .await
         .unwrap()
+        .punted
         .into_iter()
         .collect::<Set<_>>();
 
@@ -89,6 +130,7 @@ async fn should_be_able_to_punt_a_two_intersecting_cycles() {
         .run(CONCURRENCY)
         .await
         .unwrap()
+        .punted
         .into_iter()
         .collect::<Set<_>>();
 
@@ -97,6 +139,302 @@ async fn should_be_able_to_punt_a_two_intersecting_cycles() {
     assert_eq!(solver.into_problem_instance().into_evaluated(), &[]);
 }
 
+#[test]
+async fn clear_punted_should_break_all_cycles() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p0, ());
+    dependency_graph.add_edge(p2, p2, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue_fragment(p0.index().into()).await;
+    solver.enqueue_fragment(p2.index().into()).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+    assert_eq!(solver.status().await, Status::DoneWithCycles);
+
+    let cleared = solver.clear_punted().await.into_iter().collect::<Set<_>>();
+    assert_eq!(cleared, index_slice_as_set(&[p0, p1, p2]));
+    solver.assert_no_cycles().await;
+    assert_eq!(solver.into_problem_instance().into_evaluated(), &[]);
+}
+
+#[test]
+async fn clear_punted_should_unblock_fragments_waiting_on_multiple_cleared_fragments(
+) {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p0, ());
+    dependency_graph.add_edge(p2, p0, ());
+    dependency_graph.add_edge(p2, p1, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue_fragment(p0.index().into()).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+    solver.clear_punted().await;
+
+    solver.enqueue_fragment(p2.index().into()).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    solver.assert_no_cycles().await;
+    assert_eq!(solver.into_problem_instance().into_evaluated(), &[p2]);
+}
+
+#[test]
+async fn mark_all_solved_should_break_a_cycle_in_one_call() {
+    // 0, 1, and 2 form a cycle. 3 depends on 0 and 2
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    let p3 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p2, ());
+    dependency_graph.add_edge(p2, p0, ());
+    dependency_graph.add_edge(p3, p0, ());
+    dependency_graph.add_edge(p3, p2, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue_fragment(p3.index().into()).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+    assert_eq!(solver.status().await, Status::DoneWithCycles);
+
+    let cycle = [p0, p1, p2].map(|id| FragmentId::from(id.index()));
+    assert_eq!(solver.mark_all_solved(cycle).await, 3);
+    assert_eq!(solver.status().await, Status::Pending);
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    solver.assert_no_cycles().await;
+
+    assert_eq!(solver.mark_all_solved(cycle).await, 0);
+    assert_eq!(solver.into_problem_instance().into_evaluated(), &[p3]);
+}
+
 fn index_slice_as_set(indexes: &[NodeIndex<u32>]) -> Set<FragmentId> {
     indexes.iter().map(|x| x.index().into()).collect()
 }
+
+#[test]
+async fn fragments_only_blocked_by_cycles_should_ignore_pending_chains() {
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(0)),
+        (FragmentId(2), FragmentId(9)),
+        (FragmentId(4), FragmentId(0)),
+        (FragmentId(5), FragmentId(2)),
+    ]));
+    for id in [0, 2, 4, 5] {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+    // Punt 0, 1, 2, 4, and 5, leaving 9 queued
+    for _ in 0..5 {
+        assert!(solver.step().await.unwrap());
+    }
+    assert_eq!(solver.status().await, Status::Pending);
+
+    let stuck = solver
+        .fragments_only_blocked_by_cycles()
+        .await
+        .into_iter()
+        .collect::<Set<_>>();
+    let expected = [FragmentId(0), FragmentId(1), FragmentId(4)];
+    assert_eq!(stuck, Set::from(expected));
+
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+    let stuck = solver
+        .fragments_only_blocked_by_cycles()
+        .await
+        .into_iter()
+        .collect::<Set<_>>();
+    assert_eq!(stuck, Set::from(expected));
+    assert!(solver.is_evaluated(FragmentId(5)).await);
+}
+
+#[test]
+async fn explain_should_describe_a_punted_fragment_in_a_cycle() {
+    use crate::FragmentLifecycle;
+
+    // 1 and 2 depend on each other, 1 also depends on 3, and 0 depends on 1
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(2)),
+        (FragmentId(1), FragmentId(3)),
+        (FragmentId(2), FragmentId(1)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    let mut explanation = solver.explain(FragmentId(1)).await;
+    explanation.blocked_dependents.sort();
+
+    assert_eq!(
+        explanation.state,
+        FragmentLifecycle::Punted { pending_count: 1 }
+    );
+    assert_eq!(explanation.unsatisfied_dependencies, &[FragmentId(2)]);
+    assert_eq!(explanation.satisfied_dependencies, &[FragmentId(3)]);
+    assert_eq!(
+        explanation.blocked_dependents,
+        &[FragmentId(0), FragmentId(2)]
+    );
+    assert_eq!(
+        explanation.cycle_path,
+        Some(Vec::from([FragmentId(1), FragmentId(2), FragmentId(1)]))
+    );
+    assert_eq!(explanation.description, "fragment 1");
+
+    let explanation = solver.explain(FragmentId(3)).await;
+    assert_eq!(explanation.state, FragmentLifecycle::Evaluated);
+    assert!(explanation.blocked_dependents.is_empty());
+    assert_eq!(explanation.cycle_path, None);
+
+    let explanation = solver.explain(FragmentId(4)).await;
+    assert_eq!(explanation.state, FragmentLifecycle::Unknown);
+    assert!(explanation.unsatisfied_dependencies.is_empty());
+}
+
+#[test]
+async fn cycle_initiators_should_exclude_fragments_pulled_into_a_cycle() {
+    // A (0) and B (1) depend on each other, C (2) depends on B, and 3 depends on itself
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(0)),
+        (FragmentId(2), FragmentId(1)),
+        (FragmentId(3), FragmentId(3)),
+    ]));
+    solver.enqueue_fragment(FragmentId(2)).await;
+    solver.enqueue_fragment(FragmentId(3)).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(
+        solver.cycle_initiators().await,
+        &[FragmentId(0), FragmentId(1), FragmentId(3)]
+    );
+    assert_eq!(
+        solver.cycle_participants().await,
+        &[FragmentId(0), FragmentId(1), FragmentId(2), FragmentId(3)]
+    );
+}
+
+// Run until done with cycles, then check that assuming the suggestions breaks all cycles
+async fn assert_cycle_break_suggestions(
+    edges: &[(usize, usize)],
+    expected: &[usize],
+) {
+    let solver = Solver::new(HashMapProblem::from_edges(
+        edges
+            .iter()
+            .map(|&(from, to)| (FragmentId(from), FragmentId(to))),
+    ));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(!solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    let suggestions = solver.cycle_break_suggestions().await;
+    assert_eq!(
+        suggestions,
+        expected.iter().copied().map(FragmentId).collect::<Vec<_>>()
+    );
+    solver.mark_all_solved(suggestions).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    solver.assert_no_cycles().await;
+}
+
+#[test]
+async fn cycle_break_suggestions_should_break_a_single_cycle_once() {
+    // 0 depends on the 1 -> 2 -> 3 -> 1 cycle
+    assert_cycle_break_suggestions(&[(0, 1), (1, 2), (2, 3), (3, 1)], &[1])
+        .await;
+}
+
+#[test]
+async fn cycle_break_suggestions_should_break_intersecting_cycles_at_the_intersection(
+) {
+    // 0 -> 1 -> 0 and 1 -> 2 -> 1 share 1
+    assert_cycle_break_suggestions(&[(0, 1), (1, 0), (1, 2), (2, 1)], &[1])
+        .await;
+}
+
+#[test]
+async fn cycle_break_suggestions_should_include_self_cycles() {
+    // 0 depends on itself and on the 1 -> 2 -> 1 cycle
+    assert_cycle_break_suggestions(&[(0, 0), (0, 1), (1, 2), (2, 1)], &[0, 1])
+        .await;
+}
+
+#[test]
+async fn cycle_break_suggestions_should_break_disjoint_cycles_separately() {
+    assert_cycle_break_suggestions(
+        &[(0, 1), (0, 3), (1, 2), (2, 1), (3, 4), (4, 3)],
+        &[1, 3],
+    )
+    .await;
+}
+
+#[test]
+async fn cycle_break_suggestions_should_be_empty_without_cycles() {
+    assert!(Solver::new(()).cycle_break_suggestions().await.is_empty());
+}
+
+#[test]
+async fn cycle_size_histogram_should_count_each_cycle_once() {
+    // 0 -> 1 -> 0 and 2 -> 3 -> 4 -> 2, with 5 depending on both cycles
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(0)),
+        (FragmentId(2), FragmentId(3)),
+        (FragmentId(3), FragmentId(4)),
+        (FragmentId(4), FragmentId(2)),
+        (FragmentId(5), FragmentId(0)),
+        (FragmentId(5), FragmentId(2)),
+    ]));
+    solver.enqueue_fragment(FragmentId(5)).await;
+    assert_eq!(solver.run(CONCURRENCY).await.unwrap().punted.len(), 6);
+
+    assert_eq!(
+        solver.cycle_size_histogram().await,
+        BTreeMap::from([(2, 1), (3, 1)])
+    );
+    assert_eq!(solver.largest_cycle_size().await, 3);
+    assert_eq!(solver.smallest_cycle_size().await, 2);
+}
+
+#[test]
+async fn cycle_size_histogram_should_count_self_cycles_and_merge_overlapping_cycles(
+) {
+    // 0 depends on itself. 1 -> 2 -> 1 and 2 -> 3 -> 2 share 2
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(0)),
+        (FragmentId(1), FragmentId(2)),
+        (FragmentId(2), FragmentId(1)),
+        (FragmentId(2), FragmentId(3)),
+        (FragmentId(3), FragmentId(2)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(1)).await;
+    assert_eq!(solver.run(CONCURRENCY).await.unwrap().punted.len(), 4);
+
+    assert_eq!(
+        solver.cycle_size_histogram().await,
+        BTreeMap::from([(1, 1), (3, 1)])
+    );
+    assert_eq!(solver.largest_cycle_size().await, 3);
+    assert_eq!(solver.smallest_cycle_size().await, 1);
+}
+
+#[test]
+async fn cycle_size_histogram_should_be_empty_when_done() {
+    let solver = Solver::new(HashMapProblem::from_edges([(
+        FragmentId(0),
+        FragmentId(1),
+    )]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    solver.assert_no_cycles().await;
+    assert!(solver.cycle_size_histogram().await.is_empty());
+    assert_eq!(solver.largest_cycle_size().await, 0);
+    assert_eq!(solver.smallest_cycle_size().await, 0);
+}
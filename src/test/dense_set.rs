@@ -0,0 +1,59 @@
+use crate::{
+    reexported::{test, DenseBitSet, Vec},
+    test::{PetgraphProblem, CONCURRENCY},
+    FragmentId, Solver,
+};
+use petgraph::Graph;
+
+#[test]
+async fn dense_bit_set_should_insert_and_remove() {
+    let mut set = DenseBitSet::new();
+
+    assert!(set.is_empty());
+    assert!(set.insert(FragmentId(3)));
+    assert!(set.insert(FragmentId(200)));
+    assert!(!set.insert(FragmentId(3)));
+    assert_eq!(set.len(), 2);
+    assert!(set.contains(&FragmentId(3)));
+    assert!(set.contains(&FragmentId(200)));
+    assert!(!set.contains(&FragmentId(4)));
+    assert!(!set.contains(&FragmentId(100_000)));
+
+    assert!(set.remove(&FragmentId(3)));
+    assert!(!set.remove(&FragmentId(3)));
+    assert!(!set.remove(&FragmentId(100_000)));
+    assert_eq!(set.len(), 1);
+    assert!(!set.contains(&FragmentId(3)));
+}
+
+#[test]
+async fn dense_bit_set_should_iterate_in_order() {
+    let mut set = DenseBitSet::new();
+    for id in [130, 0, 64, 63, 1] {
+        set.insert(FragmentId(id));
+    }
+
+    assert_eq!(
+        set.iter().collect::<Vec<_>>(),
+        [0, 1, 63, 64, 130].map(FragmentId),
+    );
+}
+
+#[test]
+async fn solver_should_work_with_dense_ids() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p2, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue_fragment(p0.index().into()).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    solver.assert_no_cycles().await;
+    solver
+        .assert_all_solved(&[FragmentId(0), FragmentId(1), FragmentId(2)])
+        .await;
+}
@@ -0,0 +1,75 @@
+use crate::{
+    reexported::{test, Box, Vec},
+    test::CONCURRENCY,
+    FragmentId, FragmentLifecycle, Problem, Solver,
+};
+use async_trait::async_trait;
+
+// Fragment 0 depends on 1 and 2. Fetching the dependencies of 2 fails
+struct FailingFetchProblem;
+
+#[async_trait]
+impl Problem for FailingFetchProblem {
+    type Error = FragmentId;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        match id.0 {
+            0 => dependencies.extend([FragmentId(1), FragmentId(2)]),
+            2 => return Err(id),
+            _ => (),
+        }
+
+        Ok(())
+    }
+
+    async fn evaluate(&self, _: FragmentId) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[test]
+async fn failed_dependency_fetches_should_fail_the_fragment() {
+    let solver = Solver::new(FailingFetchProblem);
+    solver.enqueue_fragment(FragmentId(2)).await;
+
+    assert_eq!(solver.step().await, Err(FragmentId(2)));
+    assert_eq!(
+        solver.fragment_state(FragmentId(2)).await,
+        FragmentLifecycle::Failed
+    );
+    assert_eq!(solver.evaluations_attempted(), 0);
+    assert_eq!(solver.fragment_dependency_list(FragmentId(2)).await, None);
+    assert!(!solver.step().await.unwrap());
+}
+
+#[test]
+async fn run_should_return_dependency_fetch_errors() {
+    let solver = Solver::new(FailingFetchProblem);
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    assert_eq!(solver.run(CONCURRENCY).await, Err(FragmentId(2)));
+    assert_eq!(
+        solver.fragment_state(FragmentId(2)).await,
+        FragmentLifecycle::Failed
+    );
+    assert!(!solver.is_evaluated(FragmentId(0)).await);
+}
+
+#[test]
+async fn failed_batch_fetches_should_be_retried_by_steps() {
+    let solver = Solver::new(FailingFetchProblem);
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.expand_all_dependencies(CONCURRENCY).await;
+
+    assert_eq!(
+        solver.fragment_dependency_list(FragmentId(0)).await,
+        Some(Vec::from([FragmentId(1), FragmentId(2)]))
+    );
+    assert_eq!(solver.fragment_dependency_list(FragmentId(2)).await, None);
+    assert_eq!(solver.run(CONCURRENCY).await, Err(FragmentId(2)));
+}
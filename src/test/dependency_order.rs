@@ -0,0 +1,70 @@
+use crate::{
+    problems::HashMapProblem,
+    reexported::{test, Box, Vec},
+    test::CONCURRENCY,
+    FragmentId, Problem, Solver,
+};
+use async_trait::async_trait;
+use core::{cmp::Reverse, convert::Infallible};
+
+// Prefers dependencies with higher IDs
+struct ReversedProblem(HashMapProblem);
+
+#[async_trait]
+impl Problem for ReversedProblem {
+    type Error = Infallible;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        self.0.direct_dependencies(id, dependencies).await
+    }
+
+    async fn preferred_dependencies_order(
+        &self,
+        _: FragmentId,
+        dependencies: &mut [FragmentId],
+    ) {
+        dependencies.sort_by_key(|&id| Reverse(id));
+    }
+
+    async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        self.0.evaluate(id).await
+    }
+}
+
+fn problem() -> HashMapProblem {
+    HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(0), FragmentId(2)),
+        (FragmentId(0), FragmentId(3)),
+    ])
+}
+
+#[test]
+async fn dependency_order_should_be_unchanged_by_default() {
+    let solver = Solver::new(problem());
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.step().await.unwrap();
+
+    assert_eq!(
+        solver.what_blocks(FragmentId(0)).await,
+        [FragmentId(1), FragmentId(2), FragmentId(3)],
+    );
+}
+
+#[test]
+async fn preferred_dependencies_order_should_reorder_dependencies() {
+    let solver = Solver::new(ReversedProblem(problem()));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.step().await.unwrap();
+
+    assert_eq!(
+        solver.what_blocks(FragmentId(0)).await,
+        [FragmentId(3), FragmentId(2), FragmentId(1)],
+    );
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+}
@@ -0,0 +1,57 @@
+use crate::{reexported::test, test::PetgraphProblem, Solver};
+use petgraph::Graph;
+
+fn three_node_chain() -> Solver<PetgraphProblem> {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p2, ());
+
+    Solver::new(PetgraphProblem::new(dependency_graph))
+}
+
+#[cfg(feature = "std")]
+#[test]
+async fn dump_state_should_match_snapshot() {
+    let solver = three_node_chain();
+    solver.enqueue_fragment(0.into()).await;
+    assert!(solver.step().await.unwrap());
+    assert!(solver.step().await.unwrap());
+
+    assert_eq!(
+        solver.dump_state().await,
+        "to_solve: [2]\n\
+         punted:\n  \
+           0 (pending on 1)\n  \
+           1 (pending on 1)\n\
+         solved: []\n\
+         pending_on:\n  \
+           1 <- [0]\n  \
+           2 <- [1]\n",
+    );
+
+    while solver.step().await.unwrap() {}
+
+    assert_eq!(
+        solver.dump_state().await,
+        "to_solve: []\npunted:\nsolved: [0, 1, 2]\npending_on:\n",
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+async fn dump_state_json_should_match_snapshot() {
+    let solver = three_node_chain();
+    solver.enqueue_fragment(0.into()).await;
+    assert!(solver.step().await.unwrap());
+
+    assert_eq!(
+        solver.dump_state_json().await,
+        concat!(
+            r#"{"to_solve":[1],"punted":[{"id":0,"pending":1}],"solved":[],"#,
+            r#""pending_on":[{"id":1,"dependents":[0]}]}"#,
+        ),
+    );
+}
@@ -0,0 +1,88 @@
+use crate::{
+    reexported::test,
+    test::{PetgraphProblem, CONCURRENCY},
+    FragmentId, Solver, Status,
+};
+use petgraph::Graph;
+
+#[test]
+async fn enqueue_if_not_solved_should_skip_solved_fragments() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    assert!(solver.enqueue_if_not_solved(p0.index().into()).await);
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert!(!solver.enqueue_if_not_solved(p0.index().into()).await);
+    solver.assert_no_cycles().await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+    assert_eq!(solver.into_problem_instance().into_evaluated(), &[p0]);
+}
+
+#[test]
+async fn enqueue_if_not_solved_should_enqueue_unsolved_fragments() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue_fragment(p0.index().into()).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert!(solver.enqueue_if_not_solved(p1.index().into()).await);
+    assert_eq!(solver.status().await, Status::Pending);
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+    assert_eq!(solver.into_problem_instance().into_evaluated(), &[p0, p1]);
+}
+
+#[test]
+async fn re_enqueuing_a_dependency_should_not_evaluate_it_again() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue_fragment(p0.index().into()).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    solver
+        .enqueue_fragment(p0.index().into())
+        .await
+        .enqueue_fragment(p1.index().into())
+        .await;
+    assert_eq!(solver.status().await, Status::Done);
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+    assert_eq!(solver.evaluations_attempted(), 2);
+    assert_eq!(solver.into_problem_instance().into_evaluated(), &[p1, p0]);
+}
+
+#[test]
+async fn try_enqueue_fragment_should_only_enqueue_new_fragments() {
+    use crate::problems::HashMapProblem;
+
+    // 0 depends on 1, which depends on 2
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(2)),
+    ]));
+
+    // New fragment
+    assert!(solver.try_enqueue_fragment(FragmentId(0)).await);
+    // Already queued
+    assert!(!solver.try_enqueue_fragment(FragmentId(0)).await);
+
+    // Punt 0 and 1, then evaluate 2
+    for _ in 0..3 {
+        assert!(solver.step().await.unwrap());
+    }
+    // Already punted
+    assert!(!solver.try_enqueue_fragment(FragmentId(0)).await);
+    // Already solved
+    assert!(!solver.try_enqueue_fragment(FragmentId(2)).await);
+
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+    solver.assert_no_cycles().await;
+    assert_eq!(solver.evaluations_attempted(), 3);
+}
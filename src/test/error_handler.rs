@@ -0,0 +1,178 @@
+use crate::{
+    reexported::{
+        atomic::{AtomicUsize, Ordering},
+        test, Box, Vec,
+    },
+    test::{GatedProblem, CONCURRENCY},
+    FragmentId, FragmentLifecycle, Problem, Solver, Status,
+};
+use async_trait::async_trait;
+use futures::future;
+
+// 4 depends on 0, which depends on 1 and 2. 3 has no dependencies. Fragments in `failing` fail
+fn failing_solver(failing: &[usize]) -> Solver<GatedProblem> {
+    Solver::new(
+        GatedProblem::from_edges([
+            (FragmentId(0), FragmentId(1)),
+            (FragmentId(0), FragmentId(2)),
+            (FragmentId(4), FragmentId(0)),
+        ])
+        .with_failing(failing),
+    )
+}
+
+#[test]
+async fn independent_fragments_should_be_evaluated_when_a_sibling_fails() {
+    let solver = failing_solver(&[1]);
+    solver.enqueue_fragment(FragmentId(4)).await;
+    solver.enqueue_fragment(FragmentId(3)).await;
+
+    let calls = AtomicUsize::new(0);
+    let report = solver
+        .run_with_error_handler(CONCURRENCY, |id, err| {
+            assert_eq!(id, FragmentId(1));
+            assert_eq!(err, FragmentId(1));
+            calls.fetch_add(1, Ordering::Relaxed);
+        })
+        .await;
+
+    assert_eq!(calls.load(Ordering::Relaxed), 1);
+    assert_eq!(report.status, Status::Done);
+    assert_eq!(report.failed, 1);
+    assert_eq!(report.blocked, 2);
+    assert!(solver.is_evaluated(FragmentId(2)).await);
+    assert!(solver.is_evaluated(FragmentId(3)).await);
+    assert_eq!(
+        solver.fragment_state(FragmentId(1)).await,
+        FragmentLifecycle::Failed,
+    );
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Blocked,
+    );
+    assert_eq!(
+        solver.fragment_state(FragmentId(4)).await,
+        FragmentLifecycle::Blocked,
+    );
+}
+
+#[test]
+async fn error_handler_should_be_called_once_per_failure() {
+    let solver = failing_solver(&[1, 2, 3]);
+    solver.enqueue_fragment(FragmentId(4)).await;
+    solver.enqueue_fragment(FragmentId(3)).await;
+
+    let calls = AtomicUsize::new(0);
+    let report = solver
+        .run_with_error_handler(CONCURRENCY, |_, _| {
+            calls.fetch_add(1, Ordering::Relaxed);
+        })
+        .await;
+
+    assert_eq!(calls.load(Ordering::Relaxed), 3);
+    assert_eq!(report.failed, 3);
+    assert_eq!(report.blocked, 2);
+    assert!(report.punted.is_empty());
+}
+
+#[test]
+async fn run_with_error_handler_should_match_run_without_errors() {
+    let solver = failing_solver(&[]);
+    solver.enqueue_fragment(FragmentId(4)).await;
+
+    let report = solver
+        .run_with_error_handler(CONCURRENCY, |_, _| unreachable!())
+        .await;
+
+    assert_eq!(report.status, Status::Done);
+    assert_eq!((report.failed, report.blocked), (0, 0));
+    solver
+        .assert_all_solved(&[
+            FragmentId(0),
+            FragmentId(1),
+            FragmentId(2),
+            FragmentId(4),
+        ])
+        .await;
+}
+
+// Fragments with no dependencies. Fragment 0 never finishes evaluating and all others fail
+#[derive(Default)]
+struct StuckProblem {
+    evaluations: AtomicUsize,
+}
+
+#[async_trait]
+impl Problem for StuckProblem {
+    type Error = FragmentId;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        _: FragmentId,
+        _: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        self.evaluations.fetch_add(1, Ordering::Relaxed);
+        if id == FragmentId(0) {
+            future::pending::<()>().await;
+        }
+
+        Err(id)
+    }
+}
+
+#[test]
+async fn run_until_first_error_should_stop_at_the_first_error() {
+    let solver = Solver::new(StuckProblem::default());
+    for id in 1..=20 {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    let (id, err) = solver.run_until_first_error(CONCURRENCY).await.unwrap();
+
+    assert_eq!(id, err);
+    assert_eq!(solver.fragment_state(id).await, FragmentLifecycle::Failed);
+    let evaluations =
+        solver.problem_instance.evaluations.load(Ordering::Relaxed);
+    assert!(evaluations <= CONCURRENCY.get());
+
+    // Handling errors instead evaluates every fragment
+    let report = solver.run_with_error_handler(CONCURRENCY, |_, _| ()).await;
+    assert_eq!(report.failed, 20);
+    assert_eq!(
+        solver.problem_instance.evaluations.load(Ordering::Relaxed),
+        20 + evaluations - 1
+    );
+}
+
+#[test]
+async fn run_until_first_error_should_cancel_and_requeue_other_evaluations() {
+    let solver = Solver::new(StuckProblem::default());
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(1)).await;
+
+    // 0 never finishes, so only returning early can end the run
+    assert_eq!(
+        solver.run_until_first_error(CONCURRENCY).await,
+        Some((FragmentId(1), FragmentId(1)))
+    );
+
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Queued
+    );
+    assert!(!solver.is_running());
+}
+
+#[test]
+async fn run_until_first_error_should_be_none_without_errors() {
+    let solver = failing_solver(&[]);
+    solver.enqueue_fragment(FragmentId(4)).await;
+
+    assert!(solver.run_until_first_error(CONCURRENCY).await.is_none());
+    assert_eq!(solver.status().await, Status::Done);
+}
@@ -0,0 +1,118 @@
+use crate::{
+    reexported::{
+        atomic::{AtomicUsize, Ordering},
+        test, Box, Vec,
+    },
+    test::{chain, CONCURRENCY},
+    FragmentId, FragmentLifecycle, Problem, Solver,
+};
+use async_trait::async_trait;
+
+const MAX_ATTEMPTS: usize = 3;
+
+// Fragments without dependencies whose evaluations fail until `failures` runs out
+struct TransientProblem {
+    failures: AtomicUsize,
+}
+
+#[async_trait]
+impl Problem for TransientProblem {
+    type Error = ();
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        _: FragmentId,
+        _: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn evaluate(&self, _: FragmentId) -> Result<(), Self::Error> {
+        self.failures
+            .fetch_update(Ordering::Relaxed, Ordering::Relaxed, |x| {
+                x.checked_sub(1)
+            })
+            .map_or(Ok(()), |_| Err(()))
+    }
+}
+
+// Retry policy: enqueue `id` again after each failure, up to `MAX_ATTEMPTS` times
+async fn solve_with_retries(failures: usize) -> Solver<TransientProblem> {
+    let solver = Solver::new(TransientProblem {
+        failures: AtomicUsize::new(failures),
+    });
+    for _ in 0..MAX_ATTEMPTS {
+        solver.enqueue_fragment(FragmentId(0)).await;
+        if solver.step().await.is_ok() {
+            break;
+        }
+    }
+
+    solver
+}
+
+#[test]
+async fn evaluate_count_for_should_count_successful_evaluations() {
+    let solver = chain(2);
+    let expected = usize::from(cfg!(debug_assertions));
+
+    assert_eq!(solver.evaluate_count_for(FragmentId(0)).await, 0);
+    let _ = solver
+        .solve_fragment(FragmentId(0), CONCURRENCY)
+        .await
+        .unwrap();
+
+    assert_eq!(solver.evaluate_count_for(FragmentId(0)).await, expected);
+    assert_eq!(solver.evaluate_count_for(FragmentId(1)).await, expected);
+    assert_eq!(solver.evaluate_count_for(FragmentId(2)).await, 0);
+}
+
+#[cfg(debug_assertions)]
+#[test]
+#[should_panic(expected = "fragment FragmentId(1) was evaluated 2 times")]
+async fn evaluating_a_fragment_twice_should_panic() {
+    let solver = chain(2);
+    let _ = solver
+        .solve_fragment(FragmentId(0), CONCURRENCY)
+        .await
+        .unwrap();
+
+    // Corrupt the state so the solver forgets `1` was solved and evaluates it again
+    solver.state.lock().await.solved.remove(&FragmentId(1));
+    solver.enqueue_fragment(FragmentId(1)).await;
+    let _ = solver.step().await;
+}
+
+#[test]
+async fn punting_should_not_count_as_an_evaluation_attempt() {
+    let solver = chain(2);
+    let _ = solver
+        .solve_fragment(FragmentId(0), CONCURRENCY)
+        .await
+        .unwrap();
+
+    assert_eq!(solver.fragment_evaluation_attempts(FragmentId(0)).await, 1);
+    assert_eq!(solver.fragment_evaluation_attempts(FragmentId(1)).await, 1);
+    assert_eq!(solver.fragment_evaluation_attempts(FragmentId(2)).await, 0);
+}
+
+#[test]
+async fn every_retry_should_count_as_an_evaluation_attempt() {
+    let solver = solve_with_retries(usize::MAX).await;
+    assert_eq!(
+        solver.fragment_evaluation_attempts(FragmentId(0)).await,
+        MAX_ATTEMPTS,
+    );
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Failed,
+    );
+
+    let solver = solve_with_retries(MAX_ATTEMPTS - 1).await;
+    assert_eq!(
+        solver.fragment_evaluation_attempts(FragmentId(0)).await,
+        MAX_ATTEMPTS,
+    );
+    assert!(solver.is_evaluated(FragmentId(0)).await);
+}
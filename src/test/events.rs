@@ -0,0 +1,102 @@
+use crate::{
+    problems::HashMapProblem,
+    reexported::{test, Vec},
+    test::CONCURRENCY,
+    FragmentId, Solver, SolverEvent, Status,
+};
+use core::mem;
+use futures::future;
+use std::collections::HashSet;
+
+#[test]
+async fn all_events_should_be_emitted_during_a_run_with_cycles() {
+    // 0 depends on 1 and 3. 1 and 2 depend on each other
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(0), FragmentId(3)),
+        (FragmentId(1), FragmentId(2)),
+        (FragmentId(2), FragmentId(1)),
+    ]));
+    let mut receiver = solver.subscribe_events();
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    let mut events = Vec::new();
+    while let Ok(event) = receiver.try_recv() {
+        events.push(event);
+    }
+
+    assert_eq!(
+        events.first(),
+        Some(&SolverEvent::FragmentQueued { id: FragmentId(0) })
+    );
+    assert_eq!(events[1], SolverEvent::SolverStarted);
+    assert!(events.contains(&SolverEvent::DependenciesFetched {
+        id: FragmentId(0),
+        dep_count: 2,
+    }));
+    assert!(
+        events.contains(&SolverEvent::FragmentEvaluating { id: FragmentId(3) })
+    );
+    assert!(events.iter().any(|event| matches!(
+        event,
+        SolverEvent::FragmentSolved {
+            id: FragmentId(3),
+            ..
+        },
+    )));
+    assert!(events.contains(&SolverEvent::FragmentPunted {
+        id: FragmentId(0),
+        pending_deps: 2,
+    }));
+    assert!(events.contains(&SolverEvent::CycleFinalized {
+        members: Vec::from([FragmentId(0), FragmentId(1), FragmentId(2)]),
+    }));
+    match events.last() {
+        Some(SolverEvent::SolverFinished { status, report }) => {
+            assert_eq!(*status, Status::DoneWithCycles);
+            assert_eq!(report.status, Status::DoneWithCycles);
+        }
+        event => panic!("unexpected last event: {:?}", event),
+    }
+
+    // Every variant was seen
+    let variants = events.iter().map(mem::discriminant).collect::<HashSet<_>>();
+    assert_eq!(variants.len(), 8);
+}
+
+#[test]
+async fn events_should_not_be_sent_to_late_subscribers() {
+    let solver = Solver::new(HashMapProblem::default());
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert!(solver.subscribe_events().try_recv().is_err());
+}
+
+#[test]
+async fn pipe_into_should_enqueue_mapped_fragments_into_the_other_solver() {
+    let first = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(0), FragmentId(2)),
+    ]));
+    let second = Solver::new(HashMapProblem::from_edges([(
+        FragmentId(100),
+        FragmentId(101),
+    )]));
+    let pipe = first.pipe_into(&second, |id| FragmentId(id.0 + 100)).await;
+    first.assume_evaluated(FragmentId(2)).await;
+    first.enqueue_fragment(FragmentId(0)).await;
+
+    let (punted, ()) = future::join(first.run(CONCURRENCY), pipe).await;
+    assert!(punted.unwrap().punted.is_empty());
+
+    // Only evaluated fragments are forwarded, not assumed ones
+    assert!(second.can_make_progress().await);
+    assert!(second.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert_eq!(second.evaluations_attempted(), 2);
+    second
+        .assert_all_solved(&[FragmentId(100), FragmentId(101)])
+        .await;
+    assert!(!second.is_evaluated(FragmentId(102)).await);
+}
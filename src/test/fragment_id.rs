@@ -0,0 +1,97 @@
+use crate::{
+    reexported::{test, Vec},
+    FragmentId, FragmentIdNamespaceError,
+};
+
+#[test]
+async fn zero_and_max_should_be_the_smallest_and_largest_ids() {
+    assert_eq!(FragmentId::ZERO, FragmentId(0));
+    assert_eq!(FragmentId::MAX, FragmentId(usize::MAX));
+}
+
+#[test]
+async fn range_should_yield_consecutive_ids() {
+    let ids = FragmentId::range(FragmentId::ZERO, FragmentId(1000))
+        .collect::<Vec<_>>();
+
+    assert_eq!(ids.len(), 1000);
+    for (i, id) in ids.into_iter().enumerate() {
+        assert_eq!(id, FragmentId(i));
+    }
+}
+
+#[test]
+async fn range_should_be_double_ended_and_exact_size() {
+    let mut range = FragmentId::range(FragmentId(5), FragmentId(8));
+
+    assert_eq!(range.len(), 3);
+    assert_eq!(range.next_back(), Some(FragmentId(7)));
+    assert_eq!(range.next(), Some(FragmentId(5)));
+    assert_eq!(range.next(), Some(FragmentId(6)));
+    assert_eq!(range.next(), None);
+}
+
+#[test]
+async fn empty_ranges_should_yield_nothing() {
+    assert_eq!(FragmentId::range(FragmentId(3), FragmentId(3)).next(), None);
+    assert_eq!(FragmentId::range(FragmentId(3), FragmentId(1)).next(), None);
+    assert_eq!(
+        FragmentId::range(FragmentId::MAX, FragmentId::MAX).next(),
+        None,
+    );
+}
+
+#[cfg(feature = "nightly")]
+#[test]
+async fn native_ranges_should_match_range() {
+    assert!((FragmentId(10)..FragmentId(1010))
+        .eq(FragmentId::range(FragmentId(10), FragmentId(1010))));
+}
+
+crate::declare_fragment_id!(TypeId);
+crate::declare_fragment_id!(ValueId, namespace = 1);
+crate::declare_fragment_id!(ModuleId, namespace = 255);
+
+#[test]
+async fn declared_ids_should_round_trip_through_fragment_ids() {
+    assert_eq!(FragmentId::from(TypeId(7)), FragmentId(7));
+    assert_eq!(TypeId::try_from(FragmentId(7)), Ok(TypeId(7)));
+
+    let id = FragmentId::from(ValueId(7));
+    assert_eq!(id.namespace(), 1);
+    assert_eq!(id.local_id(), 7);
+    assert_eq!(ValueId::try_from(id), Ok(ValueId(7)));
+
+    let id = FragmentId::from(ModuleId(0));
+    assert_eq!(id.namespace(), 255);
+    assert_eq!(ModuleId::try_from(id), Ok(ModuleId(0)));
+}
+
+#[test]
+async fn declared_ids_should_not_convert_across_namespaces() {
+    let value = FragmentId::from(ValueId(7));
+    let module = FragmentId::from(ModuleId(7));
+
+    assert_ne!(value, FragmentId::from(TypeId(7)));
+    assert_ne!(value, module);
+    assert_eq!(
+        TypeId::try_from(value),
+        Err(FragmentIdNamespaceError {
+            expected: 0,
+            found: 1,
+        })
+    );
+    assert_eq!(
+        ValueId::try_from(module),
+        Err(FragmentIdNamespaceError {
+            expected: 1,
+            found: 255,
+        })
+    );
+}
+
+#[test]
+#[should_panic(expected = "overlaps the namespace bits")]
+async fn local_ids_overlapping_the_namespace_should_panic() {
+    let _ = FragmentId::from(ValueId(usize::MAX));
+}
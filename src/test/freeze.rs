@@ -0,0 +1,88 @@
+use crate::{
+    problems::HashMapProblem,
+    reexported::{test, Vec},
+    test::GatedProblem,
+    FragmentId, FragmentLifecycle, FrozenSolverState, Solver, Status,
+};
+use futures::{future, pin_mut};
+
+fn assert_send_sync<T: Send + Sync>() {}
+
+#[test]
+async fn frozen_state_should_be_consistent_at_every_step() {
+    assert_send_sync::<FrozenSolverState>();
+
+    // 0 -> 1 -> 2 is solvable, 3 <-> 4 is a cycle
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(2)),
+        (FragmentId(3), FragmentId(4)),
+        (FragmentId(4), FragmentId(3)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(3)).await;
+
+    loop {
+        let frozen = solver.freeze().await;
+
+        // Every fragment is counted in exactly one state
+        let by_state = frozen.fragment_ids_by_state();
+        let mut union =
+            by_state.values().flatten().copied().collect::<Vec<_>>();
+        union.sort_unstable();
+        assert_eq!(union, frozen.fragment_ids());
+        assert_eq!(frozen.detailed_summary().total(), frozen.fragment_count());
+
+        assert_eq!(by_state, solver.fragment_ids_by_state().await);
+        assert_eq!(frozen.status(), solver.status().await);
+        assert_eq!(frozen.summary(), solver.summary().await);
+        assert_eq!(frozen.punted_iter(), solver.punted_iter().await);
+        for id in frozen.fragment_ids() {
+            assert_eq!(frozen.is_evaluated(id), solver.is_evaluated(id).await);
+        }
+
+        if !solver.step().await.unwrap() {
+            break;
+        }
+    }
+
+    let frozen = solver.freeze().await;
+    assert_eq!(frozen.status(), Status::DoneWithCycles);
+    assert!(frozen.is_evaluated(FragmentId(2)));
+    assert!(frozen.is_punted(FragmentId(3)));
+    assert!(!frozen.is_punted(FragmentId(0)));
+    assert_eq!(
+        frozen.fragment_state(FragmentId(5)),
+        FragmentLifecycle::Unknown
+    );
+}
+
+#[test]
+async fn freeze_should_not_block_concurrent_steps() {
+    let solver = Solver::new(GatedProblem::default());
+    let open_gate = solver.problem_instance.gate(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    let step = solver.step();
+    pin_mut!(step);
+    assert!(future::poll_immediate(&mut step).await.is_none());
+
+    let frozen = solver.freeze().await;
+    assert_eq!(
+        frozen.fragment_state(FragmentId(0)),
+        FragmentLifecycle::InFlight
+    );
+
+    open_gate.send(()).unwrap();
+    assert!(step.await.unwrap());
+
+    // The snapshot doesn't follow the solver
+    assert_eq!(
+        frozen.fragment_state(FragmentId(0)),
+        FragmentLifecycle::InFlight
+    );
+    assert_eq!(
+        solver.freeze().await.fragment_state(FragmentId(0)),
+        FragmentLifecycle::Evaluated,
+    );
+}
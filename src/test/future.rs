@@ -0,0 +1,131 @@
+use crate::{
+    reexported::{NonZeroUsize, Vec},
+    test::{GatedProblem, PetgraphProblem, CONCURRENCY},
+    FragmentId, Solver, SolverConfig, Status,
+};
+use futures::{executor::block_on, future, FutureExt};
+use petgraph::Graph;
+
+fn assert_send<T: Send>(_: &T) {}
+
+#[test]
+fn solving_should_run_a_chain_to_completion() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p2, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    let report = block_on(solver.solving(&[p0.index().into()])).unwrap();
+
+    assert_eq!(report.status, Status::Done);
+    assert!(report.punted.is_empty());
+}
+
+#[test]
+fn solving_should_report_cycles() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p0, ());
+
+    let solver = Solver::with_config(
+        PetgraphProblem::new(dependency_graph),
+        SolverConfig {
+            concurrency: CONCURRENCY,
+            ..SolverConfig::default()
+        },
+    );
+    let mut report = block_on(solver.solving(&[p0.index().into()])).unwrap();
+    report.punted.sort();
+
+    assert_eq!(report.status, Status::DoneWithCycles);
+    assert_eq!(report.punted, &[p0.index().into(), p1.index().into()]);
+}
+
+#[test]
+fn solving_with_no_roots_should_be_done() {
+    let solver = Solver::new(PetgraphProblem::new(Graph::new()));
+    let report = block_on(solver.solving(&[])).unwrap();
+
+    assert_eq!(report.status, Status::Done);
+    assert!(report.punted.is_empty());
+}
+
+#[test]
+fn dropping_a_run_handle_should_stop_new_evaluations() {
+    let solver = Solver::new(GatedProblem::default());
+    let mut gates = Vec::new();
+    for id in 0..3 {
+        gates.push(block_on(solver.problem_instance.gate(FragmentId(id))));
+        block_on(solver.enqueue_fragment(FragmentId(id)));
+    }
+
+    let mut handle = solver.run(NonZeroUsize::MIN);
+    assert_send(&handle);
+    assert!((&mut handle).now_or_never().is_none());
+    assert!(solver.is_running());
+    drop(handle);
+
+    assert!(!solver.is_running());
+    assert_eq!(solver.problem_instance.evaluations(), 1);
+    assert_eq!(block_on(solver.status()), Status::Pending);
+    for id in 0..3 {
+        assert!(!block_on(solver.is_evaluated(FragmentId(id))));
+    }
+}
+
+#[test]
+fn solver_should_be_runnable_after_dropping_a_run_handle() {
+    let solver = Solver::new(GatedProblem::default());
+    let mut gates = Vec::new();
+    for id in 0..3 {
+        gates.push(block_on(solver.problem_instance.gate(FragmentId(id))));
+        block_on(solver.enqueue_fragment(FragmentId(id)));
+    }
+
+    let mut handle = solver.run(CONCURRENCY);
+    assert!((&mut handle).now_or_never().is_none());
+    drop(handle);
+
+    drop(gates);
+    assert!(block_on(solver.run(CONCURRENCY)).unwrap().punted.is_empty());
+
+    assert_eq!(block_on(solver.status()), Status::Done);
+    for id in 0..3 {
+        assert!(block_on(solver.is_evaluated(FragmentId(id))));
+    }
+    // The evaluations dropped with the first run are started again
+    assert_eq!(solver.problem_instance.evaluations(), CONCURRENCY.get() + 3);
+}
+
+#[test]
+fn dropping_a_run_handle_waiting_for_another_run_should_not_affect_it() {
+    block_on(async {
+        let solver = Solver::new(GatedProblem::default());
+        let open_0 = solver.problem_instance.gate(FragmentId(0)).await;
+        let open_1 = solver.problem_instance.gate(FragmentId(1)).await;
+        for id in 0..3 {
+            solver.enqueue_fragment(FragmentId(id)).await;
+        }
+
+        let mut running = solver.run(CONCURRENCY);
+        assert!(future::poll_immediate(&mut running).await.is_none());
+        let mut waiting = solver.run(CONCURRENCY);
+        assert!(future::poll_immediate(&mut waiting).await.is_none());
+        drop(waiting);
+        assert!(solver.is_running());
+
+        // Fragment 2 is taken while fragment 1 is still being evaluated
+        open_0.send(()).unwrap();
+        assert!(future::poll_immediate(&mut running).await.is_none());
+        open_1.send(()).unwrap();
+        assert!(running.await.unwrap().punted.is_empty());
+
+        assert_eq!(solver.status().await, Status::Done);
+        assert_eq!(solver.problem_instance.evaluations(), 3);
+    });
+}
@@ -0,0 +1,127 @@
+use crate::{
+    problems::HashMapProblem,
+    reexported::{test, Vec},
+    test::CONCURRENCY,
+    FragmentId, Solver,
+};
+
+#[test]
+async fn diamond_generations_should_follow_dependency_depth() {
+    let (a, b, c, d) =
+        (FragmentId(0), FragmentId(1), FragmentId(2), FragmentId(3));
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (a, b),
+        (a, c),
+        (b, d),
+        (c, d),
+    ]));
+    solver.enqueue_fragment(a).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(solver.fragment_generation(d).await, Some(0));
+    assert_eq!(solver.fragment_generation(b).await, Some(1));
+    assert_eq!(solver.fragment_generation(c).await, Some(1));
+    assert_eq!(solver.fragment_generation(a).await, Some(2));
+    assert_eq!(solver.max_generation().await, 2);
+}
+
+#[test]
+async fn unknown_and_cyclic_fragments_should_have_no_generation() {
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(0)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(solver.fragment_generation(FragmentId(0)).await, None);
+    assert_eq!(solver.fragment_generation(FragmentId(1)).await, None);
+    assert_eq!(solver.fragment_generation(FragmentId(2)).await, None);
+    assert_eq!(solver.max_generation().await, 0);
+}
+
+#[test]
+async fn diamond_levels_should_group_independent_fragments() {
+    let (a, b, c, d) =
+        (FragmentId(0), FragmentId(1), FragmentId(2), FragmentId(3));
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (a, b),
+        (a, c),
+        (b, d),
+        (c, d),
+    ]));
+    solver.enqueue_fragment(a).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(
+        solver.topological_levels().await,
+        [Vec::from([d]), Vec::from([b, c]), Vec::from([a])]
+    );
+    assert_eq!(solver.level_of(d).await, Some(0));
+    assert_eq!(solver.level_of(b).await, Some(1));
+    assert_eq!(solver.level_of(c).await, Some(1));
+    assert_eq!(solver.level_of(a).await, Some(2));
+    assert_eq!(solver.level_of(FragmentId(4)).await, None);
+}
+
+#[test]
+async fn forest_levels_should_merge_all_trees() {
+    // 0 depends on 1 and 2, 3 depends on 4, which depends on 5
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(0), FragmentId(2)),
+        (FragmentId(3), FragmentId(4)),
+        (FragmentId(4), FragmentId(5)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(3)).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(
+        solver.topological_levels().await,
+        [
+            Vec::from([FragmentId(1), FragmentId(2), FragmentId(5)]),
+            Vec::from([FragmentId(0), FragmentId(4)]),
+            Vec::from([FragmentId(3)]),
+        ]
+    );
+}
+
+#[test]
+async fn chain_levels_should_hold_one_fragment_each() {
+    // 0 depends on 1, which depends on 2, and so on until 4
+    let solver = Solver::new(HashMapProblem::from_edges(
+        (0..4).map(|id| (FragmentId(id), FragmentId(id + 1))),
+    ));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.expand_all_dependencies(CONCURRENCY).await;
+
+    // Levels don't need anything to be evaluated
+    assert_eq!(solver.evaluations_attempted(), 0);
+    let levels = solver.topological_levels().await;
+    assert_eq!(levels.len(), 5);
+    for id in 0..5 {
+        assert_eq!(levels[4 - id], [FragmentId(id)]);
+        assert_eq!(solver.level_of(FragmentId(id)).await, Some(4 - id));
+    }
+}
+
+#[test]
+async fn levels_should_exclude_cycles_and_their_dependents() {
+    // 0 depends on 1 and 3, 1 and 2 depend on each other
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(0), FragmentId(3)),
+        (FragmentId(1), FragmentId(2)),
+        (FragmentId(2), FragmentId(1)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(!solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(
+        solver.topological_levels().await,
+        [Vec::from([FragmentId(3)])]
+    );
+    assert_eq!(solver.level_of(FragmentId(0)).await, None);
+    assert_eq!(solver.level_of(FragmentId(1)).await, None);
+}
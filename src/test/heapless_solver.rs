@@ -0,0 +1,155 @@
+use crate::{
+    reexported::convert::Infallible, FragmentId, HeaplessProblem,
+    HeaplessSolver, SolverError, Status,
+};
+use core::{
+    cell::RefCell,
+    future::Future,
+    pin::pin,
+    task::{Context, Poll},
+};
+use futures::task::noop_waker_ref;
+use heapless::Vec;
+
+const MAX_FRAGMENTS: usize = 16;
+
+// Drive a future by polling it in a loop, as a bare-metal target without an executor would
+fn block_on<F>(future: F) -> F::Output
+where
+    F: Future,
+{
+    let mut future = pin!(future);
+    let mut cx = Context::from_waker(noop_waker_ref());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut cx) {
+            return output;
+        }
+    }
+}
+
+// Fragments `0..len`, each depending on the next one, with an optional edge from the last
+// fragment back to the first. Records the evaluation order
+struct ChainProblem {
+    len: usize,
+    cyclic: bool,
+    evaluated: RefCell<Vec<FragmentId, MAX_FRAGMENTS>>,
+}
+
+impl ChainProblem {
+    fn new(len: usize, cyclic: bool) -> Self {
+        Self {
+            len,
+            cyclic,
+            evaluated: RefCell::new(Vec::new()),
+        }
+    }
+}
+
+impl<const N: usize> HeaplessProblem<N> for ChainProblem {
+    type Error = Infallible;
+
+    async fn direct_dependencies(&self, id: FragmentId) -> Vec<FragmentId, N> {
+        let mut dependencies = Vec::new();
+        if id.0 + 1 < self.len {
+            dependencies.push(FragmentId(id.0 + 1)).unwrap();
+        } else if self.cyclic {
+            dependencies.push(FragmentId(0)).unwrap();
+        }
+
+        dependencies
+    }
+
+    async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        self.evaluated.borrow_mut().push(id).unwrap();
+
+        Ok(())
+    }
+}
+
+#[test]
+fn heapless_solver_should_solve_a_three_node_chain() {
+    let mut solver =
+        HeaplessSolver::<_, MAX_FRAGMENTS>::new(ChainProblem::new(3, false));
+
+    assert_eq!(solver.enqueue_fragment(FragmentId(0)), Ok(true));
+    assert_eq!(solver.status(), Status::Pending);
+    assert_eq!(block_on(solver.run()), Ok(Status::Done));
+
+    assert!(solver.is_evaluated(FragmentId(1)));
+    assert_eq!(solver.enqueue_fragment(FragmentId(0)), Ok(false));
+    assert_eq!(
+        solver.into_problem_instance().evaluated.into_inner(),
+        [FragmentId(2), FragmentId(1), FragmentId(0)]
+    );
+}
+
+#[test]
+fn heapless_solver_should_punt_cycles() {
+    let mut solver =
+        HeaplessSolver::<_, MAX_FRAGMENTS>::new(ChainProblem::new(3, true));
+
+    solver.enqueue_fragment(FragmentId(0)).unwrap();
+
+    assert_eq!(block_on(solver.run()), Ok(Status::DoneWithCycles));
+    let mut punted = solver.punted_iter().collect::<Vec<_, MAX_FRAGMENTS>>();
+    punted.sort_unstable();
+    assert_eq!(punted, [FragmentId(0), FragmentId(1), FragmentId(2)]);
+    assert!(solver.problem_instance().evaluated.borrow().is_empty());
+}
+
+#[test]
+fn heapless_solver_should_report_exceeded_capacity() {
+    let mut solver = HeaplessSolver::<_, 2>::new(ChainProblem::new(4, false));
+
+    solver.enqueue_fragment(FragmentId(0)).unwrap();
+
+    // Punting the third fragment needs room for three punted fragments
+    assert_eq!(block_on(solver.run()), Err(SolverError::CapacityExceeded));
+}
+
+// Fragment 0 depends on itself, fragment 1 depends on 2 to 5, and nothing else has dependencies
+struct SelfDependencyProblem;
+
+impl<const N: usize> HeaplessProblem<N> for SelfDependencyProblem {
+    type Error = Infallible;
+
+    async fn direct_dependencies(&self, id: FragmentId) -> Vec<FragmentId, N> {
+        let mut dependencies = Vec::new();
+        match id {
+            FragmentId(0) => dependencies.push(id).unwrap(),
+            FragmentId(1) => {
+                for dependency in 2..6 {
+                    dependencies.push(FragmentId(dependency)).unwrap();
+                }
+            }
+            _ => (),
+        }
+
+        dependencies
+    }
+
+    async fn evaluate(&self, _: FragmentId) -> Result<(), Self::Error> {
+        Ok(())
+    }
+}
+
+#[test]
+fn heapless_solver_should_not_register_self_dependencies() {
+    let mut solver = HeaplessSolver::<_, 4>::new(SelfDependencyProblem);
+
+    // Enqueuing a fragment that is already queued takes no room
+    for _ in 0..8 {
+        solver.enqueue_fragment(FragmentId(0)).unwrap();
+    }
+    assert_eq!(block_on(solver.step()), Ok(true));
+    assert_eq!(block_on(solver.step()), Ok(false));
+
+    // Fragment 1 waits on four fragments, which only fits if 0 is not waiting on itself
+    solver.enqueue_fragment(FragmentId(1)).unwrap();
+    assert_eq!(block_on(solver.step()), Ok(true));
+    for _ in 0..4 {
+        assert_eq!(block_on(solver.step()), Ok(true));
+    }
+    assert!(solver.is_evaluated(FragmentId(5)));
+    assert_eq!(solver.punted_iter().collect::<Vec<_, 4>>(), [FragmentId(0)]);
+}
@@ -0,0 +1,150 @@
+use crate::{
+    problems::{box_dependencies, box_evaluation, ClosureProblem},
+    reexported::{
+        atomic::{AtomicUsize, Ordering},
+        test, Arc, Mutex, NonZeroUsize, Vec,
+    },
+    test::{PetgraphProblem, CONCURRENCY},
+    FragmentId, Solver,
+};
+use petgraph::Graph;
+
+#[test]
+async fn hooks_should_fire_exactly_once_per_evaluation() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p0, p2, ());
+
+    let pre = Arc::new(AtomicUsize::new(0));
+    let post = Arc::new(AtomicUsize::new(0));
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph))
+        .with_pre_hook({
+            let pre = pre.clone();
+            move |_| {
+                pre.fetch_add(1, Ordering::SeqCst);
+            }
+        })
+        .with_post_hook({
+            let post = post.clone();
+            move |_, success| {
+                assert!(success);
+                post.fetch_add(1, Ordering::SeqCst);
+            }
+        });
+    solver.enqueue_fragment(p0.index().into()).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(pre.load(Ordering::SeqCst), 3);
+    assert_eq!(post.load(Ordering::SeqCst), 3);
+}
+
+#[test]
+async fn post_hook_should_report_failed_evaluations() {
+    let outcomes = Arc::new(Mutex::new(Vec::new()));
+
+    let solver = Solver::new(ClosureProblem::new(
+        |_| box_dependencies(async { Vec::new() }),
+        |_| box_evaluation(async { Err("evaluation failed".into()) }),
+    ))
+    .with_post_hook({
+        let outcomes = outcomes.clone();
+        move |id, success| {
+            outcomes.try_lock().unwrap().push((id, success));
+        }
+    });
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.run(CONCURRENCY).await.is_err());
+
+    assert_eq!(*outcomes.lock().await, &[(FragmentId(0), false)]);
+}
+
+#[test]
+async fn chained_hooks_should_all_fire_in_order() {
+    let calls = Arc::new(Mutex::new(Vec::new()));
+
+    let solver = Solver::new(PetgraphProblem::new({
+        let mut dependency_graph = Graph::new();
+        dependency_graph.add_node(());
+        dependency_graph
+    }))
+    .with_pre_hook({
+        let calls = calls.clone();
+        move |_| calls.try_lock().unwrap().push(0)
+    })
+    .with_pre_hook({
+        let calls = calls.clone();
+        move |_| calls.try_lock().unwrap().push(1)
+    });
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(*calls.lock().await, &[0, 1]);
+}
+
+#[test]
+async fn solved_and_punted_hooks_should_fire_in_order_on_a_chain() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p2, ());
+
+    let calls = Arc::new(Mutex::new(Vec::new()));
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph))
+        .with_solved_hook({
+            let calls = calls.clone();
+            move |id| calls.try_lock().unwrap().push(("solved", id))
+        })
+        .with_punted_hook({
+            let calls = calls.clone();
+            move |id| calls.try_lock().unwrap().push(("punted", id))
+        });
+    solver.enqueue_fragment(p0.index().into()).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(
+        *calls.lock().await,
+        &[
+            ("punted", p0.index().into()),
+            ("punted", p1.index().into()),
+            ("solved", p2.index().into()),
+            ("solved", p1.index().into()),
+            ("solved", p0.index().into()),
+        ],
+    );
+}
+
+#[test]
+async fn solved_hook_should_see_the_fragment_as_evaluated() {
+    let solver = Arc::new(Mutex::new(None::<Arc<Solver<PetgraphProblem>>>));
+    let seen = Arc::new(AtomicUsize::new(0));
+    let inner = Arc::new(
+        Solver::new(PetgraphProblem::new({
+            let mut dependency_graph = Graph::new();
+            dependency_graph.add_node(());
+            dependency_graph
+        }))
+        .with_solved_hook({
+            let solver = solver.clone();
+            let seen = seen.clone();
+            move |id| {
+                let solver = solver.try_lock().unwrap().clone().unwrap();
+                // The state lock must not be held
+                let state = solver.state.try_lock();
+                assert!(state.unwrap().solved.contains(&id));
+                seen.fetch_add(1, Ordering::SeqCst);
+            }
+        }),
+    );
+    *solver.lock().await = Some(inner.clone());
+    inner.enqueue_fragment(FragmentId(0)).await;
+    let _ = inner.run(NonZeroUsize::MIN).await.unwrap();
+
+    assert_eq!(seen.load(Ordering::SeqCst), 1);
+    // Break the reference cycle
+    solver.lock().await.take();
+}
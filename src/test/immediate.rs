@@ -0,0 +1,199 @@
+use crate::{
+    problems::{box_dependencies, box_evaluation, ClosureProblem},
+    reexported::{test, Vec},
+    test::{PetgraphProblem, CONCURRENCY},
+    FragmentId, FragmentLifecycle, ImmediateOutcome, Problem, Solver, Status,
+};
+use petgraph::{graph::NodeIndex, Graph};
+
+// 0 depends on both 1 and 2
+fn fork_solver() -> Solver<PetgraphProblem> {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p0, p2, ());
+
+    Solver::new(PetgraphProblem::new(dependency_graph))
+}
+
+fn node_indices(ids: &[usize]) -> Vec<NodeIndex<u32>> {
+    ids.iter().copied().map(NodeIndex::new).collect()
+}
+
+#[test]
+async fn evaluating_immediately_should_not_evaluate_twice() {
+    let solver = fork_solver();
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.step().await.unwrap());
+    assert_eq!(
+        solver.fragment_state(FragmentId(1)).await,
+        FragmentLifecycle::Queued
+    );
+
+    assert_eq!(
+        solver.evaluate_immediately(FragmentId(1)).await,
+        Ok(ImmediateOutcome::Evaluated),
+    );
+    assert_eq!(
+        solver.evaluate_immediately(FragmentId(1)).await,
+        Ok(ImmediateOutcome::AlreadyHandled),
+    );
+    assert_eq!(
+        solver.fragment_state(FragmentId(1)).await,
+        FragmentLifecycle::Evaluated,
+    );
+
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert_eq!(
+        solver.evaluate_immediately(FragmentId(0)).await,
+        Ok(ImmediateOutcome::AlreadyHandled),
+    );
+    assert_eq!(
+        solver.into_problem_instance().into_evaluated(),
+        node_indices(&[1, 2, 0]),
+    );
+}
+
+#[test]
+async fn evaluating_immediately_should_unblock_dependents() {
+    let solver = fork_solver();
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.step().await.unwrap());
+
+    assert_eq!(
+        solver.evaluate_immediately(FragmentId(2)).await,
+        Ok(ImmediateOutcome::Evaluated),
+    );
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Punted { pending_count: 1 },
+    );
+    assert_eq!(
+        solver.evaluate_immediately(FragmentId(1)).await,
+        Ok(ImmediateOutcome::Evaluated),
+    );
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Queued
+    );
+
+    assert!(solver.step().await.unwrap());
+    assert!(!solver.step().await.unwrap());
+    assert_eq!(solver.status().await, Status::Done);
+    assert_eq!(
+        solver.into_problem_instance().into_evaluated(),
+        node_indices(&[2, 1, 0]),
+    );
+}
+
+#[test]
+async fn evaluating_a_punted_fragment_immediately_should_unpunt_it() {
+    let solver = fork_solver();
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.step().await.unwrap());
+
+    assert_eq!(
+        solver.evaluate_immediately(FragmentId(0)).await,
+        Ok(ImmediateOutcome::EvaluatedEarly { pending_count: 2 }),
+    );
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Evaluated,
+    );
+
+    // Solving the dependencies later must not queue 0 again
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert_eq!(solver.status().await, Status::Done);
+    assert_eq!(
+        solver.into_problem_instance().into_evaluated(),
+        node_indices(&[0, 1, 2]),
+    );
+}
+
+#[test]
+async fn evaluating_an_unknown_fragment_immediately_should_do_nothing() {
+    let solver = fork_solver();
+
+    // The dependencies of 0 were never fetched
+    assert_eq!(
+        solver.evaluate_immediately(FragmentId(0)).await,
+        Ok(ImmediateOutcome::Unknown),
+    );
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Unknown,
+    );
+    assert_eq!(solver.evaluations_attempted(), 0);
+
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert_eq!(
+        solver.into_problem_instance().into_evaluated(),
+        node_indices(&[1, 2, 0]),
+    );
+}
+
+// 0 depends on 1, which always fails. Returns once 0 is blocked
+async fn blocked_solver() -> Solver<impl Problem> {
+    let solver = Solver::new(ClosureProblem::new(
+        |id| {
+            box_dependencies(async move {
+                match id {
+                    FragmentId(0) => Vec::from([FragmentId(1)]),
+                    _ => Vec::new(),
+                }
+            })
+        },
+        |_| box_evaluation(async { Err("evaluation failed".into()) }),
+    ));
+    solver.enqueue_fragment(FragmentId(1)).await;
+    assert!(solver.step().await.is_err());
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.step().await.unwrap());
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Blocked,
+    );
+
+    solver
+}
+
+#[test]
+async fn evaluating_immediately_should_skip_blocked_fragments() {
+    let solver = blocked_solver().await;
+
+    assert!(matches!(
+        solver.evaluate_immediately(FragmentId(0)).await,
+        Ok(ImmediateOutcome::Blocked),
+    ));
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Blocked,
+    );
+    assert!(!solver.is_evaluated(FragmentId(0)).await);
+    assert_eq!(solver.detailed_summary().await.total(), 2);
+}
+
+#[test]
+async fn assuming_blocked_fragments_should_unblock_them() {
+    let solver = blocked_solver().await;
+
+    solver.assume_evaluated(FragmentId(0)).await;
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Skipped,
+    );
+    let summary = solver.detailed_summary().await;
+    assert_eq!(summary.total(), 2);
+    assert_eq!(summary.blocked, 0);
+
+    let solver = blocked_solver().await;
+    assert_eq!(solver.mark_all_solved([FragmentId(0)]).await, 1);
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Skipped,
+    );
+    assert_eq!(solver.detailed_summary().await.total(), 2);
+}
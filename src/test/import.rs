@@ -0,0 +1,62 @@
+use crate::{
+    problems::HashMapProblem, reexported::test, test::CONCURRENCY, FragmentId,
+    FragmentLifecycle, Solver,
+};
+
+#[test]
+async fn importing_a_dependency_should_unblock_punted_fragments() {
+    // Solves 1 and 2 on behalf of `solver`
+    let other = Solver::new(HashMapProblem::from_edges([(
+        FragmentId(1),
+        FragmentId(2),
+    )]));
+    other.enqueue_fragment(FragmentId(1)).await;
+    assert!(other.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(0), FragmentId(2)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.step().await.unwrap();
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Punted { pending_count: 2 },
+    );
+
+    assert_eq!(
+        solver.import_solved(other.topological_order().await).await,
+        1
+    );
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Queued,
+    );
+    assert_eq!(
+        solver.fragment_state(FragmentId(1)).await,
+        FragmentLifecycle::Skipped,
+    );
+
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Evaluated,
+    );
+    assert_eq!(solver.evaluations_attempted(), 1);
+}
+
+#[test]
+async fn importing_solved_fragments_should_not_unblock_anything() {
+    let solver = Solver::new(HashMapProblem::default());
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(
+        solver.import_solved([FragmentId(0), FragmentId(1)]).await,
+        0
+    );
+    assert_eq!(
+        solver.topological_order().await,
+        [FragmentId(0), FragmentId(1)]
+    );
+}
@@ -0,0 +1,138 @@
+use crate::{
+    problems::BoxError,
+    reexported::{format, test, Arc, Box, Mutex, NonZeroUsize, Vec},
+    run_interleaved,
+    test::{chain, CONCURRENCY},
+    FragmentId, Problem, Solver, Status,
+};
+use async_trait::async_trait;
+
+// `fragments` fragments without dependencies. Evaluation appends `tag` to a shared log, and fails
+// for `fail_on`
+struct LoggingProblem {
+    tag: usize,
+    fragments: usize,
+    fail_on: Option<FragmentId>,
+    log: Arc<Mutex<Vec<usize>>>,
+}
+
+#[async_trait]
+impl Problem for LoggingProblem {
+    type Error = BoxError;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        // Fragment 0 depends on all others
+        if id == FragmentId(0) {
+            dependencies.extend((1..self.fragments).map(FragmentId));
+        }
+
+        Ok(())
+    }
+
+    async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        if Some(id) == self.fail_on {
+            return Err(format!("fragment {} failed", id.0).into());
+        }
+        self.log.lock().await.push(self.tag);
+
+        Ok(())
+    }
+}
+
+fn logging_solver(
+    tag: usize,
+    fail_on: Option<FragmentId>,
+    log: &Arc<Mutex<Vec<usize>>>,
+) -> Solver<LoggingProblem> {
+    Solver::new(LoggingProblem {
+        tag,
+        fragments: 4,
+        fail_on,
+        log: log.clone(),
+    })
+}
+
+#[test]
+async fn run_interleaved_should_match_sequential_runs() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let sequential_chain = chain(3);
+    let sequential_logging = logging_solver(0, None, &log);
+    let interleaved_chain = chain(3);
+    let interleaved_logging = logging_solver(0, None, &log);
+    for solver in [&sequential_chain, &interleaved_chain] {
+        solver.enqueue_fragment(FragmentId(0)).await;
+    }
+    for solver in [&sequential_logging, &interleaved_logging] {
+        solver.enqueue_fragment(FragmentId(0)).await;
+    }
+    assert!(sequential_chain
+        .run(CONCURRENCY)
+        .await
+        .unwrap()
+        .punted
+        .is_empty());
+    assert!(sequential_logging
+        .run(CONCURRENCY)
+        .await
+        .unwrap()
+        .punted
+        .is_empty());
+
+    let reports = run_interleaved(
+        &[&interleaved_chain, &interleaved_logging],
+        CONCURRENCY,
+    )
+    .await;
+
+    assert_eq!(reports.len(), 2);
+    for report in reports {
+        assert_eq!(report.unwrap().status, Status::Done);
+    }
+    assert_eq!(
+        interleaved_chain.topological_order().await,
+        sequential_chain.topological_order().await
+    );
+    assert_eq!(
+        interleaved_logging.topological_order().await,
+        sequential_logging.topological_order().await
+    );
+}
+
+#[test]
+async fn run_interleaved_should_alternate_between_solvers() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let first = logging_solver(0, None, &log);
+    let second = logging_solver(1, None, &log);
+    // Only fragments without dependencies, so every step evaluates one fragment
+    for id in 1..4 {
+        first.enqueue_fragment(FragmentId(id)).await;
+        second.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    let reports = run_interleaved(&[&first, &second], NonZeroUsize::MIN).await;
+
+    assert!(reports.iter().all(Result::is_ok));
+    // Neither solver runs all of its evaluations before the other gets a turn
+    assert_eq!(*log.lock().await, &[0, 1, 0, 1, 0, 1]);
+}
+
+#[test]
+async fn run_interleaved_should_keep_running_other_solvers_after_an_error() {
+    let log = Arc::new(Mutex::new(Vec::new()));
+    let failing = logging_solver(0, Some(FragmentId(1)), &log);
+    let other = chain(3);
+    failing.enqueue_fragment(FragmentId(0)).await;
+    other.enqueue_fragment(FragmentId(0)).await;
+
+    let mut reports = run_interleaved(&[&failing, &other], CONCURRENCY).await;
+
+    assert_eq!(reports.pop().unwrap().unwrap().status, Status::Done);
+    let err = reports.pop().unwrap().unwrap_err();
+    assert_eq!(format!("{}", err), "fragment 1 failed");
+    assert!(!failing.is_evaluated(FragmentId(0)).await);
+}
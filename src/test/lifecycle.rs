@@ -0,0 +1,274 @@
+use crate::{
+    problems::{box_dependencies, box_evaluation, ClosureProblem},
+    reexported::{
+        atomic::{AtomicBool, Ordering},
+        test, Arc, Box, Map, Mutex, Vec,
+    },
+    test::CONCURRENCY,
+    FragmentId, FragmentLifecycle, Problem, Solver,
+};
+use async_trait::async_trait;
+use futures::{channel::oneshot, future, pin_mut};
+
+// Fragment 0 depends on fragment 1, which is gated. Fragment 2 always fails
+struct LifecycleProblem {
+    gate: Mutex<Option<oneshot::Receiver<()>>>,
+}
+
+#[async_trait]
+impl Problem for LifecycleProblem {
+    type Error = ();
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        if id == FragmentId(0) {
+            dependencies.push(FragmentId(1));
+        }
+
+        Ok(())
+    }
+
+    async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        match id.0 {
+            1 => {
+                let gate = self.gate.lock().await.take();
+                if let Some(gate) = gate {
+                    gate.await.unwrap();
+                }
+
+                Ok(())
+            }
+            2 => Err(()),
+            _ => Ok(()),
+        }
+    }
+}
+
+fn lifecycle_solver() -> (Solver<LifecycleProblem>, oneshot::Sender<()>) {
+    let (open_gate, gate) = oneshot::channel();
+
+    (
+        Solver::new(LifecycleProblem {
+            gate: Mutex::new(Some(gate)),
+        }),
+        open_gate,
+    )
+}
+
+#[test]
+async fn fragment_should_go_through_all_evaluation_states() {
+    let (solver, open_gate) = lifecycle_solver();
+    let p0 = FragmentId(0);
+    let p1 = FragmentId(1);
+
+    assert_eq!(solver.fragment_state(p0).await, FragmentLifecycle::Unknown);
+    solver.enqueue_fragment(p0).await;
+    assert_eq!(solver.fragment_state(p0).await, FragmentLifecycle::Queued);
+
+    assert!(solver.step().await.unwrap());
+    assert_eq!(
+        solver.fragment_state(p0).await,
+        FragmentLifecycle::Punted { pending_count: 1 },
+    );
+    assert_eq!(solver.fragment_state(p1).await, FragmentLifecycle::Queued);
+
+    {
+        let step = solver.step();
+        pin_mut!(step);
+
+        assert!(future::poll_immediate(&mut step).await.is_none());
+        assert_eq!(
+            solver.fragment_state(p1).await,
+            FragmentLifecycle::InFlight,
+        );
+
+        open_gate.send(()).unwrap();
+        assert!(step.await.unwrap());
+    }
+    assert_eq!(
+        solver.fragment_state(p1).await,
+        FragmentLifecycle::Evaluated
+    );
+    assert_eq!(solver.fragment_state(p0).await, FragmentLifecycle::Queued);
+
+    assert!(solver.step().await.unwrap());
+    assert_eq!(
+        solver.fragment_state(p0).await,
+        FragmentLifecycle::Evaluated
+    );
+}
+
+#[test]
+async fn failed_evaluations_should_be_reported() {
+    let (solver, _open_gate) = lifecycle_solver();
+    let p2 = FragmentId(2);
+
+    solver.enqueue_fragment(p2).await;
+    assert!(solver.step().await.is_err());
+    assert_eq!(solver.fragment_state(p2).await, FragmentLifecycle::Failed);
+}
+
+#[test]
+async fn retried_fragments_should_not_stay_failed() {
+    // The first evaluation fails, later ones succeed
+    let failed = Arc::new(AtomicBool::new(false));
+    let solver = Solver::new(ClosureProblem::new(
+        |_| box_dependencies(async { Vec::new() }),
+        move |_| {
+            let failed = failed.clone();
+            box_evaluation(async move {
+                if failed.swap(true, Ordering::Relaxed) {
+                    Ok(())
+                } else {
+                    Err("evaluation failed".into())
+                }
+            })
+        },
+    ));
+    let p0 = FragmentId(0);
+
+    solver.enqueue_fragment(p0).await;
+    assert!(solver.step().await.is_err());
+    assert_eq!(solver.fragment_state(p0).await, FragmentLifecycle::Failed);
+
+    solver.enqueue_fragment(p0).await;
+    assert!(solver.step().await.unwrap());
+    assert_eq!(
+        solver.fragment_state(p0).await,
+        FragmentLifecycle::Evaluated
+    );
+    assert_eq!(solver.detailed_summary().await.failed, 0);
+    assert!(solver.freeze().await.is_evaluated(p0));
+    assert_eq!(solver.fragment_evaluation_attempts(p0).await, 2);
+}
+
+#[test]
+async fn dependents_of_retried_fragments_should_not_stay_blocked() {
+    // 1 depends on 0, whose first evaluation fails
+    let failed = Arc::new(AtomicBool::new(false));
+    let solver = Solver::new(ClosureProblem::new(
+        |id| {
+            box_dependencies(async move {
+                match id {
+                    FragmentId(1) => Vec::from([FragmentId(0)]),
+                    _ => Vec::new(),
+                }
+            })
+        },
+        move |_| {
+            let failed = failed.clone();
+            box_evaluation(async move {
+                if failed.swap(true, Ordering::Relaxed) {
+                    Ok(())
+                } else {
+                    Err("evaluation failed".into())
+                }
+            })
+        },
+    ));
+    let p0 = FragmentId(0);
+    let p1 = FragmentId(1);
+
+    solver.enqueue_fragment(p0).await;
+    assert!(solver.step().await.is_err());
+    solver.enqueue_fragment(p1).await;
+    assert!(solver.step().await.unwrap());
+    assert_eq!(solver.fragment_state(p1).await, FragmentLifecycle::Blocked);
+
+    solver.enqueue_fragment(p0).await.enqueue_fragment(p1).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert_eq!(
+        solver.fragment_state(p1).await,
+        FragmentLifecycle::Evaluated
+    );
+    assert_eq!(
+        solver.summary().await,
+        "Solved 2/2 (0 pending, 0 punted, 0 in-flight)"
+    );
+}
+
+#[test]
+async fn assumed_fragments_should_be_skipped() {
+    let (solver, _open_gate) = lifecycle_solver();
+    let p1 = FragmentId(1);
+
+    solver.assume_evaluated(p1).await;
+    assert_eq!(solver.fragment_state(p1).await, FragmentLifecycle::Skipped);
+}
+
+// Check that `fragment_ids` is exactly the union of `fragment_ids_by_state`
+async fn assert_fragment_ids(
+    solver: &Solver<LifecycleProblem>,
+    expected: &[(FragmentLifecycle, &[usize])],
+) {
+    let expected = expected
+        .iter()
+        .map(|&(state, ids)| {
+            (
+                state,
+                ids.iter().copied().map(FragmentId).collect::<Vec<_>>(),
+            )
+        })
+        .collect::<Map<_, _>>();
+    let mut union = expected.values().flatten().copied().collect::<Vec<_>>();
+    union.sort_unstable();
+
+    assert_eq!(solver.fragment_ids_by_state().await, expected);
+    assert_eq!(solver.fragment_ids().await, union);
+}
+
+#[test]
+async fn fragment_ids_should_include_fragments_in_every_state() {
+    let (solver, open_gate) = lifecycle_solver();
+    assert_fragment_ids(&solver, &[]).await;
+
+    solver.enqueue_fragment(FragmentId(2)).await;
+    solver.assume_evaluated(FragmentId(3)).await;
+    assert_fragment_ids(
+        &solver,
+        &[
+            (FragmentLifecycle::Queued, &[2]),
+            (FragmentLifecycle::Skipped, &[3]),
+        ],
+    )
+    .await;
+    assert!(solver.step().await.is_err());
+
+    // Punt 0, queuing 1
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.step().await.unwrap());
+    {
+        let step = solver.step();
+        pin_mut!(step);
+
+        assert!(future::poll_immediate(&mut step).await.is_none());
+        assert_fragment_ids(
+            &solver,
+            &[
+                (FragmentLifecycle::InFlight, &[1]),
+                (FragmentLifecycle::Punted { pending_count: 1 }, &[0]),
+                (FragmentLifecycle::Skipped, &[3]),
+                (FragmentLifecycle::Failed, &[2]),
+            ],
+        )
+        .await;
+
+        open_gate.send(()).unwrap();
+        assert!(step.await.unwrap());
+    }
+
+    assert!(solver.step().await.unwrap());
+    assert_fragment_ids(
+        &solver,
+        &[
+            (FragmentLifecycle::Evaluated, &[0, 1]),
+            (FragmentLifecycle::Skipped, &[3]),
+            (FragmentLifecycle::Failed, &[2]),
+        ],
+    )
+    .await;
+}
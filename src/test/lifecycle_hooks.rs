@@ -0,0 +1,114 @@
+use crate::{
+    reexported::{test, Box, Mutex, Vec},
+    test::CONCURRENCY,
+    FragmentId, Problem, SolveReport, Solver, Status,
+};
+use async_trait::async_trait;
+
+#[derive(Clone, Debug, PartialEq, Eq)]
+enum Event {
+    Start,
+    Evaluate(FragmentId),
+    Finish(Status),
+}
+
+// Fragment 0 depends on fragment 1. Evaluating `failing` returns an error
+struct RecordingProblem {
+    failing: Option<FragmentId>,
+    events: Mutex<Vec<Event>>,
+}
+
+impl RecordingProblem {
+    fn new(failing: Option<FragmentId>) -> Self {
+        Self {
+            failing,
+            events: Mutex::new(Vec::new()),
+        }
+    }
+}
+
+#[async_trait]
+impl Problem for RecordingProblem {
+    type Error = ();
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        if id == FragmentId(0) {
+            dependencies.push(FragmentId(1));
+        }
+
+        Ok(())
+    }
+
+    async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        self.events.lock().await.push(Event::Evaluate(id));
+
+        if Some(id) == self.failing {
+            Err(())
+        } else {
+            Ok(())
+        }
+    }
+
+    async fn on_start(&self) {
+        self.events.lock().await.push(Event::Start);
+    }
+
+    async fn on_finish(&self, report: &SolveReport) {
+        self.events.lock().await.push(Event::Finish(report.status));
+    }
+}
+
+#[test]
+async fn on_start_and_on_finish_should_wrap_all_evaluations() {
+    let solver = Solver::new(RecordingProblem::new(None));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(
+        solver.into_problem_instance().events.into_inner(),
+        &[
+            Event::Start,
+            Event::Evaluate(FragmentId(1)),
+            Event::Evaluate(FragmentId(0)),
+            Event::Finish(Status::Done),
+        ],
+    );
+}
+
+#[test]
+async fn on_finish_should_be_called_when_evaluation_fails() {
+    let solver = Solver::new(RecordingProblem::new(Some(FragmentId(1))));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.run(CONCURRENCY).await.is_err());
+
+    assert_eq!(
+        solver.into_problem_instance().events.into_inner(),
+        &[
+            Event::Start,
+            Event::Evaluate(FragmentId(1)),
+            Event::Finish(Status::DoneWithCycles),
+        ],
+    );
+}
+
+#[test]
+async fn on_start_and_on_finish_should_be_called_once_per_run() {
+    let solver = Solver::new(RecordingProblem::new(None));
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(
+        solver.into_problem_instance().events.into_inner(),
+        &[
+            Event::Start,
+            Event::Finish(Status::Done),
+            Event::Start,
+            Event::Finish(Status::Done),
+        ],
+    );
+}
@@ -1,16 +1,85 @@
 use crate::{
-    reexported::{Box, Mutex, NonZeroUsize, Set, Vec},
-    {FragmentId, Problem},
+    problems::HashMapProblem,
+    reexported::{
+        atomic::{AtomicUsize, Ordering},
+        Box, Map, Mutex, NonZeroUsize, Set, Vec,
+    },
+    {FragmentId, Problem, Solver},
 };
 use async_trait::async_trait;
+use futures::channel::oneshot;
 use petgraph::{graph::NodeIndex, visit::EdgeRef, Directed, Graph};
 use void::Void;
 
+mod analysis;
+mod batch;
+#[cfg(feature = "benchmarking")]
+mod benchmarking;
+#[cfg(feature = "lru")]
+mod caching;
+#[cfg(feature = "chaos")]
+mod chaos;
+mod checkpoint;
+mod clone;
+mod closure;
+mod concurrency;
+mod counters;
 mod cycles;
+#[cfg(feature = "dense-ids")]
+mod dense_set;
+mod dependency_errors;
+mod dependency_order;
+#[cfg(any(feature = "std", feature = "serde"))]
+mod dump;
+mod enqueue;
+mod error_handler;
+mod evaluation_count;
+#[cfg(feature = "events")]
+mod events;
+mod fragment_id;
+mod freeze;
+mod future;
+mod generation;
+#[cfg(feature = "heapless")]
+mod heapless_solver;
+mod hooks;
+mod immediate;
+mod import;
+mod interleaved;
+mod lifecycle;
+mod lifecycle_hooks;
+mod ordering;
+mod outputs;
+#[cfg(feature = "tokio-runtime")]
+mod parallel;
+#[cfg(feature = "petgraph")]
+mod petgraph_ext;
+mod priority;
+mod problems;
+mod progress;
+#[cfg(feature = "quickcheck")]
+mod properties;
+mod prune;
+mod remove;
+mod replay;
 mod sanity;
+#[cfg(feature = "std")]
+mod stats;
+mod status;
+mod step_batch;
+mod stream;
+mod subgraph;
+mod subset;
+mod summary;
+#[cfg(feature = "std")]
+mod sync;
+#[cfg(feature = "sync-only")]
+mod sync_only;
+mod testing;
 mod tree;
+mod typed;
 
-const CONCURRENCY: NonZeroUsize = unsafe { NonZeroUsize::new_unchecked(2) };
+const CONCURRENCY: NonZeroUsize = NonZeroUsize::new(2).unwrap();
 
 struct PetgraphProblem {
     dependency_graph: Graph<(), (), Directed>,
@@ -34,20 +103,40 @@ impl PetgraphProblem {
     }
 }
 
+// These are provided by `petgraph_ext` when the `petgraph` feature is enabled
+feature_cfg! {
+    for !"petgraph";
+
+    impl From<NodeIndex<u32>> for FragmentId {
+        fn from(index: NodeIndex<u32>) -> Self {
+            Self(index.index())
+        }
+    }
+
+    impl From<FragmentId> for NodeIndex<u32> {
+        fn from(id: FragmentId) -> Self {
+            Self::new(id.0)
+        }
+    }
+}
+
 #[async_trait]
 impl Problem for PetgraphProblem {
     type Error = Void;
+    type Output = ();
 
     async fn direct_dependencies(
         &self,
         id: FragmentId,
         dependecies: &mut Vec<FragmentId>,
-    ) {
+    ) -> Result<(), Self::Error> {
         dependecies.extend(
             self.dependency_graph
                 .edges(NodeIndex::new(id.into()))
                 .map(|x| FragmentId::from(x.target().index())),
-        )
+        );
+
+        Ok(())
     }
 
     async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
@@ -59,3 +148,86 @@ impl Problem for PetgraphProblem {
         Ok(())
     }
 }
+
+// Solver for a chain of `len` fragments where fragment `n` depends on fragment `n + 1`
+fn chain(len: usize) -> Solver<HashMapProblem> {
+    Solver::new(HashMapProblem::from_edges(
+        (1..len).map(|id| (FragmentId(id - 1), FragmentId(id))),
+    ))
+}
+
+// Fragments whose evaluations wait for their gate to open, if they have one, and then fail if they
+// are in `failing`. Evaluations are counted when they start and recorded when they succeed
+#[derive(Default)]
+struct GatedProblem {
+    dependencies: HashMapProblem,
+    failing: Set<FragmentId>,
+    gates: Mutex<Map<FragmentId, oneshot::Receiver<()>>>,
+    evaluations: AtomicUsize,
+    evaluated: Mutex<Vec<FragmentId>>,
+}
+
+impl GatedProblem {
+    fn from_edges<I>(edges: I) -> Self
+    where
+        I: IntoIterator<Item = (FragmentId, FragmentId)>,
+    {
+        Self {
+            dependencies: HashMapProblem::from_edges(edges),
+            ..Self::default()
+        }
+    }
+
+    fn with_failing(mut self, failing: &[usize]) -> Self {
+        self.failing.extend(failing.iter().copied().map(FragmentId));
+
+        self
+    }
+
+    // Close the gate of `id`. It opens once the returned sender is used or dropped
+    async fn gate(&self, id: FragmentId) -> oneshot::Sender<()> {
+        let (open, gate) = oneshot::channel();
+        self.gates.lock().await.insert(id, gate);
+
+        open
+    }
+
+    fn evaluations(&self) -> usize {
+        self.evaluations.load(Ordering::Relaxed)
+    }
+
+    fn into_evaluated(self) -> Vec<FragmentId> {
+        self.evaluated.into_inner()
+    }
+}
+
+#[async_trait]
+impl Problem for GatedProblem {
+    type Error = FragmentId;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        self.dependencies
+            .direct_dependencies(id, dependencies)
+            .await
+            .map_err(|never| match never {})
+    }
+
+    async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        self.evaluations.fetch_add(1, Ordering::Relaxed);
+        let gate = self.gates.lock().await.remove(&id);
+        if let Some(gate) = gate {
+            let _ = gate.await;
+        }
+        if self.failing.contains(&id) {
+            return Err(id);
+        }
+        self.evaluated.lock().await.push(id);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,70 @@
+use crate::{
+    problems::HashMapProblem, reexported::test, test::CONCURRENCY, FragmentId,
+    Solver,
+};
+
+#[test]
+async fn ordering_constraints_should_order_independent_fragments() {
+    let solver = Solver::new(HashMapProblem::default());
+    solver
+        .add_ordering_constraint(FragmentId(1), FragmentId(0))
+        .await;
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(1)).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(
+        solver.topological_order().await,
+        [FragmentId(1), FragmentId(0)],
+    );
+}
+
+#[test]
+async fn ordering_constraints_should_pull_in_unqueued_fragments() {
+    let solver = Solver::new(HashMapProblem::default());
+    solver
+        .add_ordering_constraint(FragmentId(1), FragmentId(0))
+        .await;
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(
+        solver.topological_order().await,
+        [FragmentId(1), FragmentId(0)],
+    );
+}
+
+#[test]
+async fn ordering_constraints_should_not_create_false_cycles() {
+    // 0 already depends on 1, so the constraint is redundant
+    let solver = Solver::new(HashMapProblem::from_edges([(
+        FragmentId(0),
+        FragmentId(1),
+    )]));
+    solver
+        .add_ordering_constraint(FragmentId(1), FragmentId(0))
+        .await
+        .add_ordering_constraint(FragmentId(1), FragmentId(0))
+        .await;
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    solver.assert_no_cycles().await;
+}
+
+#[test]
+async fn ordering_constraints_against_dependencies_should_be_cycles() {
+    let solver = Solver::new(HashMapProblem::from_edges([(
+        FragmentId(0),
+        FragmentId(1),
+    )]));
+    solver
+        .add_ordering_constraint(FragmentId(0), FragmentId(1))
+        .await;
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    assert_eq!(
+        solver.run(CONCURRENCY).await.unwrap().punted,
+        [FragmentId(0), FragmentId(1)],
+    );
+}
@@ -0,0 +1,101 @@
+use crate::{
+    reexported::{test, Arc, Box, Vec},
+    test::CONCURRENCY,
+    FragmentId, Problem, Solver,
+};
+use async_trait::async_trait;
+use void::Void;
+
+// Each fragment depends on the one before it. Evaluating a fragment outputs its ID and a clone of
+// `token`
+struct OutputProblem {
+    token: Arc<()>,
+}
+
+#[async_trait]
+impl Problem for OutputProblem {
+    type Error = Void;
+    type Output = (usize, Arc<()>);
+
+    async fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        if id.0 > 0 {
+            dependencies.push(FragmentId(id.0 - 1));
+        }
+
+        Ok(())
+    }
+
+    async fn evaluate(
+        &self,
+        id: FragmentId,
+    ) -> Result<Self::Output, Self::Error> {
+        Ok((id.0, self.token.clone()))
+    }
+}
+
+async fn solved_chain(token: &Arc<()>) -> Solver<OutputProblem> {
+    let solver = Solver::new(OutputProblem {
+        token: token.clone(),
+    });
+    solver.enqueue_fragment(FragmentId(2)).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    solver
+}
+
+#[test]
+async fn outputs_should_be_stored_for_evaluated_fragments() {
+    let token = Arc::new(());
+    let solver = solved_chain(&token).await;
+
+    for id in 0..3 {
+        let (output, _) = solver.get_output(FragmentId(id)).await.unwrap();
+        assert_eq!(output, id);
+    }
+    assert!(solver.get_output(FragmentId(3)).await.is_none());
+}
+
+#[test]
+async fn taken_outputs_should_not_be_kept() {
+    let token = Arc::new(());
+    let solver = solved_chain(&token).await;
+
+    let (output, _) = solver.take_output(FragmentId(1)).await.unwrap();
+    assert_eq!(output, 1);
+    assert!(solver.take_output(FragmentId(1)).await.is_none());
+    assert!(solver.get_output(FragmentId(1)).await.is_none());
+    assert!(solver.is_evaluated(FragmentId(1)).await);
+}
+
+#[test]
+async fn assumed_fragments_should_have_no_output() {
+    let token = Arc::new(());
+    let solver = Solver::new(OutputProblem {
+        token: token.clone(),
+    });
+    solver.assume_evaluated(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(1)).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert!(solver.get_output(FragmentId(0)).await.is_none());
+    assert!(solver.get_output(FragmentId(1)).await.is_some());
+}
+
+#[test]
+async fn outputs_should_be_dropped_with_the_solver() {
+    let token = Arc::new(());
+    let solver = solved_chain(&token).await;
+    // The problem instance and 3 outputs
+    assert_eq!(Arc::strong_count(&token), 5);
+
+    let taken = solver.take_output(FragmentId(0)).await.unwrap();
+    drop(solver);
+    assert_eq!(Arc::strong_count(&token), 2);
+
+    drop(taken);
+    assert_eq!(Arc::strong_count(&token), 1);
+}
@@ -0,0 +1,413 @@
+use crate::{
+    problems::HashMapProblem,
+    reexported::{Arc, Box, NonZeroUsize, Vec},
+    test::CONCURRENCY,
+    FragmentId, FragmentLifecycle, Problem, ScheduleError, Solver,
+    SolverConfig, Status,
+};
+use async_trait::async_trait;
+use std::{
+    sync::{
+        atomic::{AtomicUsize, Ordering},
+        Mutex,
+    },
+    time::{Duration, Instant},
+};
+use void::Void;
+
+const FRAGMENTS: usize = 1000;
+const DELAY: Duration = Duration::from_millis(10);
+
+// Fragments with no dependencies that take `DELAY` to evaluate
+struct SlowProblem;
+
+#[async_trait]
+impl Problem for SlowProblem {
+    type Error = Void;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        _: FragmentId,
+        _: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn evaluate(&self, _: FragmentId) -> Result<(), Self::Error> {
+        tokio::time::sleep(DELAY).await;
+
+        Ok(())
+    }
+}
+
+// Time is paused so it only advances when all tasks are waiting on a timer, which makes elapsed
+// times exact regardless of how loaded the machine is
+#[tokio::test(start_paused = true)]
+async fn run_parallel_core_should_evaluate_fragments_in_parallel() {
+    let concurrency = NonZeroUsize::new(100).unwrap();
+    let solver = Arc::new(Solver::new(SlowProblem));
+    for id in 0..FRAGMENTS {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    let start = tokio::time::Instant::now();
+    let punted = solver.run_parallel_core(concurrency).await.unwrap();
+    let elapsed = start.elapsed();
+
+    assert!(punted.is_empty());
+    assert_eq!(solver.evaluations_attempted(), FRAGMENTS);
+    solver.assert_no_cycles().await;
+
+    // 10 batches of 100 parallel evaluations
+    let expected = DELAY * (FRAGMENTS / concurrency.get()) as u32;
+    assert_eq!(elapsed, expected);
+}
+
+// Fragments with no dependencies that take `DELAY` to fetch dependencies for. Evaluating fragment 0
+// fails right away, other evaluations take `DELAY`
+struct SlowFetchProblem;
+
+#[async_trait]
+impl Problem for SlowFetchProblem {
+    type Error = ();
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        _: FragmentId,
+        _: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        tokio::time::sleep(DELAY).await;
+
+        Ok(())
+    }
+
+    async fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        if id == FragmentId(0) {
+            return Err(());
+        }
+        tokio::time::sleep(DELAY).await;
+
+        Ok(())
+    }
+}
+
+#[tokio::test(start_paused = true)]
+async fn run_parallel_core_should_fetch_dependencies_in_parallel() {
+    const FETCHES: usize = 100;
+
+    let solver = Arc::new(Solver::new(SlowFetchProblem));
+    for id in 1..=FETCHES {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    let start = tokio::time::Instant::now();
+    let punted = solver
+        .run_parallel_core(NonZeroUsize::new(FETCHES).unwrap())
+        .await
+        .unwrap();
+    let elapsed = start.elapsed();
+
+    assert!(punted.is_empty());
+    // One batch of parallel fetches followed by one batch of parallel evaluations
+    assert_eq!(elapsed, DELAY * 2);
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn run_parallel_core_should_join_running_steps_after_an_error() {
+    let solver = Arc::new(Solver::new(SlowFetchProblem));
+    for id in 0..4 {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    assert_eq!(
+        solver
+            .run_parallel_core(NonZeroUsize::new(4).unwrap())
+            .await,
+        Err(())
+    );
+
+    // The other evaluations were still running when fragment 0 failed
+    for id in 1..4 {
+        assert_eq!(
+            solver.fragment_state(FragmentId(id)).await,
+            FragmentLifecycle::Evaluated
+        );
+    }
+    assert!(!solver.is_running());
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn run_parallel_core_should_respect_dependencies() {
+    let solver = Arc::new(Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(2)),
+        (FragmentId(0), FragmentId(3)),
+    ])));
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    let punted = solver
+        .run_parallel_core(NonZeroUsize::new(4).unwrap())
+        .await
+        .unwrap();
+
+    assert!(punted.is_empty());
+    solver
+        .assert_all_solved(&[
+            FragmentId(0),
+            FragmentId(1),
+            FragmentId(2),
+            FragmentId(3),
+        ])
+        .await;
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn wait_until_done_should_resolve_when_a_spawned_run_finishes() {
+    let solver = Arc::new(Solver::new(SlowProblem));
+    for id in 0..5 {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    let run = tokio::spawn({
+        let solver = solver.clone();
+        async move { solver.run(NonZeroUsize::new(1).unwrap()).await }
+    });
+    solver.wait_until_done().await;
+
+    for id in 0..5 {
+        assert!(solver.is_evaluated(FragmentId(id)).await);
+    }
+    assert!(run.await.unwrap().unwrap().punted.is_empty());
+}
+
+// Fragments with no dependencies that record how many evaluations are running when each starts
+#[derive(Default)]
+struct ConcurrencyRecorder {
+    running: AtomicUsize,
+    // Time since `start` and number of running evaluations, including the new one
+    starts: Mutex<Vec<(Duration, usize)>>,
+    start: Mutex<Option<Instant>>,
+}
+
+#[async_trait]
+impl Problem for ConcurrencyRecorder {
+    type Error = Void;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        _: FragmentId,
+        _: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn evaluate(&self, _: FragmentId) -> Result<(), Self::Error> {
+        let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+        let elapsed = self
+            .start
+            .lock()
+            .unwrap()
+            .get_or_insert_with(Instant::now)
+            .elapsed();
+        self.starts.lock().unwrap().push((elapsed, running));
+        tokio::time::sleep(DELAY).await;
+        self.running.fetch_sub(1, Ordering::SeqCst);
+
+        Ok(())
+    }
+}
+
+#[tokio::test(flavor = "multi_thread", worker_threads = 4)]
+async fn concurrency_schedule_should_change_simultaneous_evaluations() {
+    let switch = DELAY * 10;
+    let solver = Solver::new(ConcurrencyRecorder::default());
+    for id in 0..50 {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    let report = solver
+        .run_with_concurrency_schedule(Vec::from([
+            (Duration::ZERO, NonZeroUsize::new(1).unwrap()),
+            (switch, NonZeroUsize::new(4).unwrap()),
+        ]))
+        .await
+        .unwrap();
+    assert!(report.punted.is_empty());
+    assert_eq!(solver.evaluations_attempted(), 50);
+
+    let starts = solver.into_problem_instance().starts.into_inner().unwrap();
+    // Leave some slack around the switch for scheduling overhead
+    assert!(starts
+        .iter()
+        .filter(|(elapsed, _)| *elapsed < switch - DELAY)
+        .all(|&(_, running)| running == 1));
+    assert!(starts.iter().all(|&(_, running)| running <= 4));
+    assert!(starts.iter().any(|&(_, running)| running == 4));
+}
+
+#[test]
+fn empty_concurrency_schedule_should_be_rejected() {
+    let solver = Solver::new(SlowProblem);
+
+    assert_eq!(
+        futures::executor::block_on(
+            solver.run_with_concurrency_schedule(Vec::new())
+        ),
+        Err(ScheduleError::Empty),
+    );
+}
+
+#[test]
+fn overlapping_concurrency_schedule_should_be_rejected() {
+    let solver = Solver::new(SlowProblem);
+    let concurrency = NonZeroUsize::new(1).unwrap();
+
+    assert_eq!(
+        futures::executor::block_on(solver.run_with_concurrency_schedule(
+            Vec::from([(DELAY, concurrency), (DELAY, concurrency)]),
+        )),
+        Err(ScheduleError::NotIncreasing),
+    );
+    assert_eq!(solver.evaluations_attempted(), 0);
+}
+
+#[tokio::test]
+async fn concurrency_schedule_should_enqueue_missing_fragments_if_configured() {
+    let solver = Solver::with_config(
+        HashMapProblem::from_edges([
+            (FragmentId(0), FragmentId(1)),
+            (FragmentId(1), FragmentId(2)),
+        ]),
+        SolverConfig {
+            auto_enqueue_missing: true,
+            ..SolverConfig::default()
+        },
+    );
+    solver.enqueue_fragment(FragmentId(0)).await;
+    // Punt 0, queuing 1
+    assert!(solver.step().await.unwrap());
+    solver.prune_unreachable(&[]).await;
+
+    let report = solver
+        .run_with_concurrency_schedule(Vec::from([(
+            Duration::ZERO,
+            CONCURRENCY,
+        )]))
+        .await
+        .unwrap();
+    assert_eq!(report.status, Status::Done);
+    assert!(solver.is_evaluated(FragmentId(0)).await);
+}
+
+// Fragments with no dependencies where the first evaluation hangs and later ones return
+// immediately
+#[derive(Default)]
+struct HangsOnceProblem {
+    attempts: AtomicUsize,
+}
+
+#[async_trait]
+impl Problem for HangsOnceProblem {
+    type Error = Void;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        _: FragmentId,
+        _: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn evaluate(&self, _: FragmentId) -> Result<(), Self::Error> {
+        if self.attempts.fetch_add(1, Ordering::SeqCst) == 0 {
+            tokio::time::sleep(Duration::from_secs(3600)).await;
+        }
+
+        Ok(())
+    }
+}
+
+#[tokio::test]
+async fn step_with_timeout_should_requeue_fragments_that_take_too_long() {
+    use crate::{FragmentLifecycle, StepOutcome};
+
+    let solver = Solver::new(HangsOnceProblem::default());
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    assert_eq!(
+        solver.step_with_timeout(DELAY).await.unwrap(),
+        StepOutcome::Timeout
+    );
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Queued
+    );
+
+    assert!(solver.step().await.unwrap());
+    assert!(solver.is_evaluated(FragmentId(0)).await);
+    assert_eq!(solver.problem_instance.attempts.load(Ordering::SeqCst), 2);
+}
+
+#[tokio::test]
+async fn step_with_timeout_should_report_progress() {
+    use crate::StepOutcome;
+
+    let solver = Solver::new(HangsOnceProblem {
+        attempts: AtomicUsize::new(1),
+    });
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    assert_eq!(
+        solver.step_with_timeout(DELAY).await.unwrap(),
+        StepOutcome::Progressed
+    );
+    assert_eq!(
+        solver.step_with_timeout(DELAY).await.unwrap(),
+        StepOutcome::Empty
+    );
+    solver.assert_all_solved(&[FragmentId(0)]).await;
+}
+
+#[tokio::test]
+async fn scheduled_fragments_should_only_be_evaluated_once_due() {
+    let delay = Duration::from_millis(50);
+    let solver = Arc::new(Solver::new(HangsOnceProblem {
+        attempts: AtomicUsize::new(1),
+    }));
+    let scheduled_at = Instant::now();
+    solver
+        .schedule_evaluation_after(FragmentId(0), (scheduled_at + delay).into())
+        .await;
+
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert!(!solver.is_evaluated(FragmentId(0)).await);
+
+    solver.wait_for_progress().await;
+    assert!(scheduled_at.elapsed() >= delay);
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert!(solver.is_evaluated(FragmentId(0)).await);
+}
+
+#[tokio::test]
+async fn drain_delayed_should_enqueue_fragments_that_are_not_due() {
+    let solver = Arc::new(Solver::new(HangsOnceProblem {
+        attempts: AtomicUsize::new(1),
+    }));
+    let later = Instant::now() + Duration::from_secs(3600);
+    solver
+        .schedule_evaluation_after(FragmentId(0), later.into())
+        .await;
+    solver
+        .schedule_evaluation_after(FragmentId(1), later.into())
+        .await;
+
+    solver.drain_delayed().await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    solver
+        .assert_all_solved(&[FragmentId(0), FragmentId(1)])
+        .await;
+}
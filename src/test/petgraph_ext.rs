@@ -0,0 +1,24 @@
+use crate::{
+    petgraph_ext::PetgraphProblem, reexported::test, test::CONCURRENCY, Status,
+    TypedSolver,
+};
+use petgraph::Graph;
+
+#[test]
+async fn petgraph_problem_should_record_the_evaluation_order() {
+    let mut dependency_graph = Graph::<&str, ()>::new();
+    let p0 = dependency_graph.add_node("p0");
+    let p1 = dependency_graph.add_node("p1");
+    let p2 = dependency_graph.add_node("p2");
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p2, ());
+
+    let solver = TypedSolver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue(p0).await;
+    let report = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(report.status, Status::Done);
+    let (_, evaluation_order) =
+        solver.into_solver().into_problem_instance().into_parts();
+    assert_eq!(evaluation_order, &[p2, p1, p0]);
+}
@@ -0,0 +1,179 @@
+use crate::{
+    problems::HashMapProblem,
+    reexported::{test, NonZeroUsize, Vec},
+    FragmentId, Solver,
+};
+
+// Evaluate one fragment at a time so the evaluation order is the scheduling order
+const CONCURRENCY: NonZeroUsize = NonZeroUsize::MIN;
+
+#[test]
+async fn boosted_fragments_should_be_evaluated_first() {
+    let solver = Solver::new(HashMapProblem::default());
+    for id in 0..4 {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    assert!(solver.priority_boost(FragmentId(2), 1).await);
+    assert!(solver.priority_boost(FragmentId(3), 2).await);
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(
+        solver.topological_order().await,
+        &[FragmentId(3), FragmentId(2), FragmentId(0), FragmentId(1)],
+    );
+}
+
+#[test]
+async fn boosts_should_accumulate() {
+    let solver = Solver::new(HashMapProblem::default());
+    for id in 0..3 {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    assert!(solver.priority_boost(FragmentId(1), 2).await);
+    assert!(solver.priority_boost(FragmentId(2), 1).await);
+    assert!(solver.priority_boost(FragmentId(2), 2).await);
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(
+        solver.topological_order().await,
+        &[FragmentId(2), FragmentId(1), FragmentId(0)],
+    );
+}
+
+#[test]
+async fn priority_boost_should_ignore_fragments_that_are_not_queued() {
+    let solver = Solver::new(HashMapProblem::default());
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    assert!(!solver.priority_boost(FragmentId(1), 1).await);
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+    assert!(!solver.priority_boost(FragmentId(0), 1).await);
+}
+
+#[test]
+async fn boosted_fragments_should_keep_their_priority_when_punted() {
+    let solver = Solver::new(HashMapProblem::from_edges([(
+        FragmentId(2),
+        FragmentId(0),
+    )]));
+    for id in 0..4 {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    assert!(solver.priority_boost(FragmentId(2), 1).await);
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    // 2 is punted first, then requeued ahead of 1 and 3 once 0 is evaluated
+    assert_eq!(
+        solver.topological_order().await,
+        &[FragmentId(0), FragmentId(2), FragmentId(1), FragmentId(3)],
+    );
+}
+
+#[test]
+async fn priority_function_should_evaluate_a_chain_leaf_first() {
+    // Each fragment depends on the one before it, so 0 is the leaf
+    let solver = Solver::new(HashMapProblem::from_edges(
+        (1..5).map(|id| (FragmentId(id), FragmentId(id - 1))),
+    ));
+    for id in (0..5).rev() {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    let report = solver
+        .run_with_priority_function(CONCURRENCY, |id| 100 - id.0 as u64)
+        .await
+        .unwrap();
+
+    assert!(report.punted.is_empty());
+    assert_eq!(solver.punts(), 0);
+    assert_eq!(
+        solver.topological_order().await,
+        (0..5).map(FragmentId).collect::<Vec<_>>(),
+    );
+}
+
+#[test]
+async fn priority_function_should_apply_to_fragments_enqueued_during_the_run() {
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(0), FragmentId(2)),
+        (FragmentId(0), FragmentId(3)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    let report = solver
+        .run_with_priority_function(CONCURRENCY, |id| id.0 as u64)
+        .await
+        .unwrap();
+    assert!(report.punted.is_empty());
+
+    assert_eq!(
+        solver.topological_order().await,
+        &[FragmentId(3), FragmentId(2), FragmentId(1), FragmentId(0)],
+    );
+}
+
+#[test]
+async fn priority_function_should_only_apply_to_its_run() {
+    let solver = Solver::new(HashMapProblem::default());
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let report = solver
+        .run_with_priority_function(CONCURRENCY, |id| id.0 as u64)
+        .await
+        .unwrap();
+    assert!(report.punted.is_empty());
+
+    for id in 1..3 {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(
+        solver.topological_order().await,
+        &[FragmentId(0), FragmentId(1), FragmentId(2)],
+    );
+}
+
+#[test]
+async fn priority_function_should_add_boosts() {
+    let solver = Solver::new(HashMapProblem::default());
+    for id in 0..3 {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    assert!(solver.priority_boost(FragmentId(0), 2).await);
+    let report = solver
+        .run_with_priority_function(CONCURRENCY, |id| id.0 as u64)
+        .await
+        .unwrap();
+    assert!(report.punted.is_empty());
+
+    // 0 and 2 both have priority 2, so the lowest ID goes first
+    assert_eq!(
+        solver.topological_order().await,
+        &[FragmentId(0), FragmentId(2), FragmentId(1)],
+    );
+}
+
+#[test]
+async fn outdated_priorities_should_be_ignored() {
+    let solver = Solver::new(HashMapProblem::default());
+    for id in 0..3 {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    // Removing 1 drops its boost, even if it is enqueued again
+    assert!(solver.priority_boost(FragmentId(1), 5).await);
+    assert!(solver.remove_fragment(FragmentId(1)).await);
+    solver.enqueue_fragment(FragmentId(1)).await;
+    assert!(solver.priority_boost(FragmentId(2), 1).await);
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(
+        solver.topological_order().await,
+        &[FragmentId(2), FragmentId(0), FragmentId(1)],
+    );
+}
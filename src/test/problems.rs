@@ -0,0 +1,140 @@
+use crate::{
+    closure_problem,
+    problems::{
+        box_dependencies, box_evaluation, ClosureProblem, HashMapProblem,
+    },
+    reexported::{format, test, Arc, Mutex, Set, Vec},
+    test::CONCURRENCY,
+    FragmentId, Solver, Status,
+};
+
+#[test]
+async fn closure_problem_should_solve_for_one_fragment_with_one_dependency() {
+    let evaluation_order = Arc::new(Mutex::new(Vec::new()));
+
+    let solver = Solver::new(ClosureProblem::new(
+        |id: FragmentId| {
+            box_dependencies(async move {
+                if id == FragmentId(0) {
+                    [FragmentId(1)].into_iter().collect()
+                } else {
+                    Vec::new()
+                }
+            })
+        },
+        {
+            let evaluation_order = evaluation_order.clone();
+            move |id| {
+                let evaluation_order = evaluation_order.clone();
+                box_evaluation(async move {
+                    evaluation_order.lock().await.push(id);
+
+                    Ok(())
+                })
+            }
+        },
+    ));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let punted = solver.run(CONCURRENCY).await.unwrap().punted;
+
+    solver.assert_no_cycles().await;
+    assert!(punted.is_empty());
+    assert_eq!(
+        *evaluation_order.lock().await,
+        &[FragmentId(1), FragmentId(0)]
+    );
+}
+
+#[test]
+async fn closure_problem_macro_should_propagate_evaluation_errors() {
+    let solver = Solver::new(closure_problem!(
+        deps: |id| async {
+            if id == FragmentId(0) {
+                [FragmentId(1)].into_iter().collect()
+            } else {
+                Vec::new()
+            }
+        },
+        eval: |id| async {
+            if id == FragmentId(1) {
+                Err("evaluation failed".into())
+            } else {
+                Ok(())
+            }
+        },
+    ));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let err = solver.run(CONCURRENCY).await.unwrap_err();
+
+    assert_eq!(format!("{}", err), "evaluation failed");
+}
+
+#[test]
+async fn hash_map_problem_should_be_able_to_solve_a_tree() {
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(0), FragmentId(2)),
+        (FragmentId(1), FragmentId(3)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let punted = solver.run(CONCURRENCY).await.unwrap().punted;
+
+    solver.assert_no_cycles().await;
+    assert!(punted.is_empty());
+    solver
+        .assert_all_solved(&[
+            FragmentId(0),
+            FragmentId(1),
+            FragmentId(2),
+            FragmentId(3),
+        ])
+        .await;
+}
+
+#[test]
+async fn hash_map_problem_should_be_able_to_punt_two_intersecting_cycles() {
+    let solver = Solver::new(HashMapProblem::from_adjacency_list(
+        [
+            (FragmentId(0), [FragmentId(1)].into_iter().collect()),
+            (
+                FragmentId(1),
+                [FragmentId(0), FragmentId(2)].into_iter().collect(),
+            ),
+            (FragmentId(2), [FragmentId(1)].into_iter().collect()),
+        ]
+        .into_iter()
+        .collect(),
+    ));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let punted = solver
+        .run(CONCURRENCY)
+        .await
+        .unwrap()
+        .punted
+        .into_iter()
+        .collect::<Set<_>>();
+
+    assert_eq!(solver.status().await, Status::DoneWithCycles);
+    assert_eq!(
+        punted,
+        [FragmentId(0), FragmentId(1), FragmentId(2)]
+            .into_iter()
+            .collect(),
+    );
+}
+
+#[cfg(feature = "serde")]
+#[test]
+async fn hash_map_problem_should_be_deserializable_from_json() {
+    let problem: HashMapProblem =
+        serde_json::from_str(r#"{"0": [1, 2], "1": [2]}"#).unwrap();
+
+    assert_eq!(
+        problem,
+        HashMapProblem::from_edges([
+            (FragmentId(0), FragmentId(1)),
+            (FragmentId(0), FragmentId(2)),
+            (FragmentId(1), FragmentId(2)),
+        ]),
+    );
+}
@@ -0,0 +1,53 @@
+use crate::{
+    reexported::test,
+    test::{PetgraphProblem, CONCURRENCY},
+    FragmentId, Solver,
+};
+use futures::{future, pin_mut};
+use petgraph::Graph;
+
+#[test]
+async fn empty_solver_should_not_make_progress() {
+    assert!(!Solver::new(()).can_make_progress().await);
+}
+
+#[test]
+async fn solver_should_make_progress_after_enqueuing() {
+    let solver = Solver::new(());
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    assert!(solver.can_make_progress().await);
+}
+
+#[test]
+async fn solver_with_only_punted_fragments_should_not_make_progress() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p0, ());
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue_fragment(p0.index().into()).await;
+    assert_eq!(
+        solver.run(CONCURRENCY).await.unwrap().punted,
+        &[FragmentId::from(p0.index())],
+    );
+
+    assert!(!solver.can_make_progress().await);
+}
+
+#[test]
+async fn wait_for_progress_should_return_after_enqueuing() {
+    let solver = Solver::new(());
+
+    {
+        let wait = solver.wait_for_progress();
+        pin_mut!(wait);
+
+        assert!(future::poll_immediate(&mut wait).await.is_none());
+        solver.enqueue_fragment(FragmentId(0)).await;
+        wait.await;
+    }
+
+    // Already has work, so this must not block
+    solver.wait_for_progress().await;
+}
@@ -0,0 +1,51 @@
+use crate::{problems::MockProblem, test::CONCURRENCY, Solver, Status};
+use futures::executor::block_on;
+use quickcheck::QuickCheck;
+
+fn solve(problem: MockProblem) -> (Status, usize) {
+    block_on(async {
+        let fragments = problem.fragments();
+        let solver = Solver::new(problem);
+        for id in fragments {
+            solver.enqueue_fragment(id).await;
+        }
+        let punted = solver.run(CONCURRENCY).await.unwrap().punted;
+
+        (solver.status().await, punted.len())
+    })
+}
+
+#[test]
+fn dags_should_be_fully_solved() {
+    fn property(problem: MockProblem) -> bool {
+        solve(problem) == (Status::Done, 0)
+    }
+
+    QuickCheck::new().quickcheck(property as fn(MockProblem) -> bool);
+}
+
+#[test]
+fn cycles_should_punt_at_least_one_fragment() {
+    fn property(problem: MockProblem, edge: usize) -> bool {
+        if problem.fragments().len() == 0 {
+            return true;
+        }
+
+        // Reverse an existing edge to close a cycle, or add a self-dependency if there are none
+        let problem =
+            match problem.edges().get(edge % problem.edges().len().max(1)) {
+                Some(&(dependent, dependency)) => {
+                    problem.with_dependency(dependency, dependent)
+                }
+                None => {
+                    let id = problem.fragments().next().unwrap();
+                    problem.with_dependency(id, id)
+                }
+            };
+        let (status, punted) = solve(problem);
+
+        status == Status::DoneWithCycles && punted > 0
+    }
+
+    QuickCheck::new().quickcheck(property as fn(MockProblem, usize) -> bool);
+}
@@ -0,0 +1,52 @@
+use crate::{
+    problems::HashMapProblem, reexported::test, test::CONCURRENCY, FragmentId,
+    FragmentLifecycle, Solver,
+};
+
+#[test]
+async fn prune_unreachable_should_remove_unreachable_fragments() {
+    let solver = Solver::new(HashMapProblem::default());
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(1)).await;
+    solver.prune_unreachable(&[FragmentId(0)]).await;
+
+    assert_eq!(
+        solver.fragment_state(FragmentId(1)).await,
+        FragmentLifecycle::Unknown,
+    );
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert_eq!(solver.topological_order().await, [FragmentId(0)]);
+}
+
+#[test]
+async fn prune_unreachable_should_keep_known_dependencies() {
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(2)),
+    ]));
+    solver
+        .add_ordering_constraint(FragmentId(3), FragmentId(1))
+        .await;
+    solver.enqueue_fragment(FragmentId(0)).await;
+    // Fetches the dependencies of 0 and punts it
+    solver.step().await.unwrap();
+    solver.enqueue_fragment(FragmentId(3)).await;
+    solver.enqueue_fragment(FragmentId(4)).await;
+    solver.prune_unreachable(&[FragmentId(0)]).await;
+
+    assert_eq!(
+        solver.fragment_state(FragmentId(1)).await,
+        FragmentLifecycle::Queued,
+    );
+    assert_eq!(
+        solver.fragment_state(FragmentId(3)).await,
+        FragmentLifecycle::Queued,
+    );
+    assert_eq!(
+        solver.fragment_state(FragmentId(4)).await,
+        FragmentLifecycle::Unknown,
+    );
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert!(solver.is_evaluated(FragmentId(3)).await);
+    assert!(!solver.is_evaluated(FragmentId(4)).await);
+}
@@ -0,0 +1,164 @@
+use crate::{
+    problems::HashMapProblem,
+    reexported::test,
+    test::{chain, CONCURRENCY},
+    FragmentId, Solver, SolverConfig,
+};
+
+#[test]
+async fn remove_fragment_should_unqueue_fragments() {
+    let solver = chain(3);
+    solver.enqueue_fragment(FragmentId(2)).await;
+
+    assert!(solver.remove_fragment(FragmentId(2)).await);
+    assert!(!solver.can_make_progress().await);
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert_eq!(solver.evaluations_attempted(), 0);
+}
+
+#[test]
+async fn remove_fragment_should_ignore_unknown_and_solved_fragments() {
+    let solver = chain(3);
+    solver.enqueue_fragment(FragmentId(2)).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert!(!solver.remove_fragment(FragmentId(2)).await);
+    assert!(!solver.remove_fragment(FragmentId(3)).await);
+    assert!(solver.is_evaluated(FragmentId(2)).await);
+}
+
+#[test]
+async fn removed_punted_fragments_should_not_be_requeued() {
+    let solver = chain(3);
+    solver.enqueue_fragment(FragmentId(1)).await;
+    // Punt 1, queuing 2
+    assert!(solver.step().await.unwrap());
+
+    assert!(solver.remove_fragment(FragmentId(1)).await);
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    solver.assert_no_cycles().await;
+    solver.assert_all_solved(&[FragmentId(2)]).await;
+}
+
+#[test]
+async fn removed_fragments_should_be_solved_once_enqueued_again() {
+    let solver = chain(3);
+    solver.enqueue_fragment(FragmentId(0)).await;
+    // Punt 0 and 1
+    assert!(solver.step().await.unwrap());
+    assert!(solver.step().await.unwrap());
+
+    // 0 can't be evaluated without 1, so it is removed too
+    assert!(solver.remove_fragment(FragmentId(1)).await);
+    assert!(solver.punted_iter().await.is_empty());
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert_eq!(solver.evaluations_attempted(), 1);
+    assert!(!solver.is_evaluated(FragmentId(0)).await);
+
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(solver.evaluations_attempted(), 3);
+    solver
+        .assert_all_solved(&[FragmentId(0), FragmentId(1), FragmentId(2)])
+        .await;
+}
+
+#[test]
+async fn missing_fragments_should_find_pruned_dependencies() {
+    let solver = chain(3);
+    solver.enqueue_fragment(FragmentId(0)).await;
+    // Punt 0, queuing 1
+    assert!(solver.step().await.unwrap());
+    assert!(solver.missing_fragments().await.is_empty());
+
+    // Nothing is reachable from no roots, so 1 is unqueued
+    solver.prune_unreachable(&[]).await;
+    assert_eq!(solver.missing_fragments().await, [FragmentId(1)]);
+    // Nothing else will evaluate 1, so 0 stays punted
+    assert_eq!(
+        solver.run(CONCURRENCY).await.unwrap().punted,
+        [FragmentId(0)]
+    );
+    assert!(!solver.is_evaluated(FragmentId(0)).await);
+    assert_eq!(solver.missing_fragments().await, [FragmentId(1)]);
+
+    assert!(solver.enqueue_if_not_solved(FragmentId(1)).await);
+    assert!(solver.missing_fragments().await.is_empty());
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert!(solver.is_evaluated(FragmentId(0)).await);
+    assert!(solver.missing_fragments().await.is_empty());
+}
+
+#[test]
+async fn missing_fragments_should_be_enqueued_if_configured() {
+    let solver = Solver::with_config(
+        HashMapProblem::from_edges([
+            (FragmentId(0), FragmentId(1)),
+            (FragmentId(1), FragmentId(2)),
+        ]),
+        SolverConfig {
+            auto_enqueue_missing: true,
+            ..SolverConfig::default()
+        },
+    );
+    solver.enqueue_fragment(FragmentId(0)).await;
+    // Punt 0, queuing 1
+    assert!(solver.step().await.unwrap());
+    solver.prune_unreachable(&[]).await;
+
+    assert_eq!(solver.missing_fragments().await, [FragmentId(1)]);
+    assert!(solver.can_make_progress().await);
+    assert!(solver.missing_fragments().await.is_empty());
+}
+
+#[test]
+async fn runs_should_enqueue_missing_fragments_if_configured() {
+    let solver = Solver::with_config(
+        HashMapProblem::from_edges([
+            (FragmentId(0), FragmentId(1)),
+            (FragmentId(1), FragmentId(2)),
+        ]),
+        SolverConfig {
+            auto_enqueue_missing: true,
+            ..SolverConfig::default()
+        },
+    );
+    solver.enqueue_fragment(FragmentId(0)).await;
+    // Punt 0, queuing 1
+    assert!(solver.step().await.unwrap());
+    solver.prune_unreachable(&[]).await;
+
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    solver.assert_no_cycles().await;
+    solver
+        .assert_all_solved(&[FragmentId(0), FragmentId(1), FragmentId(2)])
+        .await;
+}
+
+#[test]
+async fn removed_fragments_should_not_be_enqueued_if_configured() {
+    let solver = Solver::with_config(
+        HashMapProblem::from_edges([
+            (FragmentId(0), FragmentId(1)),
+            (FragmentId(1), FragmentId(2)),
+        ]),
+        SolverConfig {
+            auto_enqueue_missing: true,
+            ..SolverConfig::default()
+        },
+    );
+    solver.enqueue_fragment(FragmentId(0)).await;
+    // Punt 0, queuing 1
+    assert!(solver.step().await.unwrap());
+    assert!(solver.priority_boost(FragmentId(1), 1).await);
+    assert!(solver.remove_fragment(FragmentId(1)).await);
+
+    assert!(solver.missing_fragments().await.is_empty());
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    solver.assert_no_cycles().await;
+    solver.assert_all_solved(&[]).await;
+    assert_eq!(solver.evaluations_attempted(), 0);
+    assert!(!solver.priority_boost(FragmentId(1), 1).await);
+}
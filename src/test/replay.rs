@@ -0,0 +1,95 @@
+use crate::{
+    problems::{HashMapProblem, TracingProblem},
+    reexported::{test, Vec},
+    test::CONCURRENCY,
+    EvaluationTrace, FragmentId, SolveReport, Solver, Status,
+};
+
+fn diamond() -> HashMapProblem {
+    HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(0), FragmentId(2)),
+        (FragmentId(1), FragmentId(3)),
+        (FragmentId(2), FragmentId(3)),
+    ])
+}
+
+async fn record(problem: HashMapProblem) -> (SolveReport, EvaluationTrace) {
+    let solver = Solver::new(TracingProblem::new(problem));
+    let report = solver.solve_fragment(FragmentId(0), CONCURRENCY).await;
+    let (_, trace) = solver.into_problem_instance().into_parts();
+
+    (report.unwrap(), trace)
+}
+
+#[test]
+async fn tracing_problem_should_record_fetches_and_evaluations() {
+    let (_, trace) = record(diamond()).await;
+
+    assert_eq!(trace.eval_order.len(), 4);
+    assert_eq!(trace.eval_order.last(), Some(&FragmentId(0)));
+    assert!(trace
+        .dep_responses
+        .contains(&(FragmentId(0), Vec::from([FragmentId(1), FragmentId(2)]))));
+}
+
+#[test]
+async fn replay_from_trace_should_reproduce_the_recorded_run() {
+    let (recorded, trace) = record(diamond()).await;
+
+    let replayed = Solver::replay_from_trace(&trace, diamond()).await.unwrap();
+
+    assert_eq!(replayed, recorded);
+    assert_eq!(replayed.status, Status::Done);
+}
+
+#[test]
+async fn replay_from_trace_should_report_punted_cycle_members() {
+    let problem = || {
+        HashMapProblem::from_edges([
+            (FragmentId(0), FragmentId(1)),
+            (FragmentId(1), FragmentId(0)),
+        ])
+    };
+    let (recorded, trace) = record(problem()).await;
+
+    let replayed = Solver::replay_from_trace(&trace, problem()).await.unwrap();
+
+    assert_eq!(replayed, recorded);
+    assert_eq!(replayed.status, Status::DoneWithCycles);
+}
+
+#[test]
+#[should_panic(expected = "dependencies of fragment FragmentId(1) diverged")]
+async fn replay_from_trace_should_panic_when_dependencies_diverge() {
+    let (_, trace) = record(diamond()).await;
+
+    let _ = Solver::replay_from_trace(
+        &trace,
+        HashMapProblem::from_edges([
+            (FragmentId(0), FragmentId(1)),
+            (FragmentId(0), FragmentId(2)),
+            (FragmentId(1), FragmentId(2)),
+            (FragmentId(1), FragmentId(3)),
+            (FragmentId(2), FragmentId(3)),
+        ]),
+    )
+    .await;
+}
+
+#[test]
+#[should_panic(
+    expected = "fragment FragmentId(0) was evaluated before its dependency FragmentId(1)"
+)]
+async fn replay_from_trace_should_panic_when_evaluations_are_out_of_order() {
+    let trace = EvaluationTrace {
+        dep_responses: Vec::from([
+            (FragmentId(0), Vec::from([FragmentId(1)])),
+            (FragmentId(1), Vec::new()),
+        ]),
+        eval_order: Vec::from([FragmentId(0), FragmentId(1)]),
+    };
+
+    let problem = HashMapProblem::from_edges([(FragmentId(0), FragmentId(1))]);
+    let _ = Solver::replay_from_trace(&trace, problem).await;
+}
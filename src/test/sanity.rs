@@ -11,12 +11,13 @@ struct PanicProblem;
 #[async_trait]
 impl Problem for PanicProblem {
     type Error = Void;
+    type Output = ();
 
     async fn direct_dependencies(
         &self,
         _: FragmentId,
         _: &mut Vec<FragmentId>,
-    ) {
+    ) -> Result<(), Self::Error> {
         unreachable!()
     }
 
@@ -35,6 +36,11 @@ async fn empty_solver_punted_iter_must_be_empty() {
     assert_eq!(Solver::new(()).punted_iter().await.first(), None);
 }
 
+#[test]
+async fn empty_solver_wait_until_done_should_return_right_away() {
+    Solver::new(()).wait_until_done().await;
+}
+
 #[test]
 async fn non_empty_unexecuted_solver_status_should_be_pending() {
     assert_eq!(
@@ -72,6 +78,7 @@ async fn running_an_empty_solver_must_return_ok_wth_an_empty_iterator() {
             .run(CONCURRENCY)
             .await
             .unwrap()
+            .punted
             .first(),
         None
     );
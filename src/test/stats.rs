@@ -0,0 +1,68 @@
+use crate::{
+    problems::HashMapProblem,
+    reexported::{test, NonZeroUsize},
+    FragmentId, Solver,
+};
+
+const CONCURRENCY: NonZeroUsize = NonZeroUsize::new(1).unwrap();
+
+#[test]
+async fn run_with_stats_should_count_a_chain() {
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(2)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    let (report, stats) = solver.run_with_stats(CONCURRENCY).await.unwrap();
+
+    assert!(report.punted.is_empty());
+    assert_eq!(stats.total_evaluations, 3);
+    // Punt 0 and 1, then fetch everything again when it's requeued
+    assert_eq!(stats.total_punts, 2);
+    assert_eq!(stats.total_dependency_fetches, 5);
+    assert_eq!(stats.peak_queue_depth, 1);
+    assert_eq!(stats.slowest_5.len(), 3);
+}
+
+#[test]
+async fn run_with_stats_should_keep_the_five_slowest_evaluations() {
+    let solver = Solver::new(HashMapProblem::from_edges(
+        (1..=6).map(|id| (FragmentId(0), FragmentId(id))),
+    ));
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    let (_, stats) = solver.run_with_stats(CONCURRENCY).await.unwrap();
+
+    assert_eq!(stats.total_evaluations, 7);
+    assert_eq!(stats.total_punts, 1);
+    assert_eq!(stats.total_dependency_fetches, 8);
+    assert_eq!(stats.peak_queue_depth, 6);
+    assert_eq!(stats.slowest_5.len(), 5);
+    assert!(stats
+        .slowest_5
+        .windows(2)
+        .all(|window| window[0].1 >= window[1].1));
+}
+
+#[test]
+async fn run_with_stats_should_only_count_the_current_run() {
+    let solver = Solver::new(HashMapProblem::from_edges([(
+        FragmentId(0),
+        FragmentId(1),
+    )]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let _ = solver.run_with_stats(CONCURRENCY).await.unwrap();
+    solver.enqueue_fragment(FragmentId(2)).await;
+    solver.enqueue_fragment(FragmentId(3)).await;
+
+    let (_, stats) = solver.run_with_stats(CONCURRENCY).await.unwrap();
+
+    assert_eq!(stats.total_evaluations, 2);
+    assert_eq!(stats.total_punts, 0);
+    assert_eq!(stats.total_dependency_fetches, 2);
+    assert_eq!(stats.peak_queue_depth, 2);
+    assert_eq!(solver.evaluations_attempted(), 4);
+    assert_eq!(solver.punts(), 1);
+    assert_eq!(solver.dependency_fetches(), 5);
+}
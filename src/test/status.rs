@@ -0,0 +1,35 @@
+use crate::{reexported::format, ParseStatusError, Status};
+
+const STATUSES: [(Status, &str); 3] = [
+    (Status::Done, "done"),
+    (Status::DoneWithCycles, "done_with_cycles"),
+    (Status::Pending, "pending"),
+];
+
+#[test]
+fn status_should_display_in_snake_case() {
+    for (status, expected) in STATUSES {
+        assert_eq!(format!("{}", status), expected);
+    }
+}
+
+#[test]
+fn status_should_parse_its_display_output() {
+    for (expected, s) in STATUSES {
+        assert_eq!(s.parse::<Status>(), Ok(expected));
+    }
+}
+
+#[test]
+fn status_should_round_trip_through_display() {
+    for (status, _) in STATUSES {
+        assert_eq!(format!("{}", status).parse::<Status>(), Ok(status));
+    }
+}
+
+#[test]
+fn invalid_status_strings_should_not_parse() {
+    for s in ["", "Done", "DoneWithCycles", "done-with-cycles", "running"] {
+        assert_eq!(s.parse::<Status>(), Err(ParseStatusError));
+    }
+}
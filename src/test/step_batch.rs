@@ -0,0 +1,108 @@
+use crate::{
+    problems::{
+        box_dependencies, box_evaluation, ClosureProblem, HashMapProblem,
+    },
+    reexported::{format, task::Poll, test, Vec},
+    FragmentId, FragmentLifecycle, Solver,
+};
+use futures::future;
+
+#[test]
+async fn step_batch_should_process_at_most_max_fragments() {
+    let solver = Solver::new(HashMapProblem::default());
+    for id in 0..5 {
+        solver.enqueue_fragment(FragmentId(id)).await;
+    }
+
+    assert_eq!(solver.step_batch(3).await.unwrap(), 3);
+    assert_eq!(solver.evaluations_attempted(), 3);
+    assert_eq!(solver.step_batch(10).await.unwrap(), 2);
+    assert_eq!(solver.evaluations_attempted(), 5);
+    assert_eq!(solver.step_batch(10).await.unwrap(), 0);
+    solver.assert_no_cycles().await;
+}
+
+#[test]
+async fn step_batch_should_count_punted_fragments() {
+    // 0 depends on 1 and 2
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(0), FragmentId(2)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+
+    // Punt 0
+    assert_eq!(solver.step_batch(10).await.unwrap(), 1);
+    assert_eq!(solver.punts(), 1);
+    // Evaluate 1 and 2
+    assert_eq!(solver.step_batch(10).await.unwrap(), 2);
+    // Evaluate 0
+    assert_eq!(solver.step_batch(10).await.unwrap(), 1);
+    assert_eq!(solver.step_batch(0).await.unwrap(), 0);
+    solver
+        .assert_all_solved(&[FragmentId(0), FragmentId(1), FragmentId(2)])
+        .await;
+}
+
+#[test]
+async fn step_batch_should_return_evaluation_errors() {
+    let solver = Solver::new(ClosureProblem::new(
+        |_| box_dependencies(async { Vec::new() }),
+        |id| {
+            box_evaluation(async move {
+                if id == FragmentId(1) {
+                    Err("evaluation failed".into())
+                } else {
+                    Ok(())
+                }
+            })
+        },
+    ));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(1)).await;
+
+    let err = solver.step_batch(2).await.unwrap_err();
+
+    assert_eq!(format!("{}", err), "evaluation failed");
+}
+
+#[test]
+async fn step_batch_should_finish_other_steps_after_an_error() {
+    let solver = Solver::new(ClosureProblem::new(
+        |_| box_dependencies(async { Vec::new() }),
+        |id| {
+            box_evaluation(async move {
+                if id == FragmentId(1) {
+                    return Err("evaluation failed".into());
+                }
+                // Still running when the evaluation of 1 fails
+                let mut yielded = false;
+                future::poll_fn(|cx| {
+                    if yielded {
+                        Poll::Ready(())
+                    } else {
+                        yielded = true;
+                        cx.waker().wake_by_ref();
+                        Poll::Pending
+                    }
+                })
+                .await;
+
+                Ok(())
+            })
+        },
+    ));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(1)).await;
+
+    assert!(solver.step_batch(2).await.is_err());
+
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Evaluated
+    );
+    assert_eq!(
+        solver.fragment_state(FragmentId(1)).await,
+        FragmentLifecycle::Failed
+    );
+}
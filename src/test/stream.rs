@@ -0,0 +1,232 @@
+use crate::{
+    reexported::{test, Arc, Box, Mutex, Set, Vec},
+    test::{GatedProblem, PetgraphProblem, CONCURRENCY},
+    EvaluationEvent, FragmentId, Problem, SolveReport, Solver, Status,
+};
+use async_trait::async_trait;
+use futures::{future, pin_mut, StreamExt};
+use petgraph::{algo, Graph};
+use void::Void;
+
+#[test]
+async fn stream_should_yield_fragments_in_topological_order() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    let p3 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p2, ());
+    dependency_graph.add_edge(p2, p3, ());
+    let mut topological_order =
+        algo::toposort(&dependency_graph, None).unwrap();
+    topological_order.reverse();
+
+    let solver = Solver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue_fragment(p0.index().into()).await;
+    let evaluated = solver
+        .into_stream()
+        .map(|x| x.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+
+    assert_eq!(
+        evaluated,
+        topological_order
+            .into_iter()
+            .map(|x| x.index().into())
+            .collect::<Vec<FragmentId>>(),
+    );
+}
+
+// 2 depends on 1, which depends on 0. Evaluating 1 fails
+fn failing_chain_solver() -> Solver<GatedProblem> {
+    Solver::new(
+        GatedProblem::from_edges([
+            (FragmentId(2), FragmentId(1)),
+            (FragmentId(1), FragmentId(0)),
+        ])
+        .with_failing(&[1]),
+    )
+}
+
+#[test]
+async fn stream_should_end_after_an_evaluation_error() {
+    let solver = failing_chain_solver();
+    solver.enqueue_fragment(FragmentId(2)).await;
+    let items = solver.into_stream().collect::<Vec<_>>().await;
+
+    assert_eq!(items, &[Ok(FragmentId(0)), Err(FragmentId(1))]);
+}
+
+// Records calls to `Problem::on_start`, `Problem::evaluate`, and `Problem::on_finish`
+#[derive(Default)]
+struct RecordingProblem {
+    calls: Arc<Mutex<Vec<&'static str>>>,
+}
+
+#[async_trait]
+impl Problem for RecordingProblem {
+    type Error = Void;
+    type Output = ();
+
+    async fn direct_dependencies(
+        &self,
+        _: FragmentId,
+        _: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
+
+    async fn evaluate(&self, _: FragmentId) -> Result<(), Self::Error> {
+        self.calls.lock().await.push("evaluate");
+
+        Ok(())
+    }
+
+    async fn on_start(&self) {
+        self.calls.lock().await.push("start");
+    }
+
+    async fn on_finish(&self, _: &SolveReport) {
+        self.calls.lock().await.push("finish");
+    }
+}
+
+#[test]
+async fn stream_should_start_and_finish_a_run() {
+    let problem = RecordingProblem::default();
+    let calls = problem.calls.clone();
+    let solver = Solver::new(problem);
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(1)).await;
+
+    assert_eq!(solver.into_stream().count().await, 2);
+    assert_eq!(
+        *calls.lock().await,
+        &["start", "evaluate", "evaluate", "finish"]
+    );
+}
+
+#[test]
+async fn stream_of_an_empty_solver_should_be_empty() {
+    let solver = Solver::new(PetgraphProblem::new(Graph::new()));
+
+    assert!(solver.into_stream().collect::<Vec<_>>().await.is_empty());
+}
+
+// 0 -> 1 -> 2 is solvable, 3 <-> 4 is a cycle and 5 depends on it
+fn chain_and_cycle_solver() -> Solver<PetgraphProblem> {
+    let mut dependency_graph = Graph::new();
+    let p: Vec<_> = (0..6).map(|_| dependency_graph.add_node(())).collect();
+    dependency_graph.add_edge(p[0], p[1], ());
+    dependency_graph.add_edge(p[1], p[2], ());
+    dependency_graph.add_edge(p[3], p[4], ());
+    dependency_graph.add_edge(p[4], p[3], ());
+    dependency_graph.add_edge(p[5], p[3], ());
+
+    Solver::new(PetgraphProblem::new(dependency_graph))
+}
+
+#[test]
+async fn streaming_run_should_match_a_regular_run() {
+    let streaming = chain_and_cycle_solver();
+    streaming.enqueue_fragment(FragmentId(0)).await;
+    streaming.enqueue_fragment(FragmentId(5)).await;
+    let mut events = streaming
+        .run_streaming(CONCURRENCY)
+        .map(|x| x.unwrap())
+        .collect::<Vec<_>>()
+        .await;
+
+    let regular = chain_and_cycle_solver();
+    regular.enqueue_fragment(FragmentId(0)).await;
+    regular.enqueue_fragment(FragmentId(5)).await;
+    let report: SolveReport =
+        regular.run_detailed(CONCURRENCY).await.unwrap().into();
+
+    assert_eq!(events.pop(), Some(EvaluationEvent::Done(report.clone())));
+    assert_eq!(
+        events.pop(),
+        Some(EvaluationEvent::CycleDetected(report.punted.clone())),
+    );
+
+    let mut evaluated = Set::new();
+    let mut punted = Set::new();
+    for event in events {
+        match event {
+            EvaluationEvent::Evaluated(id) => assert!(evaluated.insert(id)),
+            EvaluationEvent::Punted(id) => {
+                punted.insert(id);
+            }
+            event => panic!("unexpected event {event:?}"),
+        }
+    }
+    assert_eq!(
+        evaluated,
+        regular
+            .into_problem_instance()
+            .into_evaluated_set()
+            .into_iter()
+            .map(FragmentId::from)
+            .collect(),
+    );
+    assert!(report.punted.iter().all(|id| punted.contains(id)));
+}
+
+#[test]
+async fn streaming_run_should_end_with_done_after_an_evaluation_error() {
+    let solver = failing_chain_solver();
+    solver.enqueue_fragment(FragmentId(2)).await;
+    let mut items = solver.run_streaming(CONCURRENCY).collect::<Vec<_>>().await;
+
+    assert!(
+        matches!(items.pop(), Some(Ok(EvaluationEvent::Done(report))) if report.failed == 1)
+    );
+    assert_eq!(items.pop(), Some(Err(FragmentId(1))));
+    assert!(items.contains(&Ok(EvaluationEvent::Evaluated(FragmentId(0)))));
+    assert!(!solver.is_running());
+}
+
+#[test]
+async fn dropping_a_streaming_run_should_cancel_it() {
+    let solver = chain_and_cycle_solver();
+    solver.enqueue_fragment(FragmentId(0)).await;
+    {
+        let events = solver.run_streaming(CONCURRENCY);
+        pin_mut!(events);
+        assert_eq!(
+            events.next().await,
+            Some(Ok(EvaluationEvent::Punted(FragmentId(0)))),
+        );
+        assert!(solver.is_running());
+    }
+    assert!(!solver.is_running());
+
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert!(solver.is_evaluated(FragmentId(0)).await);
+}
+
+#[test]
+async fn dropping_a_streaming_run_waiting_for_another_run_should_not_affect_it()
+{
+    let solver = Solver::new(GatedProblem::default());
+    let open_gate = solver.problem_instance.gate(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(1)).await;
+
+    let running = solver.run(CONCURRENCY);
+    pin_mut!(running);
+    assert!(future::poll_immediate(&mut running).await.is_none());
+    {
+        let events = solver.run_streaming(CONCURRENCY);
+        pin_mut!(events);
+        assert!(future::poll_immediate(events.next()).await.is_none());
+    }
+    assert!(solver.is_running());
+
+    open_gate.send(()).unwrap();
+    assert!(running.await.unwrap().punted.is_empty());
+    assert_eq!(solver.status().await, Status::Done);
+    assert_eq!(solver.problem_instance.evaluations(), 2);
+}
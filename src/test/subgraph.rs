@@ -0,0 +1,111 @@
+use crate::{
+    problems::HashMapProblem,
+    reexported::{test, Vec},
+    test::CONCURRENCY,
+    DependencySubgraph, FragmentId, Solver,
+};
+
+// Diamonds 0 -> {1, 2} -> 3 and 4 -> {2, 5} -> 3, which share 2 and 3, and an unrelated 8 -> 9
+async fn solved_overlapping_diamonds() -> Solver<HashMapProblem> {
+    let solver = Solver::new(HashMapProblem::from_edges(
+        [
+            (0, 1),
+            (0, 2),
+            (1, 3),
+            (2, 3),
+            (4, 2),
+            (4, 5),
+            (5, 3),
+            (8, 9),
+        ]
+        .map(|(dependent, dependency)| {
+            (FragmentId(dependent), FragmentId(dependency))
+        }),
+    ));
+    let report = solver
+        .solve_fragments(
+            [FragmentId(0), FragmentId(4), FragmentId(8)],
+            CONCURRENCY,
+        )
+        .await
+        .unwrap();
+    assert!(report.punted.is_empty());
+
+    solver
+}
+
+fn ids(ids: &[usize]) -> Vec<FragmentId> {
+    ids.iter().copied().map(FragmentId).collect()
+}
+
+fn edges(edges: &[(usize, usize)]) -> Vec<(FragmentId, FragmentId)> {
+    edges
+        .iter()
+        .map(|&(dependent, dependency)| {
+            (FragmentId(dependent), FragmentId(dependency))
+        })
+        .collect()
+}
+
+#[test]
+async fn dependency_subgraph_should_contain_transitive_dependencies() {
+    let solver = solved_overlapping_diamonds().await;
+
+    let subgraph = solver.dependency_subgraph(&[FragmentId(4)]).await;
+
+    assert_eq!(
+        subgraph,
+        DependencySubgraph {
+            nodes: ids(&[2, 3, 4, 5]),
+            edges: edges(&[(2, 3), (4, 2), (4, 5), (5, 3)]),
+        },
+    );
+    assert_eq!(subgraph.fragment_count(), 4);
+}
+
+#[test]
+async fn dependency_subgraph_should_merge_overlapping_roots() {
+    let solver = solved_overlapping_diamonds().await;
+
+    let subgraph = solver
+        .dependency_subgraph(&[FragmentId(0), FragmentId(4)])
+        .await;
+
+    assert_eq!(subgraph.nodes, ids(&[0, 1, 2, 3, 4, 5]));
+    assert_eq!(
+        subgraph.edges,
+        edges(&[(0, 1), (0, 2), (1, 3), (2, 3), (4, 2), (4, 5), (5, 3)]),
+    );
+}
+
+#[test]
+async fn dependency_subgraph_should_exclude_dependents_of_roots() {
+    let solver = solved_overlapping_diamonds().await;
+
+    let subgraph = solver.dependency_subgraph(&[FragmentId(2)]).await;
+
+    assert_eq!(subgraph.nodes, ids(&[2, 3]));
+    assert_eq!(subgraph.edges, edges(&[(2, 3)]));
+}
+
+#[test]
+async fn dependency_subgraph_should_keep_unknown_roots() {
+    let solver = solved_overlapping_diamonds().await;
+
+    let subgraph = solver.dependency_subgraph(&[FragmentId(42)]).await;
+
+    assert_eq!(subgraph.nodes, ids(&[42]));
+    assert!(subgraph.edges.is_empty());
+}
+
+#[test]
+async fn dependency_subgraph_to_dot_should_render_nodes_and_edges() {
+    let solver = solved_overlapping_diamonds().await;
+
+    let subgraph = solver.dependency_subgraph(&[FragmentId(2)]).await;
+
+    assert_eq!(
+        subgraph.to_dot(),
+        "digraph {\n    2;\n    3;\n    2 -> 3;\n}\n"
+    );
+}
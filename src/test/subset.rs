@@ -0,0 +1,80 @@
+use crate::{
+    problems::HashMapProblem, reexported::test, test::CONCURRENCY, FragmentId,
+    Solver, Status,
+};
+
+fn solver() -> Solver<HashMapProblem> {
+    Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(2), FragmentId(3)),
+        (FragmentId(4), FragmentId(1)),
+    ]))
+}
+
+#[test]
+async fn run_subset_should_only_evaluate_reachable_fragments() {
+    let solver = solver();
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(2)).await;
+
+    let report = solver
+        .run_subset(&[FragmentId(0)], CONCURRENCY)
+        .await
+        .unwrap();
+
+    assert_eq!(report.status, Status::Pending);
+    assert!(solver.is_evaluated(FragmentId(0)).await);
+    assert!(solver.is_evaluated(FragmentId(1)).await);
+    assert!(!solver.is_evaluated(FragmentId(2)).await);
+    assert!(!solver.is_evaluated(FragmentId(3)).await);
+    assert_eq!(solver.evaluations_attempted(), 2);
+
+    // The rest is still queued
+    assert!(solver.can_make_progress().await);
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    solver
+        .assert_all_solved(&[
+            FragmentId(0),
+            FragmentId(1),
+            FragmentId(2),
+            FragmentId(3),
+        ])
+        .await;
+}
+
+#[test]
+async fn run_subset_should_not_evaluate_unblocked_fragments_outside_the_subset()
+{
+    let solver = solver();
+    solver.enqueue_fragment(FragmentId(4)).await;
+    // Punt 4, queuing 1
+    assert!(solver.step().await.unwrap());
+
+    let report = solver
+        .run_subset(&[FragmentId(1)], CONCURRENCY)
+        .await
+        .unwrap();
+
+    // Evaluating 1 requeues 4, but 4 is not reachable from 1
+    assert_eq!(report.status, Status::Pending);
+    assert!(solver.is_evaluated(FragmentId(1)).await);
+    assert!(!solver.is_evaluated(FragmentId(4)).await);
+    assert!(solver.can_make_progress().await);
+}
+
+#[test]
+async fn run_subset_should_solve_dependencies_that_were_not_queued() {
+    let solver = solver();
+    solver.enqueue_fragment(FragmentId(2)).await;
+
+    let report = solver
+        .run_subset(&[FragmentId(2)], CONCURRENCY)
+        .await
+        .unwrap();
+
+    assert_eq!(report.status, Status::Done);
+    solver
+        .assert_all_solved(&[FragmentId(2), FragmentId(3)])
+        .await;
+    assert!(!solver.is_evaluated(FragmentId(0)).await);
+}
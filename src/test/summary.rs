@@ -0,0 +1,102 @@
+use crate::{
+    problems::HashMapProblem,
+    problems::{box_dependencies, box_evaluation, ClosureProblem},
+    reexported::{test, Vec},
+    test::CONCURRENCY,
+    DetailedSummary, FragmentId, Solver,
+};
+
+#[test]
+async fn summary_should_describe_an_empty_solver() {
+    let solver = Solver::new(HashMapProblem::default());
+
+    assert_eq!(solver.detailed_summary().await, DetailedSummary::default());
+    assert_eq!(
+        solver.summary().await,
+        "Solved 0/0 (0 pending, 0 punted, 0 in-flight)",
+    );
+}
+
+#[test]
+async fn summary_should_describe_a_partially_solved_chain() {
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(2)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(3)).await;
+    // Punt 0 and 1
+    assert!(solver.step().await.unwrap());
+    assert!(solver.step().await.unwrap());
+    // Evaluate 2
+    assert!(solver.step().await.unwrap());
+
+    assert_eq!(
+        solver.detailed_summary().await,
+        DetailedSummary {
+            solved: 1,
+            pending: 2,
+            punted: 1,
+            ..DetailedSummary::default()
+        },
+    );
+    assert_eq!(
+        solver.summary().await,
+        "Solved 1/4 (2 pending, 1 punted, 0 in-flight)",
+    );
+}
+
+#[test]
+async fn summary_should_describe_a_done_solver() {
+    let solver = Solver::new(HashMapProblem::from_edges([(
+        FragmentId(0),
+        FragmentId(1),
+    )]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(
+        solver.summary().await,
+        "Solved 2/2 (0 pending, 0 punted, 0 in-flight)",
+    );
+}
+
+#[test]
+async fn summary_should_describe_a_solver_done_with_cycles() {
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(0)),
+        (FragmentId(0), FragmentId(2)),
+    ]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let _ = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(
+        solver.summary().await,
+        "Solved 1/3 (0 pending, 2 punted, 0 in-flight)",
+    );
+}
+
+#[test]
+async fn summary_should_include_failed_and_blocked_fragments() {
+    let solver = Solver::new(ClosureProblem::new(
+        |id| {
+            box_dependencies(async move {
+                if id == FragmentId(0) {
+                    Vec::from([FragmentId(1)])
+                } else {
+                    Vec::new()
+                }
+            })
+        },
+        |_| box_evaluation(async { Err("evaluation failed".into()) }),
+    ));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let report = solver.run_with_error_handler(CONCURRENCY, |_, _| ()).await;
+    assert!(report.punted.is_empty());
+
+    assert_eq!(
+        solver.summary().await,
+        "Solved 0/2 (0 pending, 0 punted, 0 in-flight, 1 failed, 1 blocked)",
+    );
+}
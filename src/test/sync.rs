@@ -0,0 +1,72 @@
+use crate::{
+    problems::HashMapProblem, test::CONCURRENCY, with_default_concurrency,
+    FragmentId, SolveReport, Solver,
+};
+use futures::executor::block_on;
+
+fn solver() -> Solver<HashMapProblem> {
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(0), FragmentId(2)),
+        (FragmentId(2), FragmentId(3)),
+        (FragmentId(4), FragmentId(5)),
+        (FragmentId(5), FragmentId(4)),
+    ]));
+    block_on(async {
+        solver.enqueue_fragment(FragmentId(0)).await;
+        solver.enqueue_fragment(FragmentId(4)).await;
+    });
+
+    solver
+}
+
+#[test]
+fn run_to_completion_sync_should_match_run() {
+    let sync_solver = solver();
+    let sync_report = sync_solver.run_to_completion_sync(CONCURRENCY).unwrap();
+
+    let async_solver = solver();
+    let async_report = block_on(async {
+        let _ = async_solver.run(CONCURRENCY).await.unwrap();
+
+        async_solver.report().await
+    });
+
+    assert_eq!(sync_report, async_report);
+    assert_eq!(
+        block_on(sync_solver.topological_order()),
+        block_on(async_solver.topological_order()),
+    );
+}
+
+#[test]
+fn run_to_completion_sync_should_report_cycles() {
+    let report: SolveReport =
+        solver().run_to_completion_sync(CONCURRENCY).unwrap();
+
+    assert_eq!(report.punted, [FragmentId(4), FragmentId(5)]);
+}
+
+#[test]
+fn default_concurrency_should_be_at_least_one() {
+    assert!(with_default_concurrency().get() >= 1);
+}
+
+#[test]
+fn run_default_should_match_run_with_default_concurrency() {
+    let default_solver = solver();
+    let default_report = block_on(default_solver.run_default()).unwrap();
+
+    let async_solver = solver();
+    let async_report = block_on(async {
+        let _ = async_solver.run(with_default_concurrency()).await.unwrap();
+
+        async_solver.report().await
+    });
+
+    assert_eq!(default_report, async_report);
+    assert_eq!(
+        block_on(default_solver.topological_order()),
+        block_on(async_solver.topological_order()),
+    );
+}
@@ -0,0 +1,168 @@
+use crate::{
+    reexported::{
+        atomic::{AtomicUsize, Ordering},
+        NonZeroUsize, Vec,
+    },
+    test::CONCURRENCY,
+    BlockingSolver, FragmentId, SyncProblem,
+};
+use std::{convert::Infallible, sync::Mutex, thread, time::Duration};
+
+// Chain where each fragment depends on the next one
+struct ChainProblem {
+    fragments: usize,
+    evaluated: Mutex<Vec<FragmentId>>,
+}
+
+impl SyncProblem for ChainProblem {
+    type Error = Infallible;
+
+    fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        if id.0 + 1 < self.fragments {
+            dependencies.push(FragmentId(id.0 + 1));
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        self.evaluated.lock().unwrap().push(id);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn sync_solver_should_evaluate_a_chain_in_order() {
+    let solver = BlockingSolver::new(ChainProblem {
+        fragments: 3,
+        evaluated: Mutex::new(Vec::new()),
+    });
+    solver.enqueue_fragment(FragmentId(0));
+    assert!(solver.run(CONCURRENCY).unwrap().punted.is_empty());
+
+    assert_eq!(
+        solver
+            .into_problem_instance()
+            .evaluated
+            .into_inner()
+            .unwrap(),
+        [FragmentId(2), FragmentId(1), FragmentId(0)],
+    );
+}
+
+#[test]
+fn sync_solver_should_report_cycles() {
+    // 0 and 1 depend on each other
+    struct CycleProblem;
+
+    impl SyncProblem for CycleProblem {
+        type Error = Infallible;
+
+        fn direct_dependencies(
+            &self,
+            id: FragmentId,
+            dependencies: &mut Vec<FragmentId>,
+        ) -> Result<(), Self::Error> {
+            dependencies.push(FragmentId(1 - id.0));
+
+            Ok(())
+        }
+
+        fn evaluate(&self, _: FragmentId) -> Result<(), Self::Error> {
+            Ok(())
+        }
+    }
+
+    let solver = BlockingSolver::new(CycleProblem);
+    let report = solver.solve_fragment(FragmentId(0), CONCURRENCY).unwrap();
+
+    assert_eq!(report.punted, [FragmentId(0), FragmentId(1)]);
+    assert_eq!(solver.punted_iter(), report.punted);
+}
+
+#[test]
+fn sync_solver_should_evaluate_fragments_concurrently() {
+    // Independent fragments that record how many evaluations overlap
+    #[derive(Default)]
+    struct SlowProblem {
+        running: AtomicUsize,
+        max_running: AtomicUsize,
+    }
+
+    impl SyncProblem for SlowProblem {
+        type Error = Infallible;
+
+        fn direct_dependencies(
+            &self,
+            _: FragmentId,
+            _: &mut Vec<FragmentId>,
+        ) -> Result<(), Self::Error> {
+            Ok(())
+        }
+
+        fn evaluate(&self, _: FragmentId) -> Result<(), Self::Error> {
+            let running = self.running.fetch_add(1, Ordering::SeqCst) + 1;
+            self.max_running.fetch_max(running, Ordering::SeqCst);
+            thread::sleep(Duration::from_millis(10));
+            self.running.fetch_sub(1, Ordering::SeqCst);
+
+            Ok(())
+        }
+    }
+
+    let solver = BlockingSolver::new(SlowProblem::default());
+    for id in 0..16 {
+        solver.enqueue_fragment(FragmentId(id));
+    }
+    assert!(solver
+        .run(NonZeroUsize::new(4).unwrap())
+        .unwrap()
+        .punted
+        .is_empty());
+
+    let max_running = solver.into_problem_instance().max_running.into_inner();
+    assert!(max_running > 1);
+    assert!(max_running <= 4);
+}
+
+#[test]
+fn sync_solver_should_stop_at_the_first_error() {
+    // Fragment 0 depends on 1, which fails
+    struct FailingProblem;
+
+    impl SyncProblem for FailingProblem {
+        type Error = FragmentId;
+
+        fn direct_dependencies(
+            &self,
+            id: FragmentId,
+            dependencies: &mut Vec<FragmentId>,
+        ) -> Result<(), Self::Error> {
+            if id == FragmentId(0) {
+                dependencies.push(FragmentId(1));
+            }
+
+            Ok(())
+        }
+
+        fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+            match id {
+                FragmentId(1) => Err(id),
+                _ => Ok(()),
+            }
+        }
+    }
+
+    let solver = BlockingSolver::new(FailingProblem);
+    assert_eq!(
+        solver.solve_fragment(FragmentId(0), CONCURRENCY),
+        Err(FragmentId(1)),
+    );
+    assert!(!solver.is_evaluated(FragmentId(0)));
+    assert!(!solver.solver().is_running());
+}
@@ -0,0 +1,203 @@
+use crate::{
+    reexported::{test, Vec},
+    test::{PetgraphProblem, CONCURRENCY},
+    FragmentId, Solver, TopologicalViolation,
+};
+use petgraph::Graph;
+
+fn two_node_cycle() -> Solver<PetgraphProblem> {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p0, ());
+
+    Solver::new(PetgraphProblem::new(dependency_graph))
+}
+
+#[test]
+async fn assert_no_cycles_should_pass_for_an_empty_solver() {
+    Solver::new(()).assert_no_cycles().await;
+}
+
+#[test]
+#[should_panic(expected = "punted fragments [FragmentId(0), FragmentId(1)]")]
+async fn assert_no_cycles_should_panic_with_cycle_members() {
+    let solver = two_node_cycle();
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let _ = solver.run(1.try_into().unwrap()).await;
+
+    solver.assert_no_cycles().await;
+}
+
+#[test]
+async fn assert_all_solved_should_ignore_order() {
+    let solver = Solver::new(PetgraphProblem::new(Graph::new()));
+    solver.assume_evaluated(FragmentId(1)).await;
+    solver.assume_evaluated(FragmentId(0)).await;
+
+    solver
+        .assert_all_solved(&[FragmentId(0), FragmentId(1)])
+        .await;
+}
+
+#[test]
+#[should_panic(expected = "expected solved fragments to be [FragmentId(0)]")]
+async fn assert_all_solved_should_panic_on_mismatch() {
+    let solver = two_node_cycle();
+    solver.enqueue_fragment(FragmentId(0)).await;
+    let _ = solver.run(1.try_into().unwrap()).await;
+
+    solver.assert_all_solved(&[FragmentId(0)]).await;
+}
+
+// 0 depends on 1 and 2, and 1 depends on 2
+fn chain_dependencies(id: FragmentId) -> Vec<FragmentId> {
+    match id.0 {
+        0 => Vec::from([FragmentId(1), FragmentId(2)]),
+        1 => Vec::from([FragmentId(2)]),
+        _ => Vec::new(),
+    }
+}
+
+fn chain() -> Solver<PetgraphProblem> {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p0, p2, ());
+    dependency_graph.add_edge(p1, p2, ());
+
+    Solver::new(PetgraphProblem::new(dependency_graph))
+}
+
+#[test]
+async fn verify_solved_order_should_accept_the_solver_order() {
+    let solver = chain();
+    solver.enqueue_fragment(FragmentId(0)).await;
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+
+    assert_eq!(
+        solver.verify_solved_order(&chain_dependencies).await,
+        Ok(())
+    );
+}
+
+#[test]
+async fn verify_solved_order_should_catch_fragments_solved_too_early() {
+    let solver = chain();
+    solver.state.lock().await.solved_order =
+        Vec::from([FragmentId(2), FragmentId(0), FragmentId(1)]);
+
+    assert_eq!(
+        solver.verify_solved_order(&chain_dependencies).await,
+        Err(TopologicalViolation {
+            fragment: FragmentId(0),
+            dependency: FragmentId(1),
+        }),
+    );
+}
+
+#[test]
+async fn verify_solved_order_should_catch_missing_dependencies() {
+    let solver = chain();
+    solver.state.lock().await.solved_order = Vec::from([FragmentId(1)]);
+
+    assert_eq!(
+        solver.verify_solved_order(&chain_dependencies).await,
+        Err(TopologicalViolation {
+            fragment: FragmentId(1),
+            dependency: FragmentId(2),
+        }),
+    );
+}
+
+#[test]
+async fn assert_dependency_graph_is_dag_should_pass_on_a_dag() {
+    use crate::problems::HashMapProblem;
+
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(0), FragmentId(2)),
+        (FragmentId(1), FragmentId(3)),
+        (FragmentId(2), FragmentId(3)),
+    ]));
+
+    solver
+        .assert_dependency_graph_is_dag(&[FragmentId(0)])
+        .await;
+    assert_eq!(solver.evaluations_attempted(), 0);
+    assert_eq!(solver.dependency_fetches(), 4);
+}
+
+#[test]
+#[should_panic(
+    expected = "fragments [FragmentId(1), FragmentId(2)] are part of a cycle"
+)]
+async fn assert_dependency_graph_is_dag_should_panic_with_cycle_members() {
+    use crate::problems::HashMapProblem;
+
+    // 0 depends on the 1 <-> 2 cycle, so it is not a cycle member itself
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(0), FragmentId(1)),
+        (FragmentId(1), FragmentId(2)),
+        (FragmentId(2), FragmentId(1)),
+        (FragmentId(2), FragmentId(3)),
+    ]));
+
+    solver
+        .assert_dependency_graph_is_dag(&[FragmentId(0)])
+        .await;
+}
+
+#[test]
+async fn inject_fake_dependency_should_order_evaluations() {
+    use crate::problems::HashMapProblem;
+
+    // 0 and 1 are independent
+    let solver = Solver::new(HashMapProblem::from_edges([
+        (FragmentId(2), FragmentId(0)),
+        (FragmentId(2), FragmentId(1)),
+    ]));
+    solver
+        .inject_fake_dependency(FragmentId(0), FragmentId(1))
+        .await;
+    solver.enqueue_fragment(FragmentId(0)).await;
+    solver.enqueue_fragment(FragmentId(1)).await;
+
+    assert!(solver.run(CONCURRENCY).await.unwrap().punted.is_empty());
+    assert_eq!(
+        solver.topological_order().await,
+        Vec::from([FragmentId(1), FragmentId(0)])
+    );
+}
+
+#[test]
+async fn inject_fake_dependency_should_block_punted_fragments() {
+    use crate::{problems::HashMapProblem, FragmentLifecycle};
+
+    let solver = Solver::new(HashMapProblem::from_edges([(
+        FragmentId(0),
+        FragmentId(2),
+    )]));
+    solver.enqueue_fragment(FragmentId(0)).await;
+    // Punt 0
+    solver.step().await.unwrap();
+    solver
+        .inject_fake_dependency(FragmentId(0), FragmentId(1))
+        .await;
+
+    solver.assume_evaluated(FragmentId(2)).await;
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Punted { pending_count: 1 }
+    );
+    solver.assume_evaluated(FragmentId(1)).await;
+    assert_eq!(
+        solver.fragment_state(FragmentId(0)).await,
+        FragmentLifecycle::Queued
+    );
+    assert!(solver.step().await.unwrap());
+    assert!(solver.is_evaluated(FragmentId(0)).await);
+}
@@ -1,7 +1,7 @@
 use crate::{
-    reexported::{test, Set},
+    reexported::test,
     test::{PetgraphProblem, CONCURRENCY},
-    Solver, Status,
+    Solver,
 };
 use petgraph::Graph;
 
@@ -12,14 +12,9 @@ async fn should_be_able_to_solve_for_one_fragment_with_no_dependencies() {
 
     let solver = Solver::new(PetgraphProblem::new(dependency_graph));
     solver.enqueue_fragment(p0.index().into()).await;
-    let punted = solver
-        .run(CONCURRENCY)
-        .await
-        .unwrap()
-        .into_iter()
-        .collect::<Set<_>>();
+    let punted = solver.run(CONCURRENCY).await.unwrap().punted;
 
-    assert_eq!(solver.status().await, Status::Done);
+    solver.assert_no_cycles().await;
     assert!(punted.is_empty());
     assert_eq!(solver.into_problem_instance().into_evaluated(), &[p0]);
 }
@@ -33,15 +28,13 @@ async fn should_be_able_to_solve_for_two_fragments_with_no_dependencies() {
     let solver = Solver::new(PetgraphProblem::new(dependency_graph));
     solver.enqueue_fragment(p0.index().into()).await;
     solver.enqueue_fragment(p1.index().into()).await;
-    let punted = solver
-        .run(CONCURRENCY)
-        .await
-        .unwrap()
-        .into_iter()
-        .collect::<Set<_>>();
+    let punted = solver.run(CONCURRENCY).await.unwrap().punted;
 
-    assert_eq!(solver.status().await, Status::Done);
+    solver.assert_no_cycles().await;
     assert!(punted.is_empty());
+    solver
+        .assert_all_solved(&[p0.index().into(), p1.index().into()])
+        .await;
     assert_eq!(
         solver.into_problem_instance().into_evaluated_set(),
         [p0, p1].into_iter().collect(),
@@ -57,14 +50,9 @@ async fn should_be_able_to_solve_for_one_fragment_with_one_dependency() {
 
     let solver = Solver::new(PetgraphProblem::new(dependency_graph));
     solver.enqueue_fragment(p0.index().into()).await;
-    let punted = solver
-        .run(CONCURRENCY)
-        .await
-        .unwrap()
-        .into_iter()
-        .collect::<Set<_>>();
+    let punted = solver.run(CONCURRENCY).await.unwrap().punted;
 
-    assert_eq!(solver.status().await, Status::Done);
+    solver.assert_no_cycles().await;
     assert!(punted.is_empty());
     assert_eq!(solver.into_problem_instance().into_evaluated(), &[p1, p0]);
 }
@@ -80,16 +68,38 @@ async fn should_be_able_to_solve_for_one_fragment_with_two_dependencies() {
 
     let solver = Solver::new(PetgraphProblem::new(dependency_graph));
     solver.enqueue_fragment(p0.index().into()).await;
-    let punted = solver
-        .run(CONCURRENCY)
-        .await
-        .unwrap()
-        .into_iter()
-        .collect::<Set<_>>();
+    let punted = solver.run(CONCURRENCY).await.unwrap().punted;
 
-    assert_eq!(solver.status().await, Status::Done);
+    solver.assert_no_cycles().await;
     assert!(punted.is_empty());
     let problem = solver.into_problem_instance();
     let evaluated = problem.into_evaluated();
-    assert!(evaluated == &[p1, p2, p0] || evaluated == &[p2, p1, p0]);
+    assert!(evaluated == [p1, p2, p0] || evaluated == [p2, p1, p0]);
+}
+
+#[test]
+async fn solve_fragment_should_be_equivalent_to_enqueue_and_run() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p2, ());
+
+    let explicit = Solver::new(PetgraphProblem::new(dependency_graph.clone()));
+    explicit.enqueue_fragment(p0.index().into()).await;
+    let explicit_punted = explicit.run(CONCURRENCY).await.unwrap().punted;
+
+    let one_shot = Solver::new(PetgraphProblem::new(dependency_graph));
+    let report = one_shot
+        .solve_fragment(p0.index().into(), CONCURRENCY)
+        .await
+        .unwrap();
+
+    assert_eq!(report.status, explicit.status().await);
+    assert_eq!(report.punted, explicit_punted);
+    assert_eq!(
+        one_shot.into_problem_instance().into_evaluated(),
+        explicit.into_problem_instance().into_evaluated(),
+    );
 }
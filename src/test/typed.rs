@@ -0,0 +1,48 @@
+use crate::{
+    reexported::{test, Set},
+    test::{PetgraphProblem, CONCURRENCY},
+    Status, TypedSolver,
+};
+use petgraph::{graph::NodeIndex, Graph};
+
+#[test]
+async fn typed_solver_should_solve_a_tree_using_node_indexes() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    let p2 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p0, p2, ());
+
+    let solver = TypedSolver::<NodeIndex<u32>, _>::new(PetgraphProblem::new(
+        dependency_graph,
+    ));
+    solver.enqueue(p0).await;
+    let report = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(report.status, Status::Done);
+    assert!(report.punted.is_empty());
+    assert!(solver.is_evaluated(p0).await);
+    assert!(solver.is_evaluated(p1).await);
+    assert!(solver.is_evaluated(p2).await);
+}
+
+#[test]
+async fn typed_solver_should_report_punted_node_indexes() {
+    let mut dependency_graph = Graph::new();
+    let p0 = dependency_graph.add_node(());
+    let p1 = dependency_graph.add_node(());
+    dependency_graph.add_edge(p0, p1, ());
+    dependency_graph.add_edge(p1, p0, ());
+
+    let solver = TypedSolver::new(PetgraphProblem::new(dependency_graph));
+    solver.enqueue(p0).await;
+    let report = solver.run(CONCURRENCY).await.unwrap();
+
+    assert_eq!(report.status, Status::DoneWithCycles);
+    assert_eq!(
+        report.punted.into_iter().collect::<Set<NodeIndex<u32>>>(),
+        [p0, p1].into_iter().collect(),
+    );
+    assert!(!solver.is_evaluated(p0).await);
+}
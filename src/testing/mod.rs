@@ -0,0 +1,207 @@
+use crate::{
+    find_dependency_path,
+    reexported::{Map, NonZeroUsize, Set, Vec, VecDeque},
+    FragmentId, Problem, Solver, Status,
+};
+
+pub use self::replay::EvaluationTrace;
+
+mod replay;
+
+/// Error returned by [`Solver::verify_solved_order`] when a fragment was solved before one of its
+/// dependencies.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct TopologicalViolation {
+    /// Fragment that was solved too early.
+    pub fragment: FragmentId,
+
+    /// Dependency of `fragment` that was solved after it, or not solved at all.
+    pub dependency: FragmentId,
+}
+
+impl<P> Solver<P> {
+    /// Panic unless the solver is [done](Status::Done). The panic message includes all punted
+    /// fragments, which are the members of any cycles if the solver is done.
+    pub async fn assert_no_cycles(&self) {
+        let status = self.status().await;
+        if status != Status::Done {
+            let mut punted = self.punted_iter().await;
+            punted.sort_unstable();
+
+            panic!(
+                "expected solver status to be Done but it is {:?} with punted fragments {:?}",
+                status, punted,
+            );
+        }
+    }
+
+    /// Panic unless exactly the fragments in `expected` were evaluated or assumed to be
+    /// evaluated.
+    pub async fn assert_all_solved(&self, expected: &[FragmentId]) {
+        let state = self.state.lock().await;
+        let expected_set = expected.iter().copied().collect::<Set<_>>();
+        let solved_set = state.solved_iter().collect::<Set<_>>();
+        if solved_set != expected_set {
+            let mut expected = expected_set.into_iter().collect::<Vec<_>>();
+            expected.sort_unstable();
+            let mut solved = solved_set.into_iter().collect::<Vec<_>>();
+            solved.sort_unstable();
+
+            panic!(
+                "expected solved fragments to be {:?} but they are {:?}",
+                expected, solved,
+            );
+        }
+    }
+
+    /// Check that [`Solver::topological_order`] is a valid topological order according to `deps`,
+    /// which must return the direct dependencies of a fragment. Every dependency of a solved
+    /// fragment must have been solved before it.
+    pub async fn verify_solved_order<F>(
+        &self,
+        deps: &F,
+    ) -> Result<(), TopologicalViolation>
+    where
+        F: Fn(FragmentId) -> Vec<FragmentId>,
+    {
+        let order = self.topological_order().await;
+        let positions = order
+            .iter()
+            .enumerate()
+            .map(|(i, &id)| (id, i))
+            .collect::<Map<_, _>>();
+
+        for (i, &fragment) in order.iter().enumerate() {
+            for dependency in deps(fragment) {
+                if positions.get(&dependency).is_none_or(|&x| x >= i) {
+                    return Err(TopologicalViolation {
+                        fragment,
+                        dependency,
+                    });
+                }
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl<P> Solver<P>
+where
+    P: Problem,
+{
+    /// Panic unless the dependencies of `roots` and everything reachable from them form a
+    /// directed acyclic graph. The panic message includes all fragments that are part of a cycle
+    /// and one example cycle.
+    ///
+    /// Dependencies are fetched as with [`Solver::expand_all_dependencies`] but nothing is
+    /// evaluated, so this can be called before the first run. Dependencies of already evaluated
+    /// fragments are not fetched.
+    pub async fn assert_dependency_graph_is_dag(&self, roots: &[FragmentId]) {
+        let discovered = self
+            .discover(roots.iter().copied(), NonZeroUsize::MIN)
+            .await;
+        let state = self.state.lock().await;
+
+        // Kahn's algorithm: repeatedly remove fragments without unremoved dependencies. Whatever
+        // is left is part of a cycle or depends on one
+        let mut pending = Map::new();
+        let mut dependents = Map::<_, Vec<_>>::new();
+        for id in discovered.iter().copied() {
+            let dependencies = state
+                .dependency_graph
+                .get(&id)
+                .into_iter()
+                .flatten()
+                .copied()
+                .filter(|x| !state.solved.contains(x))
+                .collect::<Vec<_>>();
+            for dependency in dependencies.iter().copied() {
+                dependents.entry(dependency).or_default().push(id);
+            }
+            pending.insert(id, dependencies.len());
+        }
+        let mut queue = pending
+            .iter()
+            .filter(|(_, count)| **count == 0)
+            .map(|(&id, _)| id)
+            .collect::<VecDeque<_>>();
+        while let Some(id) = queue.pop_front() {
+            pending.remove(&id);
+            for dependent in dependents.remove(&id).into_iter().flatten() {
+                if let Some(count) = pending.get_mut(&dependent) {
+                    *count -= 1;
+                    if *count == 0 {
+                        queue.push_back(dependent);
+                    }
+                }
+            }
+        }
+        if pending.is_empty() {
+            return;
+        }
+
+        let in_cycle = |id| {
+            find_dependency_path(&state, id, id, |x| pending.contains_key(&x))
+        };
+        let mut members = pending
+            .keys()
+            .copied()
+            .filter(|&id| in_cycle(id).is_some())
+            .collect::<Vec<_>>();
+        members.sort_unstable();
+        let example = in_cycle(members[0]).unwrap();
+
+        panic!(
+            "dependency graph is not a DAG: fragments {:?} are part of a cycle, such as {:?}",
+            members, example,
+        );
+    }
+
+    /// Make `dependent` depend on `dependency` without [`Problem::direct_dependencies`] returning
+    /// it, such as to test that one fragment is evaluated before another. The dependency blocks
+    /// `dependent` exactly like a fetched one: if `dependent` is already punted, it now also waits
+    /// for `dependency`, and otherwise the dependency is added when its dependencies are fetched,
+    /// as with [`Solver::add_ordering_constraint`].
+    ///
+    /// Nothing changes if `dependency` is already solved, or if `dependent` is being evaluated or
+    /// already solved.
+    pub async fn inject_fake_dependency(
+        &self,
+        dependent: FragmentId,
+        dependency: FragmentId,
+    ) {
+        let mut state = self.state.lock().await;
+        if state.solved.contains(&dependency)
+            || state.solved.contains(&dependent)
+            || state.in_flight.contains(&dependent)
+        {
+            return;
+        }
+
+        let constraints =
+            state.ordering_constraints.entry(dependent).or_default();
+        if !constraints.contains(&dependency) {
+            constraints.push(dependency);
+        }
+        if !state.punted.contains_key(&dependent) {
+            return;
+        }
+        let dependencies = state.dependency_graph.entry(dependent).or_default();
+        if dependencies.contains(&dependency) {
+            return;
+        }
+        dependencies.push(dependency);
+        *state.punted.get_mut(&dependent).unwrap() += 1;
+        state
+            .pending_on
+            .entry(dependency)
+            .or_default()
+            .push(dependent);
+        if !state.punted.contains_key(&dependency)
+            && !state.in_flight.contains(&dependency)
+        {
+            self.push_to_solve(dependency, &mut state);
+        }
+    }
+}
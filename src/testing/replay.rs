@@ -0,0 +1,124 @@
+use crate::{
+    reexported::{atomic::Ordering, Vec},
+    FragmentId, Problem, SolveReport, Solver,
+};
+
+/// Dependencies fetched and fragments evaluated during a run, in the order they happened.
+/// Recorded by [`TracingProblem`](crate::problems::TracingProblem) and replayed with
+/// [`Solver::replay_from_trace`].
+#[derive(Clone, Debug, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct EvaluationTrace {
+    /// Every [`Problem::direct_dependencies`] call and the dependencies it returned, in call
+    /// order. Fragments appear once per fetch, so punted fragments usually appear more than once.
+    pub dep_responses: Vec<(FragmentId, Vec<FragmentId>)>,
+
+    /// Fragments that were successfully evaluated, in the order their evaluations completed.
+    pub eval_order: Vec<FragmentId>,
+}
+
+impl<P> Solver<P>
+where
+    P: Problem,
+{
+    /// Create a new [`Solver`] for `problem` and drive it in the exact order recorded in `trace`:
+    /// first fetch all dependencies in [`EvaluationTrace::dep_responses`] order, then evaluate all
+    /// fragments in [`EvaluationTrace::eval_order`] order. Useful to check that a mock problem
+    /// behaves like the problem that recorded the trace.
+    ///
+    /// Fragments whose dependencies were fetched but that were not evaluated are reported as
+    /// punted. Returns the first error from fetching dependencies or evaluating, if any.
+    ///
+    /// # Panics
+    ///
+    /// Panics if `problem` returns dependencies that differ from the ones recorded in `trace`, or
+    /// if `trace` evaluates a fragment before its dependencies.
+    pub async fn replay_from_trace(
+        trace: &EvaluationTrace,
+        problem: P,
+    ) -> Result<SolveReport, P::Error> {
+        let solver = Self::new(problem);
+
+        let run = solver.begin_run().await;
+        let res = solver.replay(trace).await;
+        let report = run.finish().await;
+        res?;
+
+        Ok(report)
+    }
+
+    async fn replay(&self, trace: &EvaluationTrace) -> Result<(), P::Error> {
+        let mut dependencies = Vec::new();
+        for (id, recorded) in &trace.dep_responses {
+            dependencies.clear();
+            self.dependency_fetches.fetch_add(1, Ordering::Relaxed);
+            let fetched = self
+                .problem_instance
+                .direct_dependencies(*id, &mut dependencies)
+                .await;
+            if let Err(err) = fetched {
+                self.state.lock().await.failed.insert(*id);
+
+                return Err(err);
+            }
+            assert_eq!(
+                &dependencies, recorded,
+                "dependencies of fragment {:?} diverged from the trace",
+                id,
+            );
+            self.state
+                .lock()
+                .await
+                .dependency_graph
+                .insert(*id, dependencies.clone());
+        }
+
+        for id in trace.eval_order.iter().copied() {
+            {
+                let state = self.state.lock().await;
+                let dependencies =
+                    state.dependency_graph.get(&id).unwrap_or_else(|| {
+                        panic!(
+                            "fragment {:?} was evaluated before its dependencies were fetched",
+                            id,
+                        )
+                    });
+                if let Some(dependency) =
+                    dependencies.iter().find(|x| !state.solved.contains(x))
+                {
+                    panic!(
+                        "fragment {:?} was evaluated before its dependency {:?}",
+                        id, dependency,
+                    );
+                }
+            }
+
+            self.evaluations_attempted.fetch_add(1, Ordering::Relaxed);
+            if let Err(err) = self.problem_instance.evaluate(id).await {
+                self.state.lock().await.failed.insert(id);
+
+                return Err(err);
+            }
+            self.mark_solved(id, &mut *self.state.lock().await);
+        }
+
+        let mut state = self.state.lock().await;
+        let punted = state
+            .dependency_graph
+            .iter()
+            .filter(|(id, _)| !state.solved.contains(id))
+            .map(|(&id, dependencies)| {
+                let pending = dependencies
+                    .iter()
+                    .filter(|x| !state.solved.contains(x))
+                    .count();
+
+                (id, pending)
+            })
+            .collect::<Vec<_>>();
+        state.punted.extend(punted);
+        Self::notify_if_done(&mut state);
+
+        Ok(())
+    }
+}
@@ -0,0 +1,199 @@
+use crate::{
+    reexported::{Error, NonZeroUsize, PhantomData, Vec},
+    FragmentId, Problem, Solver, Status,
+};
+use core::fmt;
+
+/// Wrapper around [`Solver`] that uses a domain-specific ID type instead of [`FragmentId`].
+///
+/// Only IDs are converted. The wrapped [`Problem`] still works with [`FragmentId`]s.
+pub struct TypedSolver<Id, P> {
+    inner: Solver<P>,
+    _phantom: PhantomData<Id>,
+}
+
+/// Typed version of [`SolveReport`](crate::SolveReport) returned by [`TypedSolver::run`].
+#[derive(Clone, Debug, PartialEq, Eq)]
+#[must_use]
+pub struct TypedSolveReport<Id> {
+    /// [`Status`] of the solver after the run.
+    pub status: Status,
+
+    /// Fragments that were punted when the run ended.
+    pub punted: Vec<Id>,
+}
+
+impl<Id, P> TypedSolver<Id, P>
+where
+    Id: Into<FragmentId> + From<FragmentId> + Copy,
+{
+    /// Create a new [`TypedSolver`] instance for a [`Problem`].
+    pub fn new(problem_instance: P) -> Self {
+        Self::from_solver(Solver::new(problem_instance))
+    }
+
+    /// Wrap an existing [`Solver`].
+    pub fn from_solver(inner: Solver<P>) -> Self {
+        Self {
+            inner,
+            _phantom: PhantomData,
+        }
+    }
+
+    /// Get the wrapped [`Solver`].
+    pub fn solver(&self) -> &Solver<P> {
+        &self.inner
+    }
+
+    /// Consume `self` and return the wrapped [`Solver`].
+    pub fn into_solver(self) -> Solver<P> {
+        self.inner
+    }
+
+    /// Typed version of [`Solver::enqueue_fragment`].
+    pub async fn enqueue(&self, id: Id) {
+        self.inner.enqueue_fragment(id.into()).await;
+    }
+
+    /// Typed version of [`Solver::is_evaluated`].
+    pub async fn is_evaluated(&self, id: Id) -> bool {
+        self.inner.is_evaluated(id.into()).await
+    }
+
+    /// Typed version of [`Solver::status`].
+    pub async fn status(&self) -> Status {
+        self.inner.status().await
+    }
+}
+
+impl<Id, P> TypedSolver<Id, P>
+where
+    Id: Into<FragmentId> + From<FragmentId> + Copy,
+    P: Problem,
+{
+    /// Typed version of [`Solver::run`] that returns a [`TypedSolveReport`].
+    pub async fn run(
+        &self,
+        concurrency: NonZeroUsize,
+    ) -> Result<TypedSolveReport<Id>, P::Error> {
+        let punted = self.inner.run_detailed(concurrency).await?.punted;
+
+        Ok(TypedSolveReport {
+            status: self.inner.status().await,
+            punted: punted.into_iter().map(Id::from).collect(),
+        })
+    }
+}
+
+/// Error returned when converting a [`FragmentId`] into an ID declared with
+/// [`declare_fragment_id`](crate::declare_fragment_id) from a different namespace.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, Hash)]
+pub struct FragmentIdNamespaceError {
+    /// Namespace of the target ID type.
+    pub expected: usize,
+
+    /// Namespace of the converted [`FragmentId`].
+    pub found: usize,
+}
+
+impl fmt::Display for FragmentIdNamespaceError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "expected a fragment ID in namespace {} but found namespace {}",
+            self.expected, self.found
+        )
+    }
+}
+
+impl Error for FragmentIdNamespaceError {}
+
+/// Declare a domain-specific fragment ID type that wraps a `usize`:
+///
+/// ```
+/// # use gpp_solver::{declare_fragment_id, FragmentId};
+/// declare_fragment_id!(pub TypeId, namespace = 1);
+/// declare_fragment_id!(pub ValueId, namespace = 2);
+///
+/// let id = FragmentId::from(TypeId(3));
+/// assert_eq!(TypeId::try_from(id), Ok(TypeId(3)));
+/// assert!(ValueId::try_from(id).is_err());
+/// ```
+///
+/// The generated type converts into a [`FragmentId`](crate::FragmentId) with the namespace
+/// encoded in the high bits, and back with `TryFrom`, which fails with a
+/// [`FragmentIdNamespaceError`] if the namespace doesn't match. The namespace defaults to `0` and
+/// must fit in [`FragmentId::NAMESPACE_BITS`](crate::FragmentId::NAMESPACE_BITS) bits. IDs from
+/// different domains can then share the same [`Solver`] without clashing.
+///
+/// Different domains are different types, so they can't be mixed up:
+///
+/// ```compile_fail
+/// # use gpp_solver::declare_fragment_id;
+/// declare_fragment_id!(TypeId, namespace = 1);
+/// declare_fragment_id!(ValueId, namespace = 2);
+///
+/// let id: TypeId = ValueId(3);
+/// ```
+///
+/// ```compile_fail
+/// # use gpp_solver::declare_fragment_id;
+/// declare_fragment_id!(TypeId, namespace = 1);
+/// declare_fragment_id!(ValueId, namespace = 2);
+///
+/// fn type_of(id: TypeId) {}
+///
+/// type_of(ValueId(3));
+/// ```
+///
+/// Converting to a [`FragmentId`](crate::FragmentId) panics if the wrapped value overlaps the
+/// namespace bits.
+///
+/// IDs with a nonzero namespace don't work with the `dense-ids` feature. Their high bits are
+/// set, so storing a solved one in the bitfield would need memory for every smaller ID, which
+/// aborts the process. Only the default namespace `0` can be used with `dense-ids`.
+#[macro_export]
+macro_rules! declare_fragment_id {
+    ($(#[$attr:meta])* $vis:vis $name:ident $(, namespace = $namespace:expr)? $(,)?) => {
+        $(#[$attr])*
+        #[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+        $vis struct $name(pub usize);
+
+        impl $name {
+            /// Namespace of this ID type.
+            pub const NAMESPACE: usize = $crate::declare_fragment_id!(@namespace $($namespace)?);
+        }
+
+        const _: () = assert!(
+            $name::NAMESPACE >> $crate::FragmentId::NAMESPACE_BITS == 0,
+            "fragment ID namespace out of range"
+        );
+
+        impl ::core::convert::From<$name> for $crate::FragmentId {
+            fn from(id: $name) -> Self {
+                $crate::FragmentId::in_namespace($name::NAMESPACE, id.0)
+            }
+        }
+
+        impl ::core::convert::TryFrom<$crate::FragmentId> for $name {
+            type Error = $crate::FragmentIdNamespaceError;
+
+            fn try_from(id: $crate::FragmentId) -> ::core::result::Result<Self, Self::Error> {
+                if id.namespace() == Self::NAMESPACE {
+                    ::core::result::Result::Ok(Self(id.local_id()))
+                } else {
+                    ::core::result::Result::Err($crate::FragmentIdNamespaceError {
+                        expected: Self::NAMESPACE,
+                        found: id.namespace(),
+                    })
+                }
+            }
+        }
+    };
+    (@namespace) => {
+        0
+    };
+    (@namespace $namespace:expr) => {
+        $namespace
+    };
+}
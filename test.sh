@@ -12,3 +12,4 @@ cargo test --no-default-features --features async-std-lock,std
 cargo test --no-default-features --features futures-lock
 cargo test --no-default-features --features tokio-lock
 cargo test --no-default-features --features async-std-lock
+cargo test --no-default-features --features futures-lock,dense-ids
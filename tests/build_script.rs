@@ -0,0 +1,54 @@
+//! Uses the solver the way a build script would: from plain synchronous code, through the public
+//! API only, without any async runtime.
+
+#![cfg(feature = "sync-only")]
+
+use gpp_solver::{BlockingSolver, FragmentId, SyncProblem};
+use std::{convert::Infallible, num::NonZeroUsize, sync::Mutex};
+
+// 0 -> 1 -> 2
+struct ChainProblem {
+    evaluated: Mutex<Vec<FragmentId>>,
+}
+
+impl SyncProblem for ChainProblem {
+    type Error = Infallible;
+
+    fn direct_dependencies(
+        &self,
+        id: FragmentId,
+        dependencies: &mut Vec<FragmentId>,
+    ) -> Result<(), Self::Error> {
+        if id.0 < 2 {
+            dependencies.push(FragmentId(id.0 + 1));
+        }
+
+        Ok(())
+    }
+
+    fn evaluate(&self, id: FragmentId) -> Result<(), Self::Error> {
+        self.evaluated.lock().unwrap().push(id);
+
+        Ok(())
+    }
+}
+
+#[test]
+fn build_script_should_evaluate_a_chain_in_order() {
+    let solver = BlockingSolver::new(ChainProblem {
+        evaluated: Mutex::new(Vec::new()),
+    });
+    let report = solver
+        .solve_fragment(FragmentId(0), NonZeroUsize::new(4).unwrap())
+        .unwrap();
+    assert!(report.punted.is_empty());
+
+    assert_eq!(
+        solver
+            .into_problem_instance()
+            .evaluated
+            .into_inner()
+            .unwrap(),
+        [FragmentId(2), FragmentId(1), FragmentId(0)],
+    );
+}